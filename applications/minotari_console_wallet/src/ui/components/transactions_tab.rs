@@ -217,6 +217,7 @@ impl TransactionsTab {
             let mut transaction_status = tx.status.clone();
             let mut transaction_type = if tx.burn { TxType::Burn } else { TxType::PaymentToOther };
             if let Some(
+                PaymentId::TypeOnly(tx_type) |
                 PaymentId::Open { tx_type, .. } |
                 PaymentId::AddressAndData { tx_type, .. } |
                 PaymentId::TransactionInfo { tx_type, .. },
@@ -230,7 +231,9 @@ impl TransactionsTab {
                 transaction_type = tx_type.clone();
             };
 
-            if let Some(PaymentId::Open { .. } | PaymentId::AddressAndData { .. }) = tx.payment_id.clone() {
+            if let Some(PaymentId::TypeOnly(_) | PaymentId::Open { .. } | PaymentId::AddressAndData { .. }) =
+                tx.payment_id.clone()
+            {
                 if transaction_type == TxType::PaymentToSelf && tx.source_address != tx.destination_address {
                     transaction_type = TxType::PaymentToOther;
                 }
@@ -1210,7 +1210,8 @@ impl CompletedTransactionInfo {
         // Faux transactions for scanned change outputs must correspond to the original transaction
         let burn = if tx.transaction.body.contains_burn() {
             true
-        } else if let PaymentId::Open { tx_type, .. } |
+        } else if let PaymentId::TypeOnly(tx_type) |
+        PaymentId::Open { tx_type, .. } |
         PaymentId::AddressAndData { tx_type, .. } |
         PaymentId::TransactionInfo { tx_type, .. } = tx.payment_id.clone()
         {
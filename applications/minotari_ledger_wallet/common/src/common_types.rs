@@ -22,6 +22,8 @@ pub enum AppSW {
     RandomNonceFail = 0xB00B,
     BadBranchKey = 0xB00C,
     MetadataSignatureFail = 0xB00D,
+    InvalidAccountLength = 0xB00E,
+    IndexOutOfRange = 0xB00F,
     WrongApduLength = 0x6e03, // See ledger-device-rust-sdk/ledger_device_sdk/src/io.rs:16
     UserCancelled = 0x6e04,   // See ledger-device-rust-sdk/ledger_device_sdk/src/io.rs:16
     Ok = 0x9000,
@@ -45,6 +47,8 @@ impl TryFrom<u16> for AppSW {
             0xB00B => Ok(AppSW::RandomNonceFail),
             0xB00C => Ok(AppSW::BadBranchKey),
             0xB00D => Ok(AppSW::MetadataSignatureFail),
+            0xB00E => Ok(AppSW::InvalidAccountLength),
+            0xB00F => Ok(AppSW::IndexOutOfRange),
             0x6e03 => Ok(AppSW::WrongApduLength),
             0x6e04 => Ok(AppSW::UserCancelled),
             0x9000 => Ok(AppSW::Ok),
@@ -69,6 +73,7 @@ pub enum Instruction {
     GetScriptSchnorrSignature = 0x10,
     GetOneSidedMetadataSignature = 0x11,
     GetScriptSignatureManaged = 0x12,
+    GetOneSidedPaymentKey = 0x13,
 }
 
 impl Instruction {
@@ -90,6 +95,7 @@ impl Instruction {
             0x10 => Some(Instruction::GetScriptSchnorrSignature),
             0x11 => Some(Instruction::GetOneSidedMetadataSignature),
             0x12 => Some(Instruction::GetScriptSignatureManaged),
+            0x13 => Some(Instruction::GetOneSidedPaymentKey),
             _ => None,
         }
     }
@@ -153,6 +159,8 @@ mod test {
             (0xB00B, AppSW::RandomNonceFail),
             (0xB00C, AppSW::BadBranchKey),
             (0xB00D, AppSW::MetadataSignatureFail),
+            (0xB00E, AppSW::InvalidAccountLength),
+            (0xB00F, AppSW::IndexOutOfRange),
             (0x6e03, AppSW::WrongApduLength),
             (0x6e04, AppSW::UserCancelled),
             (0x9000, AppSW::Ok),
@@ -199,6 +207,12 @@ mod test {
                 AppSW::MetadataSignatureFail => {
                     assert_eq!(AppSW::try_from(*value).unwrap(), *expected_app_sw);
                 },
+                AppSW::InvalidAccountLength => {
+                    assert_eq!(AppSW::try_from(*value).unwrap(), *expected_app_sw);
+                },
+                AppSW::IndexOutOfRange => {
+                    assert_eq!(AppSW::try_from(*value).unwrap(), *expected_app_sw);
+                },
                 AppSW::WrongApduLength => {
                     assert_eq!(AppSW::try_from(*value).unwrap(), *expected_app_sw);
                 },
@@ -227,6 +241,7 @@ mod test {
             (0x10, Instruction::GetScriptSchnorrSignature),
             (0x11, Instruction::GetOneSidedMetadataSignature),
             (0x12, Instruction::GetScriptSignatureManaged),
+            (0x13, Instruction::GetOneSidedPaymentKey),
         ];
 
         for (expected_byte, instruction) in &mappings {
@@ -279,6 +294,10 @@ mod test {
                     assert_eq!(instruction.as_byte(), *expected_byte);
                     assert_eq!(Instruction::from_byte(*expected_byte), Some(*instruction));
                 },
+                Instruction::GetOneSidedPaymentKey => {
+                    assert_eq!(instruction.as_byte(), *expected_byte);
+                    assert_eq!(Instruction::from_byte(*expected_byte), Some(*instruction));
+                },
             }
         }
     }
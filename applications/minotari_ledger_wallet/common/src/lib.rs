@@ -10,4 +10,12 @@ extern crate alloc;
 
 pub mod common_types;
 mod utils;
-pub use utils::{get_public_spend_key_bytes_from_tari_dual_address, tari_dual_address_display, TARI_DUAL_ADDRESS_SIZE};
+pub use utils::{
+    account_from_bytes,
+    get_public_spend_key_bytes_from_tari_dual_address,
+    is_valid_payment_key_index,
+    tari_dual_address_display,
+    ACCOUNT_SIZE,
+    MAX_PAYMENT_KEY_INDEX,
+    TARI_DUAL_ADDRESS_SIZE,
+};
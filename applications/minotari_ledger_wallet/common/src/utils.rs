@@ -33,6 +33,24 @@ pub fn u16_to_string(number: u16) -> String {
     String::from_utf8_lossy(&buffer[..pos]).to_string()
 }
 
+/// The size of a serialized account index
+pub const ACCOUNT_SIZE: usize = 8;
+
+/// Parse a little-endian account index from APDU data, which must be exactly `ACCOUNT_SIZE` bytes long
+pub fn account_from_bytes(data: &[u8]) -> Option<u64> {
+    let account_bytes: [u8; ACCOUNT_SIZE] = data.try_into().ok()?;
+    Some(u64::from_le_bytes(account_bytes))
+}
+
+/// The largest index that can be used to derive a one-sided payment key. Indices are used as hardened BIP32 path
+/// components, so they must not have the hardened bit set.
+pub const MAX_PAYMENT_KEY_INDEX: u64 = 0x7FFF_FFFF;
+
+/// Whether `index` may be used to derive a one-sided payment key
+pub fn is_valid_payment_key_index(index: u64) -> bool {
+    index <= MAX_PAYMENT_KEY_INDEX
+}
+
 /// The Tari dual address size
 pub const TARI_DUAL_ADDRESS_SIZE: usize = 67;
 
@@ -104,3 +122,29 @@ fn mask() -> u8 {
 
     mask
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_an_account_of_the_exact_size() {
+        assert_eq!(account_from_bytes(&42u64.to_le_bytes()), Some(42));
+        assert_eq!(account_from_bytes(&u64::MAX.to_le_bytes()), Some(u64::MAX));
+    }
+
+    #[test]
+    fn it_rejects_account_data_of_the_wrong_size() {
+        assert_eq!(account_from_bytes(&[]), None);
+        assert_eq!(account_from_bytes(&[1u8; ACCOUNT_SIZE - 1]), None);
+        assert_eq!(account_from_bytes(&[1u8; ACCOUNT_SIZE + 1]), None);
+    }
+
+    #[test]
+    fn it_validates_payment_key_index_bounds() {
+        assert!(is_valid_payment_key_index(0));
+        assert!(is_valid_payment_key_index(MAX_PAYMENT_KEY_INDEX));
+        assert!(!is_valid_payment_key_index(MAX_PAYMENT_KEY_INDEX + 1));
+        assert!(!is_valid_payment_key_index(u64::MAX));
+    }
+}
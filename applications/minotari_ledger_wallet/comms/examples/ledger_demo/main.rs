@@ -16,11 +16,13 @@
 /// `cargo run --release --example ledger_demo`
 /// -----------------------------------------------------------------------------------------------
 use dialoguer::{theme::ColorfulTheme, Select};
+use minotari_ledger_wallet_common::MAX_PAYMENT_KEY_INDEX;
 use minotari_ledger_wallet_comms::{
     accessor_methods::{
         ledger_get_app_name,
         ledger_get_dh_shared_secret,
         ledger_get_one_sided_metadata_signature,
+        ledger_get_one_sided_payment_key,
         ledger_get_public_key,
         ledger_get_public_spend_key,
         ledger_get_raw_schnorr_signature,
@@ -113,6 +115,18 @@ fn main() {
         },
     }
 
+    // GetOneSidedPaymentKey
+    println!("\ntest: GetOneSidedPaymentKey");
+    for index in [0, 1, MAX_PAYMENT_KEY_INDEX] {
+        match ledger_get_one_sided_payment_key(account, index) {
+            Ok(payment_key) => println!("payment_key:    {}", payment_key.to_hex()),
+            Err(e) => {
+                println!("\nError: {}\n", e);
+                return;
+            },
+        }
+    }
+
     // GetPublicKey
     println!("\ntest: GetPublicKey");
     let index = OsRng.next_u64();
@@ -23,7 +23,11 @@
 use std::sync::Mutex;
 
 use log::debug;
-use minotari_ledger_wallet_common::common_types::{AppSW, Instruction};
+use minotari_ledger_wallet_common::{
+    common_types::{AppSW, Instruction},
+    is_valid_payment_key_index,
+    MAX_PAYMENT_KEY_INDEX,
+};
 use once_cell::sync::Lazy;
 use rand::{rngs::OsRng, RngCore};
 use semver::Version;
@@ -230,6 +234,37 @@ pub fn ledger_get_public_spend_key(account: u64) -> Result<PublicKey, LedgerDevi
     }
 }
 
+/// Get the public key of a one-sided payment or subaddress at the given index from the ledger device
+pub fn ledger_get_one_sided_payment_key(account: u64, index: u64) -> Result<PublicKey, LedgerDeviceError> {
+    debug!(
+        target: LOG_TARGET,
+        "ledger_get_one_sided_payment_key: account '{}', index '{}'", account, index
+    );
+    if !is_valid_payment_key_index(index) {
+        return Err(LedgerDeviceError::Processing(format!(
+            "GetOneSidedPaymentKey: index {} exceeds the maximum of {}",
+            index, MAX_PAYMENT_KEY_INDEX
+        )));
+    }
+    verify_ledger_application()?;
+
+    let data = index.to_le_bytes().to_vec();
+    match Command::<Vec<u8>>::build_command(account, Instruction::GetOneSidedPaymentKey, data).execute() {
+        Ok(result) => {
+            if result.data().len() < 33 {
+                return Err(LedgerDeviceError::Processing(format!(
+                    "GetOneSidedPaymentKey: expected 33 bytes, got {} ({:?})",
+                    result.data().len(),
+                    AppSW::try_from(result.retcode())?
+                )));
+            }
+            let public_key = PublicKey::from_canonical_bytes(&result.data()[1..33])?;
+            Ok(public_key)
+        },
+        Err(e) => Err(LedgerDeviceError::Processing(format!("GetOneSidedPaymentKey: {}", e))),
+    }
+}
+
 /// Get a public key from the ledger device
 pub fn ledger_get_public_key(
     account: u64,
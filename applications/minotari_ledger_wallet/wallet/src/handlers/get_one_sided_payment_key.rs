@@ -0,0 +1,64 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use ledger_device_sdk::io::Comm;
+use minotari_ledger_wallet_common::{is_valid_payment_key_index, ACCOUNT_SIZE};
+use tari_crypto::{keys::PublicKey, ristretto::RistrettoPublicKey, tari_utilities::ByteArray};
+
+use crate::{
+    utils::{derive_from_bip32_key, show_error_message},
+    AppSW,
+    KeyType,
+    RESPONSE_VERSION,
+};
+
+/// Derive the public spend key for a one-sided payment or subaddress at a caller-supplied index.
+///
+/// Expects the account followed by the derivation index, both as little-endian `u64`s.
+pub fn handler_get_one_sided_payment_key(comm: &mut Comm) -> Result<(), AppSW> {
+    let data = comm.get_data().map_err(|_| AppSW::WrongApduLength)?;
+    if data.len() != 2 * ACCOUNT_SIZE {
+        show_error_message("Invalid data length");
+        return Err(AppSW::WrongApduLength);
+    }
+
+    let mut account_bytes = [0u8; ACCOUNT_SIZE];
+    account_bytes.clone_from_slice(&data[0..ACCOUNT_SIZE]);
+    let account = u64::from_le_bytes(account_bytes);
+    let mut index_bytes = [0u8; ACCOUNT_SIZE];
+    index_bytes.clone_from_slice(&data[ACCOUNT_SIZE..]);
+    let index = u64::from_le_bytes(index_bytes);
+    if !is_valid_payment_key_index(index) {
+        show_error_message("Index out of range");
+        return Err(AppSW::IndexOutOfRange);
+    }
+
+    // Derivation errors are already displayed on the device
+    let k = derive_from_bip32_key(account, index, KeyType::Spend)?;
+    let pk = RistrettoPublicKey::from_secret_key(&k);
+
+    comm.append(&[RESPONSE_VERSION]); // version
+    comm.append(pk.as_bytes());
+    comm.reply_ok();
+
+    Ok(())
+}
@@ -16,6 +16,7 @@ mod app_ui {
 mod handlers {
     pub mod get_dh_shared_secret;
     pub mod get_one_sided_metadata_signature;
+    pub mod get_one_sided_payment_key;
     pub mod get_public_key;
     pub mod get_public_spend_key;
     pub mod get_schnorr_signature;
@@ -28,6 +29,7 @@ use app_ui::menu::ui_menu_main;
 use handlers::{
     get_dh_shared_secret::handler_get_dh_shared_secret,
     get_one_sided_metadata_signature::handler_get_one_sided_metadata_signature,
+    get_one_sided_payment_key::handler_get_one_sided_payment_key,
     get_public_key::handler_get_public_key,
     get_public_spend_key::handler_get_public_spend_key,
     get_schnorr_signature::{handler_get_raw_schnorr_signature, handler_get_script_schnorr_signature},
@@ -71,6 +73,8 @@ pub enum AppSW {
     RandomNonceFail = AppSWMapping::RandomNonceFail as u16,
     BadBranchKey = AppSWMapping::BadBranchKey as u16,
     MetadataSignatureFail = AppSWMapping::MetadataSignatureFail as u16,
+    InvalidAccountLength = AppSWMapping::InvalidAccountLength as u16,
+    IndexOutOfRange = AppSWMapping::IndexOutOfRange as u16,
     WrongApduLength = StatusWords::BadLen as u16, // See ledger-device-rust-sdk/ledger_device_sdk/src/io.rs:16
     UserCancelled = StatusWords::UserCancelled as u16, // See ledger-device-rust-sdk/ledger_device_sdk/src/io.rs:16
     Ok = AppSWMapping::Ok as u16,
@@ -97,6 +101,7 @@ pub enum Instruction {
     GetRawSchnorrSignature,
     GetScriptSchnorrSignature,
     GetOneSidedMetadataSignature,
+    GetOneSidedPaymentKey,
 }
 
 const P2_MORE: u8 = 0x01;
@@ -172,6 +177,7 @@ impl TryFrom<ApduHeader> for Instruction {
             (InstructionMapping::GetRawSchnorrSignature, 0, 0) => Ok(Instruction::GetRawSchnorrSignature),
             (InstructionMapping::GetScriptSchnorrSignature, 0, 0) => Ok(Instruction::GetScriptSchnorrSignature),
             (InstructionMapping::GetOneSidedMetadataSignature, 0, 0) => Ok(Instruction::GetOneSidedMetadataSignature),
+            (InstructionMapping::GetOneSidedPaymentKey, 0, 0) => Ok(Instruction::GetOneSidedPaymentKey),
             (InstructionMapping::GetScriptSchnorrSignature, _, _) => Err(AppSW::WrongP1P2),
             (_, _, _) => Err(AppSW::InsNotSupported),
         }
@@ -264,5 +270,6 @@ fn handle_apdu(comm: &mut Comm, ins: Instruction, offset_ctx: &mut ScriptOffsetC
         Instruction::GetRawSchnorrSignature => handler_get_raw_schnorr_signature(comm),
         Instruction::GetScriptSchnorrSignature => handler_get_script_schnorr_signature(comm),
         Instruction::GetOneSidedMetadataSignature => handler_get_one_sided_metadata_signature(comm),
+        Instruction::GetOneSidedPaymentKey => handler_get_one_sided_payment_key(comm),
     }
 }
@@ -178,6 +178,18 @@ fn get_raw_key_hash(path: &[u32]) -> Result<Zeroizing<[u8; 64]>, String> {
     Ok(raw_key_hashed)
 }
 
+/// Display an error message on the device.
+pub fn show_error_message(msg: &str) {
+    #[cfg(not(any(target_os = "stax", target_os = "flex")))]
+    {
+        SingleMessage::new(msg).show_and_wait();
+    }
+    #[cfg(any(target_os = "stax", target_os = "flex"))]
+    {
+        NbglStatus::new().text(msg).show(false);
+    }
+}
+
 /// Derive a secret key from a BIP32 path. In case of an error, display an interactive message on the device.
 pub fn derive_from_bip32_key(
     u64_account: u64,
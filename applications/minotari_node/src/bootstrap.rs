@@ -43,7 +43,7 @@ use tari_core::{
         chain_metadata_service::ChainMetadataServiceInitializer,
         service::BaseNodeServiceInitializer,
         state_machine_service::initializer::BaseNodeStateMachineInitializer,
-        tari_pulse_service::TariPulseServiceInitializer,
+        tari_pulse_service::{TariPulseConfig, TariPulseServiceInitializer},
         LocalNodeCommsInterface,
         StateMachineHandle,
     },
@@ -158,9 +158,13 @@ where B: BlockchainBackend + 'static
                 },
                 peer_message_subscriptions,
             ))
-            .add_initializer(MonitorPeersInitializer::new(
-                base_node_config.metadata_auto_ping_interval,
-            ))
+            .add_initializer(
+                MonitorPeersInitializer::new(
+                    base_node_config.metadata_auto_ping_interval,
+                    base_node_config.monitor_peers_cull_policy,
+                )
+                .with_config(base_node_config.monitor_peers.clone()),
+            )
             .add_initializer(ChainMetadataServiceInitializer)
             .add_initializer(BaseNodeStateMachineInitializer::new(
                 self.db.clone().into(),
@@ -170,10 +174,27 @@ where B: BlockchainBackend + 'static
                 self.randomx_factory,
                 self.app_config.base_node.bypass_range_proof_verification,
             ))
-            .add_initializer(TariPulseServiceInitializer::new(
-                base_node_config.tari_pulse_interval,
-                base_node_config.network,
-            ))
+            .add_initializer(TariPulseServiceInitializer::with_config(TariPulseConfig {
+                enabled: base_node_config.tari_pulse_enabled,
+                check_interval: base_node_config.tari_pulse_interval,
+                network: base_node_config.network,
+                log_raw_answers: base_node_config.tari_pulse_log_raw_answers,
+                initial_delay: base_node_config.tari_pulse_initial_delay,
+                consecutive_failures_before_notify: base_node_config.tari_pulse_consecutive_failures_before_notify,
+                dns_resolvers: base_node_config.tari_pulse_dns_resolvers.clone(),
+                legacy_trust_anchor_deadline: base_node_config.tari_pulse_legacy_trust_anchor_deadline,
+                anchor_review_date: base_node_config.tari_pulse_anchor_review_date,
+                history_file: base_node_config
+                    .tari_pulse_history_file
+                    .clone()
+                    .filter(|path| !path.as_os_str().is_empty()),
+                history_file_max_size: base_node_config.tari_pulse_history_file_max_size,
+                checkpoint_file: base_node_config.tari_pulse_checkpoint_file.clone(),
+                checkpoint_file_public_key: base_node_config.tari_pulse_checkpoint_file_public_key.clone(),
+                prefer_file: base_node_config.tari_pulse_prefer_checkpoint_file,
+                ..Default::default()
+            })
+            .with_profiles(base_node_config.tari_pulse_profiles.clone()))
             .build()
             .await?;
 
@@ -51,6 +51,7 @@ mod rewind_blockchain;
 mod search_kernel;
 mod search_utxo;
 mod status;
+mod test_all_peers;
 mod test_peer_liveness;
 mod unban_all_peers;
 mod version;
@@ -88,7 +89,7 @@ pub use watch_command::WatchCommand;
 
 use crate::{
     builder::BaseNodeContext,
-    commands::{nom_parser::ParsedCommand, parser::FromHex},
+    commands::{markers::Markers, nom_parser::ParsedCommand, parser::FromHex},
     ApplicationConfig,
 };
 
@@ -120,6 +121,7 @@ pub enum Command {
     RewindBlockchain(rewind_blockchain::Args),
     AddPeer(add_peer::ArgsAddPeer),
     TestPeerLiveness(test_peer_liveness::ArgsTestPeerLiveness),
+    TestAllPeers(test_all_peers::Args),
     BanPeer(ban_peer::ArgsBan),
     UnbanPeer(ban_peer::ArgsUnban),
     UnbanAllPeers(unban_all_peers::Args),
@@ -174,6 +176,7 @@ pub struct CommandContext {
     state_machine_info: watch::Receiver<StatusInfo>,
     pub software_updater: SoftwareUpdaterHandle,
     last_time_full: Instant,
+    no_emoji: bool,
     pub shutdown: Shutdown,
 }
 
@@ -194,10 +197,16 @@ impl CommandContext {
             state_machine_info: ctx.get_state_machine_info_channel(),
             software_updater: ctx.software_updater(),
             last_time_full: Instant::now(),
+            no_emoji: Markers::disabled_in_env(),
             shutdown,
         }
     }
 
+    /// The output markers to use, emoji are disabled if `no_emoji` is set or emoji are disabled in the environment
+    pub fn markers(&self, no_emoji: bool) -> Markers {
+        Markers::new(!(no_emoji || self.no_emoji))
+    }
+
     pub async fn handle_command_str(&mut self, line: &str) -> Result<Option<WatchCommand>, Error> {
         let args: Args = line.parse()?;
         if let Command::Watch(command) = args.command {
@@ -241,8 +250,8 @@ impl CommandContext {
                 Command::CreateTlsCerts(_) |
                 Command::Quit(_) |
                 Command::Exit(_) => 30,
-                // This test can potentially take a longer time and should be allowed to run longer
-                Command::TestPeerLiveness(_) => 240,
+                // These tests can potentially take a longer time and should be allowed to run longer
+                Command::TestPeerLiveness(_) | Command::TestAllPeers(_) => 240,
                 // These commands involve intense blockchain db operations and needs a lot of time to complete
                 Command::CheckDb(_) | Command::PeriodStats(_) | Command::RewindBlockchain(_) => 600,
             };
@@ -282,6 +291,7 @@ impl HandleCommand<Command> for CommandContext {
             Command::ListPeers(args) => self.handle_command(args).await,
             Command::DialPeer(args) => self.handle_command(args).await,
             Command::PingPeer(args) => self.handle_command(args).await,
+            Command::TestAllPeers(args) => self.handle_command(args).await,
             Command::AddPeer(args) => self.handle_command(args).await,
             Command::BanPeer(args) => self.handle_command(args).await,
             Command::UnbanPeer(args) => self.handle_command(args).await,
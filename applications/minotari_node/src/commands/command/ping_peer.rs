@@ -19,67 +19,304 @@
 //  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use std::time::Duration;
 
 use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
 use minotari_app_utilities::utilities::UniNodeId;
 use tari_comms::peer_manager::NodeId;
-use tari_p2p::services::liveness::LivenessEvent;
-use tokio::{sync::broadcast::error::RecvError, task};
+use tari_p2p::services::liveness::{LivenessEvent, LivenessEventReceiver, LivenessHandle};
+use tokio::{sync::broadcast::error::RecvError, task, time};
 
 use super::{CommandContext, HandleCommand};
+use crate::commands::{
+    markers::Markers,
+    rtt_format::{RttFormat, RttUnit},
+};
+
+/// How long to wait for a pong before reporting that the peer did not respond
+const PING_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Send a ping to a known peer and wait for a pong reply
 #[derive(Debug, Parser)]
 pub struct Args {
     /// hex public key or emoji id
     node_id: UniNodeId,
+    /// Only print the final result
+    #[clap(long, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Print every liveness event received while waiting for the pong
+    #[clap(long)]
+    verbose: bool,
+    /// Wait for the result and fail the command if the peer does not respond
+    #[clap(long)]
+    exit_on_fail: bool,
+    /// Use plain ASCII markers instead of emoji
+    #[clap(long)]
+    no_emoji: bool,
+    /// The unit to report the round-trip-time in (us, ms or s)
+    #[clap(long, default_value = "ms")]
+    rtt_unit: RttUnit,
+    /// The number of decimal places to report the round-trip-time with
+    #[clap(long, default_value_t = 2)]
+    rtt_precision: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PingOutcome {
+    Pong { nonce: u64, latency: Duration },
+    SendFailed(String),
+    TimedOut,
+    Closed,
+}
+
+impl PingOutcome {
+    fn is_pong(&self) -> bool {
+        matches!(self, PingOutcome::Pong { .. })
+    }
+}
+
+#[derive(Debug)]
+enum PingProgress {
+    Sent { nonce: u64 },
+    ReceivedEvent(String),
+    Done(PingOutcome),
 }
 
 #[async_trait]
 impl HandleCommand<Args> for CommandContext {
     async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
-        self.ping_peer(args.node_id.into()).await
+        let verbosity = if args.quiet {
+            Verbosity::Quiet
+        } else if args.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        };
+        let markers = self.markers(args.no_emoji);
+        let rtt = RttFormat::new(args.rtt_unit, args.rtt_precision);
+        self.ping_peer_with_options(args.node_id.into(), verbosity, markers, rtt, args.exit_on_fail)
+            .await
     }
 }
 
 impl CommandContext {
     /// Function to process the ping-peer command
     pub async fn ping_peer(&mut self, dest_node_id: NodeId) -> Result<(), Error> {
-        let mut liveness_events = self.liveness.get_event_stream();
-        let mut liveness = self.liveness.clone();
-        task::spawn(async move {
-            match liveness.send_ping(dest_node_id.clone()).await {
-                Ok(nonce) => {
-                    println!("🏓 Pinging peer {} with nonce {} ...", dest_node_id, nonce);
-                    loop {
-                        match liveness_events.recv().await {
-                            Ok(event) => {
-                                if let LivenessEvent::ReceivedPong(pong) = &*event {
-                                    if pong.node_id == dest_node_id && pong.nonce == nonce {
-                                        println!(
-                                            "🏓️ Pong: peer {} responded with nonce {}, round-trip-time is {:.2?}!",
-                                            pong.node_id,
-                                            pong.nonce,
-                                            pong.latency.unwrap_or_default()
-                                        );
-                                        break;
-                                    }
-                                }
-                            },
-                            Err(RecvError::Closed) => {
-                                break;
-                            },
-                            Err(RecvError::Lagged(_)) => {},
+        let markers = self.markers(false);
+        self.ping_peer_with_options(dest_node_id, Verbosity::Normal, markers, RttFormat::default(), false)
+            .await
+    }
+
+    async fn ping_peer_with_options(
+        &mut self,
+        dest_node_id: NodeId,
+        verbosity: Verbosity,
+        markers: Markers,
+        rtt: RttFormat,
+        exit_on_fail: bool,
+    ) -> Result<(), Error> {
+        let liveness_events = self.liveness.get_event_stream();
+        let liveness = self.liveness.clone();
+        let node_id = dest_node_id.clone();
+        let ping = task::spawn(async move {
+            let wait_for_pong =
+                ping_and_wait_for_pong(liveness, liveness_events, node_id.clone(), verbosity, markers, rtt);
+            let outcome = time::timeout(PING_TIMEOUT, wait_for_pong)
+                .await
+                .unwrap_or(PingOutcome::TimedOut);
+            print_progress(&node_id, verbosity, markers, rtt, &PingProgress::Done(outcome.clone()));
+            outcome
+        });
+        if !exit_on_fail {
+            return Ok(());
+        }
+
+        if ping.await?.is_pong() {
+            Ok(())
+        } else {
+            Err(Error::msg(format!("Peer {} did not respond to the ping", dest_node_id)))
+        }
+    }
+}
+
+async fn ping_and_wait_for_pong(
+    mut liveness: LivenessHandle,
+    mut liveness_events: LivenessEventReceiver,
+    dest_node_id: NodeId,
+    verbosity: Verbosity,
+    markers: Markers,
+    rtt: RttFormat,
+) -> PingOutcome {
+    match liveness.send_ping(dest_node_id.clone()).await {
+        Ok(nonce) => {
+            print_progress(&dest_node_id, verbosity, markers, rtt, &PingProgress::Sent { nonce });
+            loop {
+                match liveness_events.recv().await {
+                    Ok(event) => {
+                        if let LivenessEvent::ReceivedPong(pong) = &*event {
+                            if pong.node_id == dest_node_id && pong.nonce == nonce {
+                                break PingOutcome::Pong {
+                                    nonce: pong.nonce,
+                                    latency: pong.latency.unwrap_or_default(),
+                                };
+                            }
                         }
-                    }
+                        print_progress(
+                            &dest_node_id,
+                            verbosity,
+                            markers,
+                            rtt,
+                            &PingProgress::ReceivedEvent(format!("{:?}", event)),
+                        );
+                    },
+                    Err(RecvError::Closed) => {
+                        break PingOutcome::Closed;
+                    },
+                    Err(RecvError::Lagged(n)) => {
+                        print_progress(
+                            &dest_node_id,
+                            verbosity,
+                            markers,
+                            rtt,
+                            &PingProgress::ReceivedEvent(format!("lagged by {} events", n)),
+                        );
+                    },
+                }
+            }
+        },
+        Err(e) => PingOutcome::SendFailed(e.to_string()),
+    }
+}
+
+fn print_progress(
+    dest_node_id: &NodeId,
+    verbosity: Verbosity,
+    markers: Markers,
+    rtt: RttFormat,
+    progress: &PingProgress,
+) {
+    if let Some(line) = format_progress(dest_node_id, verbosity, markers, rtt, progress) {
+        println!("{}", line);
+    }
+}
+
+fn format_progress(
+    dest_node_id: &NodeId,
+    verbosity: Verbosity,
+    markers: Markers,
+    rtt: RttFormat,
+    progress: &PingProgress,
+) -> Option<String> {
+    match progress {
+        PingProgress::Sent { nonce } => match verbosity {
+            Verbosity::Quiet => None,
+            Verbosity::Normal | Verbosity::Verbose => Some(format!(
+                "{} Pinging peer {} with nonce {} ...",
+                markers.ping(),
+                dest_node_id,
+                nonce
+            )),
+        },
+        PingProgress::ReceivedEvent(event) => match verbosity {
+            Verbosity::Verbose => Some(format!(
+                "{} Waiting for pong from {}, received {}",
+                markers.ping(),
+                dest_node_id,
+                event
+            )),
+            Verbosity::Quiet | Verbosity::Normal => None,
+        },
+        PingProgress::Done(outcome) => {
+            let line = match outcome {
+                PingOutcome::Pong { nonce, latency } => match verbosity {
+                    Verbosity::Quiet => format!(
+                        "Pong: peer {} responded with nonce {}, round-trip-time is {}",
+                        dest_node_id,
+                        nonce,
+                        rtt.format(*latency)
+                    ),
+                    Verbosity::Normal | Verbosity::Verbose => format!(
+                        "{} Pong: peer {} responded with nonce {}, round-trip-time is {}!",
+                        markers.ping(),
+                        dest_node_id,
+                        nonce,
+                        rtt.format(*latency)
+                    ),
                 },
-                Err(e) => {
-                    println!("🏓 Ping failed to send to {}: {}", dest_node_id, e);
+                PingOutcome::SendFailed(e) => match verbosity {
+                    Verbosity::Quiet => format!("Ping failed to send to {}: {}", dest_node_id, e),
+                    Verbosity::Normal | Verbosity::Verbose => {
+                        format!("{} Ping failed to send to {}: {}", markers.ping(), dest_node_id, e)
+                    },
                 },
-            }
-        });
-        Ok(())
+                PingOutcome::TimedOut => format!("No pong received from {} within {:.0?}", dest_node_id, PING_TIMEOUT),
+                // The node is shutting down, matches the previous behaviour of not reporting anything
+                PingOutcome::Closed => return None,
+            };
+            Some(line)
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quiet_mode_only_produces_the_summary_line() {
+        let node_id = NodeId::default();
+        let rtt = RttFormat::default();
+        let progress = [
+            PingProgress::Sent { nonce: 123 },
+            PingProgress::ReceivedEvent("ReceivedPing".to_string()),
+            PingProgress::Done(PingOutcome::Pong {
+                nonce: 123,
+                latency: Duration::from_millis(50),
+            }),
+        ];
+
+        let lines = progress
+            .iter()
+            .filter_map(|p| format_progress(&node_id, Verbosity::Quiet, Markers::default(), rtt, p))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![format!(
+            "Pong: peer {} responded with nonce 123, round-trip-time is 50.00ms",
+            node_id
+        )]);
+
+        let lines = progress
+            .iter()
+            .filter_map(|p| format_progress(&node_id, Verbosity::Verbose, Markers::default(), rtt, p))
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn it_uses_ascii_markers_when_emoji_are_disabled() {
+        let node_id = NodeId::default();
+        let rtt = RttFormat::default();
+        let progress = [
+            PingProgress::Sent { nonce: 123 },
+            PingProgress::Done(PingOutcome::Pong {
+                nonce: 123,
+                latency: Duration::from_millis(50),
+            }),
+        ];
+
+        let lines = progress
+            .iter()
+            .filter_map(|p| format_progress(&node_id, Verbosity::Normal, Markers::new(false), rtt, p))
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.starts_with("[PING] ") && line.is_ascii()));
     }
 }
@@ -0,0 +1,247 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use tari_comms::{
+    connection_manager::ConnectionDirection,
+    connectivity::ConnectivityRequester,
+    multiaddr::Multiaddr,
+    peer_manager::NodeId,
+};
+use tari_p2p::services::liveness::LivenessHandle;
+
+use super::{CommandContext, HandleCommand};
+use crate::commands::{
+    markers::Markers,
+    rtt_format::{RttFormat, RttUnit},
+};
+
+/// Ping every connected outbound peer and report whether each responded and its round-trip-time
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// How long to wait for the pongs, in seconds
+    #[clap(long, default_value_t = 30)]
+    timeout: u64,
+    /// Write a CSV summary of the results to this file
+    #[clap(long)]
+    output_file: Option<PathBuf>,
+    /// Use plain ASCII markers instead of emoji
+    #[clap(long)]
+    no_emoji: bool,
+    /// The unit to report the round-trip-times in (us, ms or s)
+    #[clap(long, default_value = "ms")]
+    rtt_unit: RttUnit,
+    /// The number of decimal places to report the round-trip-times with
+    #[clap(long, default_value_t = 2)]
+    rtt_precision: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PeerTestResult {
+    node_id: NodeId,
+    address: Multiaddr,
+    latency: Option<Duration>,
+}
+
+impl PeerTestResult {
+    fn passed(&self) -> bool {
+        self.latency.is_some()
+    }
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        let markers = self.markers(args.no_emoji);
+        let rtt = RttFormat::new(args.rtt_unit, args.rtt_precision);
+        let mut connectivity = self.comms.connectivity();
+        let results = test_all_peers(&mut connectivity, &mut self.liveness, Duration::from_secs(args.timeout)).await?;
+        if results.is_empty() {
+            println!("No connected outbound peers to test");
+            return Ok(());
+        }
+
+        for result in &results {
+            println!("{}", format_result(result, markers, rtt));
+        }
+        let num_passed = results.iter().filter(|r| r.passed()).count();
+        println!("{} of {} peers responded", num_passed, results.len());
+
+        if let Some(path) = args.output_file {
+            match write_csv_summary(&path, &results, rtt) {
+                Ok(_) => println!("{} Test results written to file: {}", markers.file(), path.display()),
+                Err(e) => println!("{} Error writing test results to file: {}", markers.fail(), e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pings all connected outbound base node peers at once and waits up to `timeout` for their pongs
+async fn test_all_peers(
+    connectivity: &mut ConnectivityRequester,
+    liveness: &mut LivenessHandle,
+    timeout: Duration,
+) -> Result<Vec<PeerTestResult>, Error> {
+    let peers = connectivity
+        .get_active_connections()
+        .await?
+        .into_iter()
+        .filter(|conn| conn.peer_features().is_node() && conn.direction() == ConnectionDirection::Outbound)
+        .map(|conn| (conn.peer_node_id().clone(), conn.address().clone()))
+        .collect::<Vec<_>>();
+    if peers.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let node_ids = peers.iter().map(|(node_id, _)| node_id.clone()).collect();
+    let mut latencies = liveness.ping_and_collect(node_ids, timeout).await?;
+    Ok(peers
+        .into_iter()
+        .map(|(node_id, address)| PeerTestResult {
+            latency: latencies.remove(&node_id).flatten(),
+            node_id,
+            address,
+        })
+        .collect())
+}
+
+fn format_result(result: &PeerTestResult, markers: Markers, rtt: RttFormat) -> String {
+    match result.latency {
+        Some(latency) => format!(
+            "{} {} ({}) responded, round-trip-time is {}",
+            markers.ok(),
+            result.node_id,
+            result.address,
+            rtt.format(latency)
+        ),
+        None => format!("{} {} ({}) did not respond", markers.fail(), result.node_id, result.address),
+    }
+}
+
+fn csv_summary(results: &[PeerTestResult], rtt: RttFormat) -> String {
+    let mut csv = format!("Node Id,Address,Result,Round Trip Time ({})\n", rtt.unit());
+    for result in results {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            result.node_id,
+            result.address,
+            if result.passed() { "PASS" } else { "FAIL" },
+            result.latency.map(|l| rtt.value(l)).unwrap_or_default()
+        ));
+    }
+    csv
+}
+
+fn write_csv_summary(path: &Path, results: &[PeerTestResult], rtt: RttFormat) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, csv_summary(results, rtt))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use tari_comms::test_utils::{
+        mocks::{create_connectivity_mock, create_dummy_peer_connection_with_direction},
+        node_id,
+    };
+    use tari_p2p::services::liveness::{LivenessEvent, LivenessRequest, LivenessResponse, Metadata, PingPongEvent};
+    use tari_service_framework::reply_channel;
+    use tokio::{sync::broadcast, task};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_tests_all_connected_outbound_peers() {
+        let (mut connectivity, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        let (sender, mut requests) = reply_channel::unbounded();
+        let (event_publisher, _) = broadcast::channel(10);
+        let mut liveness = LivenessHandle::new(sender, event_publisher.clone());
+
+        // Nothing to test without connections
+        let results = test_all_peers(&mut connectivity, &mut liveness, Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+
+        let mut conn_requests = Vec::new();
+        let outbound = (0..3).map(|_| node_id::random()).collect::<Vec<_>>();
+        for peer in &outbound {
+            let (conn, rx) =
+                create_dummy_peer_connection_with_direction(peer.clone(), ConnectionDirection::Outbound);
+            mock_state.add_active_connection(conn).await;
+            conn_requests.push(rx);
+        }
+        let inbound = node_id::random();
+        let (conn, rx) = create_dummy_peer_connection_with_direction(inbound.clone(), ConnectionDirection::Inbound);
+        mock_state.add_active_connection(conn).await;
+        conn_requests.push(rx);
+
+        // Every peer except the first outbound peer responds
+        let silent = outbound[0].clone();
+        task::spawn(async move {
+            let (request, reply) = requests.next().await.unwrap().split();
+            let LivenessRequest::SendPings(node_ids) = request else {
+                panic!("Unexpected liveness request {:?}", request);
+            };
+            let nonces = (0u64..).take(node_ids.len()).collect::<Vec<_>>();
+            reply.send(Ok(LivenessResponse::Ok(Some(nonces.clone())))).unwrap();
+            for (node_id, nonce) in node_ids.into_iter().zip(nonces) {
+                if node_id == silent {
+                    continue;
+                }
+                let pong = PingPongEvent::new(node_id, Some(Duration::from_millis(10 + nonce)), Metadata::new(), nonce);
+                event_publisher
+                    .send(Arc::new(LivenessEvent::ReceivedPong(Box::new(pong))))
+                    .unwrap();
+            }
+        });
+
+        let results = test_all_peers(&mut connectivity, &mut liveness, Duration::from_millis(500))
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|r| r.node_id != inbound));
+        for result in &results {
+            assert_eq!(result.passed(), result.node_id != outbound[0], "{:?}", result);
+        }
+        let csv = csv_summary(&results, RttFormat::default());
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(csv.lines().filter(|line| line.contains(",PASS,")).count(), 2);
+        assert!(csv.contains(&format!("{},/ip4/23.23.23.23/tcp/80,FAIL,\n", outbound[0])));
+    }
+}
@@ -23,8 +23,10 @@
 use std::{
     fs,
     fs::OpenOptions,
-    io::Write,
-    path::PathBuf,
+    io,
+    io::{BufRead, BufReader, Write},
+    iter,
+    path::{Path, PathBuf},
     process,
     time::{Duration, Instant},
 };
@@ -36,14 +38,22 @@ use clap::Parser;
 use minotari_app_utilities::utilities::UniPublicKey;
 use tari_common_types::types::PublicKey;
 use tari_comms::{
+    connectivity::ConnectivityRequester,
     multiaddr::Multiaddr,
     net_address::{MultiaddressesWithStats, PeerAddressSource},
     peer_manager::{NodeId, Peer, PeerFeatures, PeerFlags},
 };
-use tari_p2p::services::liveness::{LivenessEvent, LivenessHandle};
+use tari_p2p::services::liveness::LivenessHandle;
 use tokio::{sync::watch, task};
 
 use super::{CommandContext, HandleCommand};
+use crate::commands::{
+    markers::Markers,
+    rtt_format::{RttFormat, RttUnit},
+};
+
+/// How long to wait for the pong to each ping
+const PONG_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Adds a peer
 #[derive(Debug, Parser)]
@@ -52,6 +62,9 @@ pub struct ArgsTestPeerLiveness {
     public_key: UniPublicKey,
     /// The address of the peer to be tested
     address: Multiaddr,
+    /// Additional addresses of the peer to be tested, the address that responded will be reported
+    #[clap(long = "address")]
+    additional_addresses: Vec<Multiaddr>,
     /// Auto exit the base node after test
     exit: Option<bool>,
     /// Write the responsiveness result to file - results will be written to
@@ -61,6 +74,22 @@ pub struct ArgsTestPeerLiveness {
     refresh_file: Option<bool>,
     /// Optional output directory (otherwise current directory will be used)
     output_directory: Option<PathBuf>,
+    /// Use plain ASCII markers instead of emoji
+    #[clap(long)]
+    no_emoji: bool,
+    /// Flush and sync the output file to disk after every row is written
+    #[clap(long)]
+    durable_output: bool,
+    /// Number of pings sent and discarded before the measured ping, so that connection-setup latency does not skew
+    /// the reported round-trip-time
+    #[clap(long, default_value_t = 0)]
+    warmup_pings: usize,
+    /// The unit to report round-trip-times and the test duration in (us, ms or s)
+    #[clap(long, default_value = "ms")]
+    rtt_unit: RttUnit,
+    /// The number of decimal places to report round-trip-times and the test duration with
+    #[clap(long, default_value_t = 2)]
+    rtt_precision: usize,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -70,10 +99,31 @@ enum PingResult {
     Fail,
 }
 
+/// Round-trip-times observed while pinging a peer, `None` where no pong was received
+#[derive(Debug, Default)]
+struct PingLatencies {
+    warmup: Vec<Option<Duration>>,
+    measured: Vec<Option<Duration>>,
+}
+
+impl PingLatencies {
+    /// The average round-trip-time of the measured pings that were answered; warm-up pings are excluded
+    fn measured_average(&self) -> Option<Duration> {
+        let answered = self.measured.iter().flatten().collect::<Vec<_>>();
+        if answered.is_empty() {
+            return None;
+        }
+        let count = u32::try_from(answered.len()).unwrap_or(u32::MAX);
+        Some(answered.into_iter().sum::<Duration>() / count)
+    }
+}
+
 #[async_trait]
 impl HandleCommand<ArgsTestPeerLiveness> for CommandContext {
     async fn handle_command(&mut self, args: ArgsTestPeerLiveness) -> Result<(), Error> {
         println!("\nTesting peer liveness...\n");
+        let markers = self.markers(args.no_emoji);
+        let rtt = RttFormat::new(args.rtt_unit, args.rtt_precision);
         let peer_manager = self.comms.peer_manager();
 
         let public_key = args.public_key.into();
@@ -83,16 +133,21 @@ impl HandleCommand<ArgsTestPeerLiveness> for CommandContext {
         let node_id = NodeId::from_public_key(&public_key);
         let node_id_clone = node_id.clone();
         let public_key_clone = public_key.clone();
-        let address_clone = args.address.clone();
+        let mut addresses = Vec::with_capacity(args.additional_addresses.len() + 1);
+        for address in iter::once(args.address).chain(args.additional_addresses) {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
 
         // Remove the peer from the peer manager (not the peer db)
         let _res = peer_manager.delete_peer(&node_id).await;
 
-        // Create a new peer with the given address, if the peer exists, this will merge the given address
+        // Create a new peer with the given addresses, if the peer exists, this will merge the given addresses
         let peer = Peer::new(
             public_key.clone(),
             node_id.clone(),
-            MultiaddressesWithStats::from_addresses_with_source(vec![args.address], &PeerAddressSource::Config),
+            MultiaddressesWithStats::from_addresses_with_source(addresses.clone(), &PeerAddressSource::Config),
             PeerFlags::empty(),
             PeerFeatures::COMMUNICATION_NODE,
             vec![],
@@ -100,16 +155,33 @@ impl HandleCommand<ArgsTestPeerLiveness> for CommandContext {
         );
         peer_manager.add_peer(peer).await?;
 
-        let (tx, mut rx) = watch::channel(PingResult::Initial);
+        let (tx, mut rx) = watch::channel((PingResult::Initial, None));
 
         // Attempt to dial and ping the peer
         let start = Instant::now();
         for _ in 0..5 {
             if self.dial_peer(node_id.clone()).await.is_ok() {
-                println!("🏓 Peer ({}, {}) dialed successfully", node_id, public_key);
+                println!(
+                    "{} Peer ({}, {}) dialed successfully",
+                    markers.ping(),
+                    node_id,
+                    public_key
+                );
                 let liveness = self.liveness.clone();
+                let connectivity = self.comms.connectivity();
+                let warmup_pings = args.warmup_pings;
                 task::spawn(async move {
-                    ping_peer_liveness(liveness, node_id, public_key, tx).await;
+                    ping_peer_liveness(
+                        liveness,
+                        connectivity,
+                        node_id,
+                        public_key,
+                        warmup_pings,
+                        tx,
+                        markers,
+                        rtt,
+                    )
+                    .await;
                 });
                 // Break if the dial was successful
                 break;
@@ -123,10 +195,20 @@ impl HandleCommand<ArgsTestPeerLiveness> for CommandContext {
             tokio::select! {
                 _ = rx.changed() => {
                     let test_duration = start.elapsed();
-                    let responsive = *rx.borrow();
+                    let (responsive, responding_address) = rx.borrow().clone();
                     let date_time = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
 
-                    print_results_to_console(&date_time, responsive, &public_key_clone, &node_id_clone, &address_clone, test_duration);
+                    print_results_to_console(
+                        &date_time,
+                        responsive,
+                        &public_key_clone,
+                        &node_id_clone,
+                        &addresses,
+                        responding_address.as_ref(),
+                        test_duration,
+                        markers,
+                        rtt,
+                    );
 
                     if let Some(true) = args.output_to_file {
                         print_to_file(
@@ -135,8 +217,12 @@ impl HandleCommand<ArgsTestPeerLiveness> for CommandContext {
                             args.output_directory,
                             args.refresh_file,
                             public_key_clone,
-                            address_clone,
-                            test_duration
+                            &addresses,
+                            responding_address.as_ref(),
+                            test_duration,
+                            markers,
+                            rtt,
+                            args.durable_output,
                         ).await;
                     }
 
@@ -166,56 +252,128 @@ fn print_results_to_console(
     responsive: PingResult,
     public_key: &PublicKey,
     node_id: &NodeId,
-    address: &Multiaddr,
+    addresses: &[Multiaddr],
+    responding_address: Option<&Multiaddr>,
     test_duration: Duration,
+    markers: Markers,
+    rtt: RttFormat,
 ) {
     println!();
     if responsive == PingResult::Success {
-        println!("✅ Peer is responsive");
+        println!("{} Peer is responsive", markers.ok());
     } else {
-        println!("❌ Peer is unresponsive");
+        println!("{} Peer is unresponsive", markers.fail());
     }
     println!("  Date Time:     {}", date_time);
     println!("  Public Key:    {}", public_key);
     println!("  Node ID:       {}", node_id);
-    println!("  Address:       {}", address);
+    for address in addresses {
+        println!("  Address:       {}", address);
+    }
+    match responding_address {
+        Some(address) => println!("  Responded On:  {}", address),
+        None => println!("  Responded On:  none of the {} address(es) responded", addresses.len()),
+    }
     println!("  Result:        {:?}", responsive);
-    println!("  Test Duration: {:.2?}", test_duration);
+    println!("  Test Duration: {}", rtt.format(test_duration));
     println!();
 }
 
 async fn ping_peer_liveness(
     mut liveness: LivenessHandle,
+    connectivity: ConnectivityRequester,
     node_id: NodeId,
     public_key: PublicKey,
-    tx: watch::Sender<PingResult>,
+    warmup_pings: usize,
+    tx: watch::Sender<(PingResult, Option<Multiaddr>)>,
+    markers: Markers,
+    rtt: RttFormat,
 ) {
-    let mut liveness_events = liveness.get_event_stream();
-    if let Ok(nonce) = liveness.send_ping(node_id.clone()).await {
-        println!("🏓 Pinging peer ({}, {}) with nonce {} ...", node_id, public_key, nonce);
-        for _ in 0..5 {
-            match liveness_events.recv().await {
-                Ok(event) => {
-                    if let LivenessEvent::ReceivedPong(pong) = &*event {
-                        if pong.node_id == node_id && pong.nonce == nonce {
-                            println!(
-                                "🏓️ Pong: peer ({}, {}) responded with nonce {}, round-trip-time is {:.2?}!",
-                                pong.node_id,
-                                public_key,
-                                pong.nonce,
-                                pong.latency.unwrap_or_default()
-                            );
-                            let _ = tx.send(PingResult::Success);
-                            return;
-                        }
-                    }
-                },
-                Err(e) => {
-                    println!("🏓 Ping peer ({}, {}) gave error: {}", node_id, public_key, e);
-                },
+    let mut latencies = PingLatencies::default();
+
+    for i in 1..=warmup_pings {
+        println!(
+            "{} Warm-up ping {}/{} (result discarded)",
+            markers.ping(),
+            i,
+            warmup_pings
+        );
+        let latency = ping_once(&mut liveness, &node_id, &public_key, markers, rtt).await;
+        latencies.warmup.push(latency);
+    }
+
+    let latency = ping_once(&mut liveness, &node_id, &public_key, markers, rtt).await;
+    latencies.measured.push(latency);
+    print_latencies(&latencies, markers, rtt);
+
+    if latency.is_some() {
+        // The pong arrived over the active connection, so its address is the one that responded
+        let responding_address = match connectivity.get_connection(node_id.clone()).await {
+            Ok(Some(conn)) => Some(conn.address().clone()),
+            _ => None,
+        };
+        let _ = tx.send((PingResult::Success, responding_address));
+    } else {
+        let _ = tx.send((PingResult::Fail, None));
+    }
+}
+
+/// Sends a single ping to the peer and waits up to `PONG_TIMEOUT` for the matching pong, returning its round-trip-time
+async fn ping_once(
+    liveness: &mut LivenessHandle,
+    node_id: &NodeId,
+    public_key: &PublicKey,
+    markers: Markers,
+    rtt: RttFormat,
+) -> Option<Duration> {
+    println!("{} Pinging peer ({}, {}) ...", markers.ping(), node_id, public_key);
+    match liveness.ping_and_collect(vec![node_id.clone()], PONG_TIMEOUT).await {
+        Ok(mut latencies) => {
+            let latency = latencies.remove(node_id).flatten();
+            match latency {
+                Some(latency) => println!(
+                    "{} Pong: peer ({}, {}) responded, round-trip-time is {}!",
+                    markers.ping(),
+                    node_id,
+                    public_key,
+                    rtt.format(latency)
+                ),
+                None => println!(
+                    "{} Peer ({}, {}) did not respond within {:.0?}",
+                    markers.ping(),
+                    node_id,
+                    public_key,
+                    PONG_TIMEOUT
+                ),
             }
-        }
-        let _ = tx.send(PingResult::Fail);
+            latency
+        },
+        Err(e) => {
+            println!(
+                "{} Ping peer ({}, {}) gave error: {}",
+                markers.ping(),
+                node_id,
+                public_key,
+                e
+            );
+            None
+        },
+    }
+}
+
+fn print_latencies(latencies: &PingLatencies, markers: Markers, rtt: RttFormat) {
+    if !latencies.warmup.is_empty() {
+        let warmup = latencies
+            .warmup
+            .iter()
+            .map(|l| l.map(|l| rtt.format(l)).unwrap_or_else(|| "no pong".to_string()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("{} Warm-up round-trip-times (discarded): {}", markers.ping(), warmup);
+    }
+    match latencies.measured_average() {
+        Some(average) => println!("{} Measured round-trip-time: {}", markers.ping(), rtt.format(average)),
+        None => println!("{} Measured round-trip-time: no pong received", markers.ping()),
     }
 }
 
@@ -225,8 +383,12 @@ async fn print_to_file(
     output_directory: Option<PathBuf>,
     refresh_file: Option<bool>,
     public_key: PublicKey,
-    address: Multiaddr,
+    addresses: &[Multiaddr],
+    responding_address: Option<&Multiaddr>,
     test_duration: Duration,
+    markers: Markers,
+    rtt: RttFormat,
+    durable_output: bool,
 ) {
     let test_result = if responsive == PingResult::Success {
         "PASS"
@@ -251,23 +413,192 @@ async fn print_to_file(
         let _unused = fs::remove_file(&file_path);
         tokio::time::sleep(Duration::from_secs(1)).await;
     }
-    let write_header = !file_path.exists();
-    if let Ok(mut file) = OpenOptions::new().append(true).create(true).open(file_path.clone()) {
-        let mut file_content = String::new();
-        if write_header {
-            file_content.push_str("Date Time,Public Key,Address,Result,Test Duration\n");
+    let header = csv_header(rtt);
+    if let Err(e) = start_new_file_if_header_changed(&file_path, &header) {
+        println!("{} Error starting a new results file: {}", markers.fail(), e);
+    }
+    let mut file_content = String::new();
+    if !file_path.exists() {
+        file_content.push_str(&header);
+        file_content.push('\n');
+    }
+    let addresses = addresses.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(";");
+    let responding_address = responding_address.map(|a| a.to_string()).unwrap_or_default();
+    file_content.push_str(&format!(
+        "{},{},{},{},{},{}",
+        date_time,
+        public_key,
+        addresses,
+        test_result,
+        rtt.value(test_duration),
+        responding_address
+    ));
+    match append_to_file(&file_path, &file_content, durable_output) {
+        Ok(_) => {
+            println!(
+                "{} Test result written to file: {}",
+                markers.file(),
+                file_path.display()
+            );
+        },
+        Err(e) => {
+            println!("{} Error writing test result to file: {}", markers.fail(), e);
+        },
+    }
+}
+
+fn csv_header(rtt: RttFormat) -> String {
+    format!(
+        "Date Time,Public Key,Address,Result,Test Duration ({}),Responding Address",
+        rtt.unit()
+    )
+}
+
+/// Rows are only comparable under the same header (e.g. the test duration unit set by `--rtt-unit`), so if the
+/// existing file was started with a different header it is moved aside and a new file is started.
+fn start_new_file_if_header_changed(file_path: &Path, header: &str) -> io::Result<()> {
+    let file = match fs::File::open(file_path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let mut existing_header = String::new();
+    BufReader::new(file).read_line(&mut existing_header)?;
+    if existing_header.trim_end() == header {
+        return Ok(());
+    }
+    let stem = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("peer_liveness_test");
+    let archived = file_path.with_file_name(format!("{}_{}.csv", stem, Local::now().format("%Y%m%d_%H%M%S")));
+    fs::rename(file_path, archived)
+}
+
+/// Appends a line to the file, creating it if needed. With `durable_output` the write is flushed and synced to disk
+/// before returning, so the row survives a crash at the cost of a slower write.
+fn append_to_file(file_path: &Path, line: &str, durable_output: bool) -> io::Result<()> {
+    let mut file = OpenOptions::new().append(true).create(true).open(file_path)?;
+    write_line(&mut file, line, durable_output, fs::File::sync_all)
+}
+
+/// Writes a line, and with `durable_output` flushes the writer and syncs it with `sync` before returning
+fn write_line<W: Write>(
+    writer: &mut W,
+    line: &str,
+    durable_output: bool,
+    sync: impl FnOnce(&W) -> io::Result<()>,
+) -> io::Result<()> {
+    writeln!(writer, "{}", line)?;
+    if durable_output {
+        writer.flush()?;
+        sync(writer)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, io::BufWriter};
+
+    use super::*;
+
+    #[test]
+    fn durable_output_flushes_and_syncs_each_row() {
+        for durable_output in [false, true] {
+            let synced = Cell::new(false);
+            let mut writer = BufWriter::new(Vec::new());
+            write_line(&mut writer, "first,row", durable_output, |_| {
+                synced.set(true);
+                Ok(())
+            })
+            .unwrap();
+            assert_eq!(synced.get(), durable_output);
+            // Without durable output the row may still be buffered when the call returns
+            let expected: &[u8] = if durable_output { b"first,row\n" } else { b"" };
+            assert_eq!(writer.get_ref().as_slice(), expected);
         }
-        file_content.push_str(&format!(
-            "{},{},{},{},{:.2?}",
-            date_time, public_key, address, test_result, test_duration
-        ));
-        match writeln!(file, "{}", file_content) {
-            Ok(_) => {
-                println!("📝 Test result written to file: {}", file_path.display());
-            },
-            Err(e) => {
-                println!("❌ Error writing test result to file: {}", e);
-            },
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("peer_liveness_test.csv");
+        append_to_file(&file_path, "first,row", true).unwrap();
+        append_to_file(&file_path, "second,row", false).unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "first,row\nsecond,row\n");
+    }
+
+    #[tokio::test]
+    async fn each_row_reports_all_tested_addresses_and_the_responding_one() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let addresses: Vec<Multiaddr> = vec![
+            "/ip4/127.0.0.1/tcp/18189".parse().unwrap(),
+            "/ip4/127.0.0.1/tcp/18190".parse().unwrap(),
+        ];
+        let rtt = RttFormat::new(RttUnit::Millis, 0);
+        for (result, responding_address) in [(PingResult::Success, Some(&addresses[1])), (PingResult::Fail, None)] {
+            print_to_file(
+                "2025-01-01 00:00:00",
+                result,
+                Some(temp_dir.path().to_path_buf()),
+                None,
+                PublicKey::default(),
+                &addresses,
+                responding_address,
+                Duration::from_millis(25),
+                Markers::new(false),
+                rtt,
+                false,
+            )
+            .await;
         }
+
+        let content = fs::read_to_string(temp_dir.path().join("peer_liveness_test.csv")).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], csv_header(rtt));
+        let rows = lines[1..]
+            .iter()
+            .map(|line| line.split(',').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.len() == lines[0].split(',').count()));
+        let tested_addresses = "/ip4/127.0.0.1/tcp/18189;/ip4/127.0.0.1/tcp/18190";
+        assert_eq!(rows[0][2..], [tested_addresses, "PASS", "25", "/ip4/127.0.0.1/tcp/18190"]);
+        // No address is reported if none responded
+        assert_eq!(rows[1][2..], [tested_addresses, "FAIL", "25", ""]);
+    }
+
+    #[test]
+    fn a_changed_header_starts_a_new_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let file_path = dir.join("peer_liveness_test.csv");
+        let ms_header = csv_header(RttFormat::new(RttUnit::Millis, 0));
+        let us_header = csv_header(RttFormat::new(RttUnit::Micros, 0));
+        assert!(ms_header.ends_with(",Responding Address"));
+
+        append_to_file(&file_path, &ms_header, false).unwrap();
+        append_to_file(&file_path, "first,row", false).unwrap();
+        start_new_file_if_header_changed(&file_path, &ms_header).unwrap();
+        assert!(file_path.exists());
+
+        start_new_file_if_header_changed(&file_path, &us_header).unwrap();
+        assert!(!file_path.exists());
+        let archived = fs::read_dir(dir).unwrap().map(|e| e.unwrap().path()).collect::<Vec<_>>();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(
+            fs::read_to_string(&archived[0]).unwrap(),
+            format!("{}\nfirst,row\n", ms_header)
+        );
+    }
+
+    #[test]
+    fn warmup_pings_are_excluded_from_the_measured_average() {
+        let latencies = PingLatencies {
+            warmup: vec![Some(Duration::from_millis(900)), None],
+            measured: vec![Some(Duration::from_millis(40)), Some(Duration::from_millis(60)), None],
+        };
+        assert_eq!(latencies.measured_average(), Some(Duration::from_millis(50)));
+
+        let latencies = PingLatencies {
+            warmup: vec![Some(Duration::from_millis(900))],
+            measured: vec![None],
+        };
+        assert_eq!(latencies.measured_average(), None);
     }
 }
@@ -0,0 +1,98 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+/// Set this environment variable to replace emoji in command output with plain ASCII markers
+pub const NO_EMOJI_ENV_VAR: &str = "NO_EMOJI";
+
+/// Markers used to decorate command output. Emoji can be swapped for plain ASCII for terminals and log collectors that
+/// cannot display them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Markers {
+    emoji: bool,
+}
+
+impl Markers {
+    pub fn new(emoji: bool) -> Self {
+        Self { emoji }
+    }
+
+    /// Returns true if emoji output has been disabled in the environment
+    pub fn disabled_in_env() -> bool {
+        std::env::var_os(NO_EMOJI_ENV_VAR).is_some_and(|v| !v.is_empty())
+    }
+
+    pub fn ping(&self) -> &'static str {
+        if self.emoji {
+            "🏓"
+        } else {
+            "[PING]"
+        }
+    }
+
+    pub fn ok(&self) -> &'static str {
+        if self.emoji {
+            "✅"
+        } else {
+            "[OK]"
+        }
+    }
+
+    pub fn fail(&self) -> &'static str {
+        if self.emoji {
+            "❌"
+        } else {
+            "[FAIL]"
+        }
+    }
+
+    pub fn file(&self) -> &'static str {
+        if self.emoji {
+            "📝"
+        } else {
+            "[FILE]"
+        }
+    }
+}
+
+impl Default for Markers {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_uses_ascii_markers_when_emoji_are_disabled() {
+        let markers = Markers::new(false);
+        assert_eq!(markers.ping(), "[PING]");
+        assert_eq!(markers.ok(), "[OK]");
+        assert_eq!(markers.fail(), "[FAIL]");
+        assert_eq!(markers.file(), "[FILE]");
+
+        let markers = Markers::default();
+        assert_eq!(markers.ok(), "✅");
+        assert_eq!(markers.fail(), "❌");
+    }
+}
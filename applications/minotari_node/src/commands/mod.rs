@@ -23,7 +23,9 @@
 pub mod cli;
 pub mod cli_loop;
 pub mod command;
+pub mod markers;
 pub mod nom_parser;
 pub mod parser;
 pub mod reader;
+pub mod rtt_format;
 pub mod status_line;
@@ -0,0 +1,127 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+/// The unit that round-trip-times are reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RttUnit {
+    Micros,
+    #[default]
+    Millis,
+    Secs,
+}
+
+impl RttUnit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RttUnit::Micros => "us",
+            RttUnit::Millis => "ms",
+            RttUnit::Secs => "s",
+        }
+    }
+
+    fn convert(&self, duration: Duration) -> f64 {
+        match self {
+            RttUnit::Micros => duration.as_secs_f64() * 1_000_000.0,
+            RttUnit::Millis => duration.as_secs_f64() * 1_000.0,
+            RttUnit::Secs => duration.as_secs_f64(),
+        }
+    }
+}
+
+impl FromStr for RttUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "us" => Ok(RttUnit::Micros),
+            "ms" => Ok(RttUnit::Millis),
+            "s" => Ok(RttUnit::Secs),
+            _ => Err(format!("Invalid round-trip-time unit '{}', expected one of: us, ms, s", s)),
+        }
+    }
+}
+
+impl fmt::Display for RttUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Formats round-trip-times in a fixed unit and precision, unlike the `Debug` output of `Duration` which picks the
+/// unit based on the magnitude
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RttFormat {
+    unit: RttUnit,
+    precision: usize,
+}
+
+impl RttFormat {
+    pub fn new(unit: RttUnit, precision: usize) -> Self {
+        Self { unit, precision }
+    }
+
+    pub fn unit(&self) -> RttUnit {
+        self.unit
+    }
+
+    /// The duration in the configured unit without a unit suffix, e.g. for CSV columns
+    pub fn value(&self, duration: Duration) -> String {
+        format!("{:.*}", self.precision, self.unit.convert(duration))
+    }
+
+    /// The duration in the configured unit with a unit suffix, e.g. `12.35ms`
+    pub fn format(&self, duration: Duration) -> String {
+        format!("{}{}", self.value(duration), self.unit)
+    }
+}
+
+impl Default for RttFormat {
+    fn default() -> Self {
+        Self::new(RttUnit::default(), 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_formats_durations_in_a_fixed_unit_and_precision() {
+        let rtt = RttFormat::default();
+        assert_eq!(rtt.format(Duration::from_micros(12_346)), "12.35ms");
+        assert_eq!(rtt.format(Duration::from_secs(2)), "2000.00ms");
+        assert_eq!(rtt.format(Duration::from_micros(50)), "0.05ms");
+
+        let rtt = RttFormat::new(RttUnit::Micros, 0);
+        assert_eq!(rtt.format(Duration::from_millis(3)), "3000us");
+        assert_eq!(rtt.value(Duration::from_millis(3)), "3000");
+
+        let rtt = RttFormat::new(RttUnit::Secs, 3);
+        assert_eq!(rtt.format(Duration::from_millis(1500)), "1.500s");
+
+        assert_eq!("us".parse::<RttUnit>(), Ok(RttUnit::Micros));
+        assert_eq!("s".parse::<RttUnit>(), Ok(RttUnit::Secs));
+        assert!("minutes".parse::<RttUnit>().is_err());
+    }
+}
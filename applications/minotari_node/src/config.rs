@@ -21,10 +21,13 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashMap,
+    net::SocketAddr,
     path::{Path, PathBuf},
     time::Duration,
 };
 
+use chrono::NaiveDate;
 use config::Config;
 use serde::{Deserialize, Serialize};
 use tari_common::{
@@ -33,14 +36,22 @@ use tari_common::{
     DefaultConfigLoader,
     SubConfigPath,
 };
-use tari_common_types::grpc_authentication::GrpcAuthentication;
+use tari_common_types::{grpc_authentication::GrpcAuthentication, types::PublicKey};
 use tari_comms::multiaddr::Multiaddr;
 use tari_core::{
-    base_node::BaseNodeStateMachineConfig,
+    base_node::{
+        tari_pulse_service::{TariPulseConfig, TariPulseProfile},
+        BaseNodeStateMachineConfig,
+    },
     chain_storage::BlockchainDatabaseConfig,
     mempool::MempoolConfig,
 };
-use tari_p2p::{auto_update::AutoUpdateConfig, P2pConfig, PeerSeedsConfig};
+use tari_p2p::{
+    auto_update::AutoUpdateConfig,
+    services::monitor_peers::{CullPolicy, MonitorPeersConfig},
+    P2pConfig,
+    PeerSeedsConfig,
+};
 use tari_storage::lmdb_store::LMDBConfig;
 
 use crate::grpc_method::GrpcMethod;
@@ -69,6 +80,7 @@ impl ApplicationConfig {
         };
 
         config.base_node.set_base_path(config.common.base_path());
+        config.base_node.validate()?;
         Ok(config)
     }
 
@@ -138,13 +150,57 @@ pub struct BaseNodeConfig {
     /// Liveness metadata auto ping interval between peers
     #[serde(with = "serializers::seconds")]
     pub metadata_auto_ping_interval: Duration,
+    /// The settings for monitoring the liveness of connected peers and culling unresponsive ones
+    pub monitor_peers: MonitorPeersConfig,
+    /// The liveness history kept per monitored peer and the number of failed rounds before a peer is culled
+    pub monitor_peers_cull_policy: CullPolicy,
     /// The state_machine config settings
     pub state_machine: BaseNodeStateMachineConfig,
     /// Obscure GRPC error responses
     pub report_grpc_error: bool,
+    /// When set, the estimated hash rates reported over GRPC are smoothed with an exponential moving average over
+    /// successive tips, using this weight in (0, 1] for each new estimate
+    pub grpc_hash_rate_smoothing_factor: Option<f64>,
+    /// When set, the block templates cached for GRPC mining requests are evicted once no template has been requested
+    /// for this long, freeing their memory while no miners are connected
+    #[serde(with = "serializers::optional_seconds")]
+    pub grpc_template_cache_idle_timeout: Option<Duration>,
     // Interval to check if the base node is still in sync with the network
     #[serde(with = "serializers::seconds")]
     pub tari_pulse_interval: Duration,
+    /// Enable the DNS checkpoint checks, which can be disabled on private or offline networks
+    pub tari_pulse_enabled: bool,
+    /// Log every raw TXT record received from the DNS checkpoint checks at debug level before it is parsed
+    pub tari_pulse_log_raw_answers: bool,
+    /// How long to wait after startup before the first checkpoint check, giving the node time to start syncing
+    #[serde(with = "serializers::seconds")]
+    pub tari_pulse_initial_delay: Duration,
+    /// The number of consecutive checks that must report a checkpoint mismatch before the node is flagged as having
+    /// failed the checkpoints
+    pub tari_pulse_consecutive_failures_before_notify: usize,
+    /// The DNS resolvers that the checkpoint checks query, in order. The next resolver is tried if one cannot be
+    /// reached or fails DNSSEC validation.
+    pub tari_pulse_dns_resolvers: Vec<SocketAddr>,
+    /// When set, a warning is logged if DNSSEC validation still relies on the original root trust anchor (key tag
+    /// 20326) after this date, e.g. the end of a KSK rollover
+    pub tari_pulse_legacy_trust_anchor_deadline: Option<NaiveDate>,
+    /// When set, a warning is logged at startup if the current date is past this date, as a reminder to check that
+    /// the DNSSEC trust anchors compiled into this build are still current
+    pub tari_pulse_anchor_review_date: Option<NaiveDate>,
+    /// The CSV file that the result of every checkpoint check is appended to, relative to the data dir. An empty path
+    /// disables the history.
+    pub tari_pulse_history_file: Option<PathBuf>,
+    /// The size in bytes at which the checkpoint check history file is rotated
+    pub tari_pulse_history_file_max_size: u64,
+    /// A local file of `height:hash` checkpoints, relative to the config dir, that the checkpoint checks use when the
+    /// DNS checkpoints cannot be fetched, e.g. in air-gapped or DNS-blocked environments
+    pub tari_pulse_checkpoint_file: Option<PathBuf>,
+    /// When set, the checkpoint file must be signed with this key
+    pub tari_pulse_checkpoint_file_public_key: Option<PublicKey>,
+    /// Use the checkpoint file instead of querying DNS
+    pub tari_pulse_prefer_checkpoint_file: bool,
+    /// Per-network overrides of the checkpoint check schedule, so that nodes on multiple networks can share one config
+    pub tari_pulse_profiles: HashMap<Network, TariPulseProfile>,
 }
 
 impl Default for BaseNodeConfig {
@@ -153,6 +209,7 @@ impl Default for BaseNodeConfig {
             datastore_path: PathBuf::from("peer_db/base_node"),
             ..Default::default()
         };
+        let tari_pulse = TariPulseConfig::default();
         Self {
             override_from: None,
             network: Network::default(),
@@ -181,9 +238,26 @@ impl Default for BaseNodeConfig {
             status_line_interval: Duration::from_secs(5),
             buffer_size: 1_500,
             metadata_auto_ping_interval: Duration::from_secs(30),
+            monitor_peers: MonitorPeersConfig::default(),
+            monitor_peers_cull_policy: CullPolicy::default(),
             state_machine: Default::default(),
             report_grpc_error: false,
+            grpc_hash_rate_smoothing_factor: None,
+            grpc_template_cache_idle_timeout: None,
             tari_pulse_interval: Duration::from_secs(120),
+            tari_pulse_enabled: true,
+            tari_pulse_log_raw_answers: false,
+            tari_pulse_initial_delay: tari_pulse.initial_delay,
+            tari_pulse_consecutive_failures_before_notify: tari_pulse.consecutive_failures_before_notify,
+            tari_pulse_dns_resolvers: tari_pulse.dns_resolvers,
+            tari_pulse_legacy_trust_anchor_deadline: None,
+            tari_pulse_anchor_review_date: None,
+            tari_pulse_history_file: Some(PathBuf::from("tari_pulse_history.csv")),
+            tari_pulse_history_file_max_size: tari_pulse.history_file_max_size,
+            tari_pulse_checkpoint_file: None,
+            tari_pulse_checkpoint_file_public_key: None,
+            tari_pulse_prefer_checkpoint_file: false,
+            tari_pulse_profiles: HashMap::new(),
         }
     }
 }
@@ -211,8 +285,45 @@ impl BaseNodeConfig {
         if !self.lmdb_path.is_absolute() {
             self.lmdb_path = self.data_dir.join(self.lmdb_path.as_path());
         }
+        if let Some(history_file) = self.tari_pulse_history_file.as_mut() {
+            if !history_file.as_os_str().is_empty() && !history_file.is_absolute() {
+                *history_file = self.data_dir.join(history_file.as_path());
+            }
+        }
+        if let Some(checkpoint_file) = self.tari_pulse_checkpoint_file.as_mut() {
+            if !checkpoint_file.is_absolute() {
+                *checkpoint_file = self.config_dir.join(checkpoint_file.as_path());
+            }
+        }
         self.p2p.set_base_path(base_path);
     }
+
+    pub fn validate(&self) -> Result<(), ConfigurationError> {
+        if let Some(factor) = self.grpc_hash_rate_smoothing_factor {
+            if !(factor > 0.0 && factor <= 1.0) {
+                return Err(ConfigurationError::new(
+                    "base_node.grpc_hash_rate_smoothing_factor",
+                    Some(factor.to_string()),
+                    "must be greater than 0 and at most 1",
+                ));
+            }
+        }
+        if self.tari_pulse_prefer_checkpoint_file && self.tari_pulse_checkpoint_file.is_none() {
+            return Err(ConfigurationError::new(
+                "base_node.tari_pulse_prefer_checkpoint_file",
+                Some("true".to_string()),
+                "requires tari_pulse_checkpoint_file to be set",
+            ));
+        }
+        if self.tari_pulse_enabled && self.tari_pulse_dns_resolvers.is_empty() {
+            return Err(ConfigurationError::new(
+                "base_node.tari_pulse_dns_resolvers",
+                None,
+                "at least one DNS resolver is required while tari_pulse_enabled is set",
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +123,10 @@ pub struct BaseNodeGrpcServer {
 
 impl BaseNodeGrpcServer {
     pub fn from_base_node_context(ctx: &BaseNodeContext, config: BaseNodeConfig) -> Self {
+        let data_cache = DataCache::new().with_hash_rate_smoothing(config.grpc_hash_rate_smoothing_factor);
+        if let Some(idle_timeout) = config.grpc_template_cache_idle_timeout {
+            data_cache.spawn_idle_eviction(idle_timeout);
+        }
         Self {
             node_service: ctx.local_node(),
             mempool_service: ctx.local_mempool(),
@@ -134,8 +138,8 @@ impl BaseNodeGrpcServer {
             liveness: ctx.liveness(),
             report_grpc_error: ctx.get_report_grpc_error(),
             tari_pulse: ctx.tari_pulse(),
+            data_cache,
             config,
-            data_cache: DataCache::new(),
         }
     }
 
@@ -409,8 +413,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 let estimated_hash_rate = target_difficulty.as_u64() / target_time;
                 self.data_cache
                     .set_sha3x_estimated_hash_rate(estimated_hash_rate, *metadata.best_block_hash())
-                    .await;
-                estimated_hash_rate
+                    .await
             },
         };
         let randomx_estimated_hash_rate = match self
@@ -435,12 +438,11 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 let estimated_hash_rate = target_difficulty.as_u64() / target_time;
                 self.data_cache
                     .set_randomx_estimated_hash_rate(estimated_hash_rate, *metadata.best_block_hash())
-                    .await;
-                estimated_hash_rate
+                    .await
             },
         };
 
-        let failed_checkpoints = *self.tari_pulse.get_failed_checkpoints_notifier();
+        let failed_checkpoints = self.tari_pulse.status().has_failed();
         let status_watch = self.state_machine_handle.get_status_info_watch();
         let state: tari_rpc::BaseNodeState = (&status_watch.borrow().state_info).into();
 
@@ -727,62 +729,33 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
         })?;
 
-        let new_template = match algo {
-            PowAlgorithm::Sha3x => {
-                match self
-                    .data_cache
-                    .get_sha3x_new_block_template(metadata.best_block_hash())
-                    .await
-                {
-                    Some(template) => template,
-                    None => {
-                        let new_template =
-                            handler
-                                .get_new_block_template(algo, request.max_weight)
-                                .await
-                                .map_err(|e| {
-                                    warn!(
-                                        target: LOG_TARGET,
-                                        "Could not get new block template: {}",
-                                        e.to_string()
-                                    );
-                                    obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
-                                })?;
-                        self.data_cache
-                            .set_sha3x_new_block_template(new_template.clone(), *metadata.best_block_hash())
-                            .await;
-                        new_template
-                    },
-                }
-            },
-            PowAlgorithm::RandomX => {
-                match self
-                    .data_cache
-                    .get_randomx_new_block_template(metadata.best_block_hash())
+        let new_template = match self
+            .data_cache
+            .get_best_template_for(metadata.best_block_hash(), algo)
+            .await
+        {
+            Some(template) => template,
+            None => {
+                let new_template = handler
+                    .get_new_block_template(algo, request.max_weight)
                     .await
-                {
-                    Some(template) => template,
-                    None => {
-                        let new_template =
-                            handler
-                                .get_new_block_template(algo, request.max_weight)
-                                .await
-                                .map_err(|e| {
-                                    warn!(
-                                        target: LOG_TARGET,
-                                        "Could not get new block template: {}",
-                                        e.to_string()
-                                    );
-                                    obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
-                                })?;
-                        self.data_cache
-                            .set_randomx_new_block_template(new_template.clone(), *metadata.best_block_hash())
-                            .await;
-                        new_template
-                    },
-                }
+                    .map_err(|e| {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Could not get new block template: {}",
+                            e.to_string()
+                        );
+                        obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+                    })?;
+                self.data_cache
+                    .set_new_block_template_for(new_template.clone(), *metadata.best_block_hash(), algo)
+                    .await;
+                new_template
             },
         };
+        if log_enabled!(target: LOG_TARGET, Level::Trace) {
+            trace!(target: LOG_TARGET, "Data cache entry ages: {}", self.data_cache.entry_ages().await);
+        }
 
         let status_watch = self.state_machine_handle.get_status_info_watch();
         let pow = algo as i32;
@@ -1805,7 +1778,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         trace!(target: LOG_TARGET, "Incoming GRPC request for BN tip data");
 
         let mut handler = self.node_service.clone();
-        let failed_checkpoints = *self.tari_pulse.get_failed_checkpoints_notifier();
+        let failed_checkpoints = self.tari_pulse.status().has_failed();
 
         let meta = handler
             .get_metadata()
@@ -20,23 +20,72 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::sync::Arc;
+use std::{
+    fmt::{Display, Formatter},
+    sync::{Arc, Mutex, PoisonError, Weak},
+    time::Duration,
+};
 
+use log::*;
 use tari_common_types::types::FixedHash;
-use tari_core::blocks::NewBlockTemplate;
-use tokio::sync::RwLock;
+use tari_core::{blocks::NewBlockTemplate, proof_of_work::PowAlgorithm};
+use tokio::{
+    sync::{watch, RwLock},
+    task,
+    time::{self, Instant},
+};
+
+const LOG_TARGET: &str = "minotari::base_node::grpc::data_cache";
 
 pub struct DataCache {
     inner_data_cache: Arc<RwLock<InnerDataCache>>,
+    tip_sender: watch::Sender<FixedHash>,
+    hash_rate_smoothing_factor: Option<f64>,
 }
 
 impl DataCache {
     pub fn new() -> Self {
+        let (tip_sender, _) = watch::channel(FixedHash::default());
         Self {
             inner_data_cache: Arc::new(RwLock::new(InnerDataCache::default())),
+            tip_sender,
+            hash_rate_smoothing_factor: None,
         }
     }
 
+    /// Blend the estimated hash rate of each new tip with the one cached for the previous tip using an exponential
+    /// moving average, where `factor` is the weight of the new estimate. The factor must be in (0, 1], which
+    /// `BaseNodeConfig::validate` enforces; a factor outside of it is ignored with a warning.
+    pub fn with_hash_rate_smoothing(mut self, factor: Option<f64>) -> Self {
+        self.hash_rate_smoothing_factor = factor.filter(|factor| {
+            let is_valid = *factor > 0.0 && *factor <= 1.0;
+            if !is_valid {
+                warn!(
+                    target: LOG_TARGET,
+                    "Hash rate smoothing factor {} is not in (0, 1], estimated hash rates will not be smoothed", factor
+                );
+            }
+            is_valid
+        });
+        self
+    }
+
+    /// Subscribe to changes of the tip that cached data was last stored against
+    pub fn subscribe_tip_changes(&self) -> watch::Receiver<FixedHash> {
+        self.tip_sender.subscribe()
+    }
+
+    fn notify_tip(&self, tip: FixedHash) {
+        self.tip_sender.send_if_modified(|current| {
+            if *current == tip {
+                false
+            } else {
+                *current = tip;
+                true
+            }
+        });
+    }
+
     pub async fn get_randomx_estimated_hash_rate(&self, current_tip: &FixedHash) -> Option<u64> {
         let res = &self.inner_data_cache.read().await.randomx_estimated_hash_rate;
         if res.tip == *current_tip {
@@ -55,40 +104,165 @@ impl DataCache {
         }
     }
 
-    pub async fn set_randomx_estimated_hash_rate(&self, hash_rate: u64, current_tip: FixedHash) {
-        self.inner_data_cache.write().await.randomx_estimated_hash_rate = DataCacheData::new(hash_rate, current_tip);
-    }
-
-    pub async fn set_sha3x_estimated_hash_rate(&self, hash_rate: u64, current_tip: FixedHash) {
-        self.inner_data_cache.write().await.sha3x_estimated_hash_rate = DataCacheData::new(hash_rate, current_tip);
+    /// Stores the estimated hash rate for `current_tip` and returns the value that was cached, which is smoothed if
+    /// smoothing is enabled
+    pub async fn set_randomx_estimated_hash_rate(&self, hash_rate: u64, current_tip: FixedHash) -> u64 {
+        let mut cache = self.inner_data_cache.write().await;
+        let hash_rate = self.smooth_hash_rate(&cache.randomx_estimated_hash_rate, hash_rate, &current_tip);
+        cache.randomx_estimated_hash_rate = DataCacheData::new(hash_rate, current_tip);
+        self.notify_tip(current_tip);
+        hash_rate
     }
 
-    pub async fn get_randomx_new_block_template(&self, current_tip: &FixedHash) -> Option<NewBlockTemplate> {
-        let res = &self.inner_data_cache.read().await.randomx_new_block_template;
-        if res.tip == *current_tip {
-            Some(res.data.clone())
-        } else {
-            None
-        }
+    /// Stores the estimated hash rate for `current_tip` and returns the value that was cached, which is smoothed if
+    /// smoothing is enabled
+    pub async fn set_sha3x_estimated_hash_rate(&self, hash_rate: u64, current_tip: FixedHash) -> u64 {
+        let mut cache = self.inner_data_cache.write().await;
+        let hash_rate = self.smooth_hash_rate(&cache.sha3x_estimated_hash_rate, hash_rate, &current_tip);
+        cache.sha3x_estimated_hash_rate = DataCacheData::new(hash_rate, current_tip);
+        self.notify_tip(current_tip);
+        hash_rate
     }
 
-    pub async fn get_sha3x_new_block_template(&self, current_tip: &FixedHash) -> Option<NewBlockTemplate> {
-        let res = &self.inner_data_cache.read().await.sha3x_new_block_template;
-        if res.tip == *current_tip {
-            Some(res.data.clone())
-        } else {
-            None
+    /// The hash rate to cache. With smoothing enabled, the estimate for a new tip is blended with the average cached
+    /// for the previous tip, and the average of an unchanged tip is kept so that it is not weighted twice. Without
+    /// smoothing, or before the first estimate, the new estimate is cached as is.
+    #[allow(clippy::cast_possible_truncation)]
+    fn smooth_hash_rate(&self, cached: &DataCacheData<u64>, hash_rate: u64, current_tip: &FixedHash) -> u64 {
+        match self.hash_rate_smoothing_factor {
+            Some(_) if cached.updated_at.is_some() && cached.tip == *current_tip => cached.data,
+            Some(factor) if cached.updated_at.is_some() => {
+                (factor * hash_rate as f64 + (1.0 - factor) * cached.data as f64).round() as u64
+            },
+            _ => hash_rate,
         }
     }
 
     pub async fn set_randomx_new_block_template(&self, new_block_template: NewBlockTemplate, current_tip: FixedHash) {
         self.inner_data_cache.write().await.randomx_new_block_template =
             DataCacheData::new(new_block_template, current_tip);
+        self.notify_tip(current_tip);
     }
 
     pub async fn set_sha3x_new_block_template(&self, new_block_template: NewBlockTemplate, current_tip: FixedHash) {
         self.inner_data_cache.write().await.sha3x_new_block_template =
             DataCacheData::new(new_block_template, current_tip);
+        self.notify_tip(current_tip);
+    }
+
+    /// The cached template for the requested algorithm, if it was built on `current_tip`. The template of the other
+    /// algorithm is never served in its place, even if it is current.
+    pub async fn get_best_template_for(&self, current_tip: &FixedHash, algo: PowAlgorithm) -> Option<NewBlockTemplate> {
+        let cache = self.inner_data_cache.read().await;
+        let (requested, other) = match algo {
+            PowAlgorithm::RandomX => (&cache.randomx_new_block_template, &cache.sha3x_new_block_template),
+            PowAlgorithm::Sha3x => (&cache.sha3x_new_block_template, &cache.randomx_new_block_template),
+        };
+        requested.touch();
+        if requested.tip == *current_tip {
+            return Some(requested.data.clone());
+        }
+        if other.tip == *current_tip {
+            debug!(
+                target: LOG_TARGET,
+                "Only the other algorithm's template is cached for tip {}, a {} template must be built", current_tip, algo
+            );
+        } else {
+            debug!(
+                target: LOG_TARGET,
+                "No template is cached for tip {}, a {} template must be built", current_tip, algo
+            );
+        }
+        None
+    }
+
+    pub async fn set_new_block_template_for(
+        &self,
+        new_block_template: NewBlockTemplate,
+        current_tip: FixedHash,
+        algo: PowAlgorithm,
+    ) {
+        match algo {
+            PowAlgorithm::RandomX => {
+                self.set_randomx_new_block_template(new_block_template, current_tip)
+                    .await
+            },
+            PowAlgorithm::Sha3x => self.set_sha3x_new_block_template(new_block_template, current_tip).await,
+        }
+    }
+
+    /// Evicts the cached block templates that have not been set or requested since `idle_since`, to free their memory
+    /// while no miners are connected. An evicted template is rebuilt on the next request. Returns the number of
+    /// templates that were evicted.
+    pub async fn clear_if_idle(&self, idle_since: Instant) -> usize {
+        self.inner_data_cache.write().await.clear_templates_if_idle(idle_since)
+    }
+
+    /// Periodically evicts the cached block templates that have not been set or requested for `idle_timeout`. The
+    /// task ends when the cache is dropped.
+    pub fn spawn_idle_eviction(&self, idle_timeout: Duration) {
+        let inner_data_cache = Arc::downgrade(&self.inner_data_cache);
+        task::spawn(evict_idle_templates(inner_data_cache, idle_timeout));
+    }
+
+    /// How long ago each cached entry was last set, e.g. to spot a block template that has not been refreshed in a
+    /// suspiciously long time
+    pub async fn entry_ages(&self) -> DataCacheAges {
+        self.entry_ages_at(Instant::now()).await
+    }
+
+    async fn entry_ages_at(&self, now: Instant) -> DataCacheAges {
+        let cache = self.inner_data_cache.read().await;
+        DataCacheAges {
+            randomx_estimated_hash_rate: cache.randomx_estimated_hash_rate.age(now),
+            sha3x_estimated_hash_rate: cache.sha3x_estimated_hash_rate.age(now),
+            randomx_new_block_template: cache.randomx_new_block_template.age(now),
+            sha3x_new_block_template: cache.sha3x_new_block_template.age(now),
+        }
+    }
+}
+
+/// The time since each cached entry was last set, `None` if it has never been set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DataCacheAges {
+    pub randomx_estimated_hash_rate: Option<Duration>,
+    pub sha3x_estimated_hash_rate: Option<Duration>,
+    pub randomx_new_block_template: Option<Duration>,
+    pub sha3x_new_block_template: Option<Duration>,
+}
+
+impl Display for DataCacheAges {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let age = |age: Option<Duration>| age.map_or_else(|| "never set".to_string(), |age| format!("{:.0?}", age));
+        write!(
+            f,
+            "randomx hash rate: {}, sha3x hash rate: {}, randomx template: {}, sha3x template: {}",
+            age(self.randomx_estimated_hash_rate),
+            age(self.sha3x_estimated_hash_rate),
+            age(self.randomx_new_block_template),
+            age(self.sha3x_new_block_template)
+        )
+    }
+}
+
+async fn evict_idle_templates(inner_data_cache: Weak<RwLock<InnerDataCache>>, idle_timeout: Duration) {
+    let mut interval = time::interval(idle_timeout);
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(inner_data_cache) = inner_data_cache.upgrade() else {
+            break;
+        };
+        let Some(idle_since) = Instant::now().checked_sub(idle_timeout) else {
+            continue;
+        };
+        let evicted = inner_data_cache.write().await.clear_templates_if_idle(idle_since);
+        if evicted > 0 {
+            debug!(
+                target: LOG_TARGET,
+                "Evicted {} block template(s) that were idle for {:.0?}", evicted, idle_timeout
+            );
+        }
     }
 }
 
@@ -98,6 +272,19 @@ struct InnerDataCache {
     pub sha3x_new_block_template: DataCacheData<NewBlockTemplate>,
     pub randomx_new_block_template: DataCacheData<NewBlockTemplate>,
 }
+
+impl InnerDataCache {
+    fn clear_templates_if_idle(&mut self, idle_since: Instant) -> usize {
+        let mut evicted = 0;
+        for template in [&mut self.randomx_new_block_template, &mut self.sha3x_new_block_template] {
+            if template.updated_at.is_some() && template.is_idle_since(idle_since) {
+                *template = DataCacheData::new_empty(NewBlockTemplate::empty());
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+}
 impl Default for InnerDataCache {
     fn default() -> Self {
         Self {
@@ -112,17 +299,262 @@ impl Default for InnerDataCache {
 struct DataCacheData<T> {
     pub data: T,
     pub tip: FixedHash,
+    pub updated_at: Option<Instant>,
+    /// When the entry was last set or requested. Requests only hold the cache's read lock, so this has its own.
+    accessed_at: Mutex<Option<Instant>>,
 }
 
 impl<T> DataCacheData<T> {
     pub fn new(data: T, tip: FixedHash) -> Self {
-        Self { data, tip }
+        let now = Instant::now();
+        Self {
+            data,
+            tip,
+            updated_at: Some(now),
+            accessed_at: Mutex::new(Some(now)),
+        }
     }
 
     pub fn new_empty(data: T) -> Self {
         Self {
             data,
             tip: FixedHash::default(),
+            updated_at: None,
+            accessed_at: Mutex::new(None),
         }
     }
+
+    /// Marks the entry as requested now
+    pub fn touch(&self) {
+        *self.accessed_at.lock().unwrap_or_else(PoisonError::into_inner) = Some(Instant::now());
+    }
+
+    /// True if the entry has not been set or requested after `idle_since`
+    pub fn is_idle_since(&self, idle_since: Instant) -> bool {
+        self.accessed_at
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .map_or(true, |accessed_at| accessed_at <= idle_since)
+    }
+
+    pub fn age(&self, now: Instant) -> Option<Duration> {
+        self.updated_at
+            .map(|updated_at| now.saturating_duration_since(updated_at))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::runtime::{self, Runtime};
+
+    use super::*;
+
+    #[test]
+    fn it_emits_each_new_tip_stored_in_the_cache() {
+        Runtime::new().unwrap().block_on(async {
+            let cache = DataCache::new();
+            let mut tip_changes = cache.subscribe_tip_changes();
+            let tip_a = FixedHash::from([1u8; 32]);
+            let tip_b = FixedHash::from([2u8; 32]);
+
+            cache
+                .set_randomx_new_block_template(NewBlockTemplate::empty(), tip_a)
+                .await;
+            assert!(tip_changes.has_changed().unwrap());
+            assert_eq!(*tip_changes.borrow_and_update(), tip_a);
+
+            // Storing more data against the same tip is not a tip change
+            cache
+                .set_sha3x_new_block_template(NewBlockTemplate::empty(), tip_a)
+                .await;
+            assert!(!tip_changes.has_changed().unwrap());
+
+            cache
+                .set_sha3x_new_block_template(NewBlockTemplate::empty(), tip_b)
+                .await;
+            assert!(tip_changes.has_changed().unwrap());
+            assert_eq!(*tip_changes.borrow_and_update(), tip_b);
+        });
+    }
+
+    #[test]
+    fn it_smooths_estimated_hash_rates_over_successive_tips() {
+        Runtime::new().unwrap().block_on(async {
+            let cache = DataCache::new().with_hash_rate_smoothing(Some(0.5));
+            let tips = (1..=21u8).map(|i| FixedHash::from([i; 32])).collect::<Vec<_>>();
+
+            assert_eq!(cache.set_sha3x_estimated_hash_rate(0, tips[0]).await, 0);
+            assert_eq!(cache.get_sha3x_estimated_hash_rate(&tips[0]).await, Some(0));
+            // A steady input is approached a step at a time, one tip after the other
+            let mut previous = 0;
+            for tip in &tips[1..] {
+                let smoothed = cache.set_sha3x_estimated_hash_rate(1_000_000, *tip).await;
+                assert_eq!(cache.get_sha3x_estimated_hash_rate(tip).await, Some(smoothed));
+                assert!(smoothed > previous || smoothed == 1_000_000);
+                previous = smoothed;
+            }
+            assert!(1_000_000 - previous <= 1);
+
+            // Storing another estimate against an unchanged tip does not weigh it twice
+            let tip_a = FixedHash::from([1u8; 32]);
+            let tip_b = FixedHash::from([2u8; 32]);
+            let cache = DataCache::new().with_hash_rate_smoothing(Some(0.5));
+            cache.set_sha3x_estimated_hash_rate(100, tip_a).await;
+            assert_eq!(cache.set_sha3x_estimated_hash_rate(300, tip_b).await, 200);
+            assert_eq!(cache.set_sha3x_estimated_hash_rate(300, tip_b).await, 200);
+
+            // Without smoothing the cached value is overwritten
+            let cache = DataCache::new();
+            cache.set_randomx_estimated_hash_rate(10, tip_a).await;
+            cache.set_randomx_estimated_hash_rate(20, tip_a).await;
+            assert_eq!(cache.get_randomx_estimated_hash_rate(&tip_a).await, Some(20));
+        });
+    }
+
+    #[test]
+    fn it_reports_the_age_of_each_cached_entry() {
+        Runtime::new().unwrap().block_on(async {
+            let cache = DataCache::new();
+            let tip = FixedHash::from([1u8; 32]);
+            assert_eq!(cache.entry_ages().await, DataCacheAges::default());
+
+            cache
+                .set_randomx_new_block_template(NewBlockTemplate::empty(), tip)
+                .await;
+            cache.set_sha3x_estimated_hash_rate(100, tip).await;
+            let now = Instant::now();
+
+            let ages = cache.entry_ages_at(now + Duration::from_secs(5)).await;
+            let randomx_template_age = ages.randomx_new_block_template.unwrap();
+            assert!(randomx_template_age >= Duration::from_secs(5));
+            assert!(ages.sha3x_estimated_hash_rate.unwrap() >= Duration::from_secs(5));
+            // Entries that were never set have no age
+            assert_eq!(ages.randomx_estimated_hash_rate, None);
+            assert_eq!(ages.sha3x_new_block_template, None);
+
+            // Ages grow as the clock advances
+            let later = cache.entry_ages_at(now + Duration::from_secs(65)).await;
+            assert_eq!(
+                later.randomx_new_block_template.unwrap() - randomx_template_age,
+                Duration::from_secs(60)
+            );
+
+            // Refreshing an entry resets its age
+            cache
+                .set_randomx_new_block_template(NewBlockTemplate::empty(), tip)
+                .await;
+            let refreshed = cache.entry_ages_at(now + Duration::from_secs(65)).await;
+            assert!(refreshed.randomx_new_block_template.unwrap() < later.randomx_new_block_template.unwrap());
+        });
+    }
+
+    #[test]
+    fn it_clears_templates_that_are_idle() {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let cache = DataCache::new();
+            let tip = FixedHash::from([1u8; 32]);
+            // Nothing to evict
+            assert_eq!(cache.clear_if_idle(Instant::now()).await, 0);
+
+            cache
+                .set_new_block_template_for(NewBlockTemplate::empty(), tip, PowAlgorithm::RandomX)
+                .await;
+            cache
+                .set_new_block_template_for(NewBlockTemplate::empty(), tip, PowAlgorithm::Sha3x)
+                .await;
+            cache.set_sha3x_estimated_hash_rate(100, tip).await;
+            time::advance(Duration::from_millis(5)).await;
+            let idle_since = Instant::now();
+            time::advance(Duration::from_millis(5)).await;
+
+            // Only the RandomX template is requested after the idle window started
+            assert!(cache
+                .get_best_template_for(&tip, PowAlgorithm::RandomX)
+                .await
+                .is_some());
+            assert_eq!(cache.clear_if_idle(idle_since).await, 1);
+            assert!(cache
+                .get_best_template_for(&tip, PowAlgorithm::RandomX)
+                .await
+                .is_some());
+            assert!(cache.get_best_template_for(&tip, PowAlgorithm::Sha3x).await.is_none());
+            assert_eq!(cache.entry_ages().await.sha3x_new_block_template, None);
+            // Estimated hash rates are not evicted
+            assert_eq!(cache.get_sha3x_estimated_hash_rate(&tip).await, Some(100));
+
+            // With no intervening access, the remaining template is evicted too
+            assert_eq!(cache.clear_if_idle(Instant::now()).await, 1);
+            assert!(cache
+                .get_best_template_for(&tip, PowAlgorithm::RandomX)
+                .await
+                .is_none());
+        });
+    }
+
+    #[test]
+    fn it_only_serves_a_current_template_of_the_requested_algorithm() {
+        Runtime::new().unwrap().block_on(async {
+            let cache = DataCache::new();
+            let tip_a = FixedHash::from([1u8; 32]);
+            let tip_b = FixedHash::from([2u8; 32]);
+            let mut sha3x_template = NewBlockTemplate::empty();
+            sha3x_template.header.pow.pow_algo = PowAlgorithm::Sha3x;
+            sha3x_template.header.height = 10;
+            let mut randomx_template = NewBlockTemplate::empty();
+            randomx_template.header.pow.pow_algo = PowAlgorithm::RandomX;
+            randomx_template.header.height = 11;
+
+            // Absent
+            for algo in [PowAlgorithm::RandomX, PowAlgorithm::Sha3x] {
+                assert!(cache.get_best_template_for(&tip_a, algo).await.is_none());
+            }
+
+            // Only a Sha3x template is cached for the tip, so it is not served for RandomX
+            cache
+                .set_new_block_template_for(sha3x_template.clone(), tip_a, PowAlgorithm::Sha3x)
+                .await;
+            let template = cache.get_best_template_for(&tip_a, PowAlgorithm::Sha3x).await.unwrap();
+            assert_eq!(template.header, sha3x_template.header);
+            assert!(cache
+                .get_best_template_for(&tip_a, PowAlgorithm::RandomX)
+                .await
+                .is_none());
+
+            // Both present
+            cache
+                .set_new_block_template_for(randomx_template.clone(), tip_a, PowAlgorithm::RandomX)
+                .await;
+            let template = cache
+                .get_best_template_for(&tip_a, PowAlgorithm::RandomX)
+                .await
+                .unwrap();
+            assert_eq!(template.header, randomx_template.header);
+            let template = cache.get_best_template_for(&tip_a, PowAlgorithm::Sha3x).await.unwrap();
+            assert_eq!(template.header, sha3x_template.header);
+
+            // Both stale once the tip moves on
+            for algo in [PowAlgorithm::RandomX, PowAlgorithm::Sha3x] {
+                assert!(cache.get_best_template_for(&tip_b, algo).await.is_none());
+            }
+
+            // One current, one stale
+            cache
+                .set_new_block_template_for(randomx_template.clone(), tip_b, PowAlgorithm::RandomX)
+                .await;
+            assert!(cache
+                .get_best_template_for(&tip_b, PowAlgorithm::RandomX)
+                .await
+                .is_some());
+            assert!(cache.get_best_template_for(&tip_b, PowAlgorithm::Sha3x).await.is_none());
+            assert!(cache
+                .get_best_template_for(&tip_a, PowAlgorithm::RandomX)
+                .await
+                .is_none());
+        });
+    }
 }
@@ -119,6 +119,10 @@ pub trait BaseNodeWalletService: Send + Sync + 'static {
         &self,
         request: Request<GetMempoolFeePerGramStatsRequest>,
     ) -> Result<Response<GetMempoolFeePerGramStatsResponse>, RpcStatus>;
+
+    /// The wire byte of the network the base node is running on
+    #[rpc(method = 13)]
+    async fn get_network(&self, request: Request<()>) -> Result<Response<u32>, RpcStatus>;
 }
 
 #[cfg(feature = "base_node")]
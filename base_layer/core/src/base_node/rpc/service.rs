@@ -677,4 +677,9 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
 
         Ok(Response::new(stats.into()))
     }
+
+    async fn get_network(&self, _request: Request<()>) -> Result<Response<u32>, RpcStatus> {
+        let network = self.db().inner().rules().network().as_network();
+        Ok(Response::new(u32::from(network.as_byte())))
+    }
 }
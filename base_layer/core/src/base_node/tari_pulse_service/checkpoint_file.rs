@@ -0,0 +1,198 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use blake2::Blake2b;
+use digest::consts::U64;
+use rand::rngs::OsRng;
+use tari_common_types::types::{PrivateKey, PublicKey, Signature};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey as PublicKeyT};
+use tari_utilities::{hex::Hex, ByteArray};
+
+use super::PulseError;
+
+hash_domain!(
+    CheckpointFileHashDomain,
+    "com.tari.base_layer.core.base_node.tari_pulse.checkpoint_file",
+    0
+);
+
+const SIGNATURE_PREFIX: &str = "signature:";
+
+/// Checkpoints read from a local file, an alternative to DNS in air-gapped or DNS-blocked environments. The file has
+/// one `height:hash` checkpoint per line, the same format as the DNS records; blank lines and lines starting with `#`
+/// are ignored. A file can be signed with a trailing `signature:<public nonce><signature>` line over its checkpoints,
+/// which is required when a public key is configured.
+#[derive(Debug, Clone)]
+pub struct CheckpointFile {
+    path: PathBuf,
+    public_key: Option<PublicKey>,
+}
+
+impl CheckpointFile {
+    pub fn new<P: Into<PathBuf>>(path: P, public_key: Option<PublicKey>) -> Self {
+        Self {
+            path: path.into(),
+            public_key,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the checkpoints from the file, verifying the signature if a public key is configured
+    pub fn load(&self) -> Result<Vec<(u64, String)>, PulseError> {
+        let contents = fs::read_to_string(&self.path).map_err(|e| self.error(format!("could not be read: {}", e)))?;
+        let (checkpoints, signature) = parse(&contents).map_err(|e| self.error(e))?;
+        if let Some(public_key) = self.public_key.as_ref() {
+            let signature = signature.ok_or_else(|| self.error("is not signed".to_string()))?;
+            let challenge = challenge(public_key, signature.get_public_nonce(), &checkpoints);
+            if !signature.verify_raw_uniform(public_key, &challenge) {
+                return Err(self.error("has an invalid signature".to_string()));
+            }
+        }
+        Ok(checkpoints)
+    }
+
+    /// Renders the checkpoints in the file format, signed with `private_key`
+    pub fn sign(private_key: &PrivateKey, checkpoints: &[(u64, String)]) -> String {
+        let (secret_nonce, public_nonce) = PublicKey::random_keypair(&mut OsRng);
+        let public_key = PublicKey::from_secret_key(private_key);
+        let challenge = challenge(&public_key, &public_nonce, checkpoints);
+        let signature = Signature::sign_raw_uniform(private_key, secret_nonce, &challenge)
+            .expect("Sign cannot fail with 64-byte challenge and a RistrettoPublicKey");
+        format!(
+            "{}{}{}{}\n",
+            message(checkpoints),
+            SIGNATURE_PREFIX,
+            signature.get_public_nonce().to_hex(),
+            signature.get_signature().to_hex()
+        )
+    }
+
+    fn error(&self, reason: String) -> PulseError {
+        PulseError::CheckpointFile(format!("{} {}", self.path.display(), reason))
+    }
+}
+
+/// The signed message, i.e. the checkpoints in canonical `height:hash` lines
+fn message(checkpoints: &[(u64, String)]) -> String {
+    checkpoints
+        .iter()
+        .map(|(height, hash)| format!("{}:{}\n", height, hash))
+        .collect()
+}
+
+fn challenge(public_key: &PublicKey, public_nonce: &PublicKey, checkpoints: &[(u64, String)]) -> [u8; 64] {
+    let hasher = DomainSeparatedHasher::<Blake2b<U64>, CheckpointFileHashDomain>::new_with_label("checkpoints")
+        .chain(public_key.as_bytes())
+        .chain(public_nonce.as_bytes())
+        .chain(message(checkpoints).as_bytes());
+    digest::Digest::finalize(hasher).into()
+}
+
+fn parse(contents: &str) -> Result<(Vec<(u64, String)>, Option<Signature>), String> {
+    let mut checkpoints = Vec::new();
+    let mut signature = None;
+    for (number, line) in contents.lines().enumerate().map(|(i, line)| (i + 1, line.trim())) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(hex) = line.strip_prefix(SIGNATURE_PREFIX) {
+            signature =
+                Some(parse_signature(hex).ok_or_else(|| format!("has an invalid signature on line {}", number))?);
+            continue;
+        }
+        let checkpoint = line
+            .split_once(':')
+            .and_then(|(height, hash)| Some((height.parse().ok()?, hash.to_string())))
+            .ok_or_else(|| format!("has an invalid checkpoint on line {}", number))?;
+        checkpoints.push(checkpoint);
+    }
+    if checkpoints.is_empty() {
+        return Err("does not contain any checkpoints".to_string());
+    }
+    Ok((checkpoints, signature))
+}
+
+fn parse_signature(hex: &str) -> Option<Signature> {
+    if hex.len() != 128 || !hex.is_ascii() {
+        return None;
+    }
+    let public_nonce = PublicKey::from_hex(&hex[..64]).ok()?;
+    let signature = PrivateKey::from_hex(&hex[64..]).ok()?;
+    Some(Signature::new(public_nonce, signature))
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+
+    fn checkpoints() -> Vec<(u64, String)> {
+        vec![(100, "aa".repeat(32)), (200, "bb".repeat(32))]
+    }
+
+    #[test]
+    fn it_loads_unsigned_checkpoints_ignoring_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoints.txt");
+        fs::write(&path, format!("# Mainnet checkpoints\n\n{}", message(&checkpoints()))).unwrap();
+
+        assert_eq!(CheckpointFile::new(&path, None).load().unwrap(), checkpoints());
+
+        fs::write(&path, "100:aa\nnot a checkpoint\n").unwrap();
+        assert!(matches!(
+            CheckpointFile::new(&path, None).load(),
+            Err(PulseError::CheckpointFile(_))
+        ));
+    }
+
+    #[test]
+    fn it_verifies_signed_checkpoint_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoints.txt");
+        let private_key = PrivateKey::random(&mut OsRng);
+        let public_key = PublicKey::from_secret_key(&private_key);
+        let signed = CheckpointFile::sign(&private_key, &checkpoints());
+        fs::write(&path, &signed).unwrap();
+
+        assert_eq!(
+            CheckpointFile::new(&path, Some(public_key.clone())).load().unwrap(),
+            checkpoints()
+        );
+
+        // A file signed by another key, a tampered file or an unsigned file is rejected
+        let other_key = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+        assert!(CheckpointFile::new(&path, Some(other_key)).load().is_err());
+        fs::write(&path, signed.replace(&"bb".repeat(32), &"cc".repeat(32))).unwrap();
+        assert!(CheckpointFile::new(&path, Some(public_key.clone())).load().is_err());
+        fs::write(&path, message(&checkpoints())).unwrap();
+        assert!(CheckpointFile::new(&path, Some(public_key)).load().is_err());
+    }
+}
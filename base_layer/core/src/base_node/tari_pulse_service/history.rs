@@ -0,0 +1,183 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs,
+    fs::OpenOptions,
+    io,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use tokio::task;
+
+use super::CheckpointReport;
+
+const HEADER: &str = "timestamp,checkpoint_height,checkpoint_hash,local_hash,passed";
+
+/// Appends the result of each checkpoint check to a CSV file, providing an audit trail of fork detections. Once the
+/// file reaches `max_size` bytes it is rotated to `<file>.1`, replacing any previously rotated file.
+#[derive(Debug, Clone)]
+pub struct CheckHistory {
+    path: PathBuf,
+    max_size: u64,
+}
+
+impl CheckHistory {
+    pub fn new<P: Into<PathBuf>>(path: P, max_size: u64) -> Self {
+        Self {
+            path: path.into(),
+            max_size,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The path that the history file is rotated to
+    pub fn rotated_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".1");
+        path.into()
+    }
+
+    /// Appends a row for every checkpoint in the report that was compared to the local chain
+    pub fn append(&self, timestamp: DateTime<Utc>, report: &CheckpointReport) -> io::Result<()> {
+        if report.checked.is_empty() {
+            return Ok(());
+        }
+        self.rotate_if_full()?;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut rows = String::new();
+        if !self.path.exists() {
+            rows.push_str(HEADER);
+            rows.push('\n');
+        }
+        let timestamp = timestamp.to_rfc3339();
+        for checkpoint in &report.checked {
+            rows.push_str(&format!(
+                "{},{},{},{},{}\n",
+                timestamp, checkpoint.height, checkpoint.checkpoint_hash, checkpoint.local_hash, checkpoint.passed
+            ));
+        }
+        let mut file = OpenOptions::new().append(true).create(true).open(&self.path)?;
+        file.write_all(rows.as_bytes())
+    }
+
+    /// Appends the report on the blocking thread pool, so that the file I/O does not stall the runtime
+    pub async fn append_async(&self, timestamp: DateTime<Utc>, report: CheckpointReport) -> io::Result<()> {
+        let history = self.clone();
+        task::spawn_blocking(move || history.append(timestamp, &report))
+            .await
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+    }
+
+    fn rotate_if_full(&self) -> io::Result<()> {
+        match fs::metadata(&self.path) {
+            Ok(metadata) if metadata.len() >= self.max_size => fs::rename(&self.path, self.rotated_path()),
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::base_node::tari_pulse_service::CheckedCheckpoint;
+
+    fn report(checks: &[(u64, bool)]) -> CheckpointReport {
+        let checked = checks
+            .iter()
+            .map(|(height, passed)| CheckedCheckpoint {
+                height: *height,
+                checkpoint_hash: format!("hash{}", height),
+                local_hash: if *passed {
+                    format!("hash{}", height)
+                } else {
+                    format!("fork{}", height)
+                },
+                passed: *passed,
+            })
+            .collect::<Vec<_>>();
+        CheckpointReport {
+            checked,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_appends_and_parses_back_check_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = CheckHistory::new(dir.path().join("pulse").join("history.csv"), 1024 * 1024);
+        let timestamp = Utc::now();
+        history.append(timestamp, &report(&[(100, true)])).unwrap();
+        history
+            .append(timestamp, &report(&[(100, true), (200, false)]))
+            .unwrap();
+        // Checks without any compared checkpoints are not recorded
+        history.append(timestamp, &report(&[])).unwrap();
+
+        let contents = fs::read_to_string(history.path()).unwrap();
+        let mut lines = contents.lines();
+        assert_eq!(lines.next(), Some(HEADER));
+        let rows = lines
+            .map(|line| line.split(',').map(str::to_string).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 3);
+        for row in &rows {
+            assert_eq!(row.len(), 5);
+            assert_eq!(DateTime::parse_from_rfc3339(&row[0]).unwrap(), timestamp);
+        }
+        let parsed = rows
+            .iter()
+            .map(|row| (row[1].parse::<u64>().unwrap(), row[4].parse::<bool>().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(parsed, vec![(100, true), (100, true), (200, false)]);
+        assert_eq!(rows[2][2], "hash200");
+        assert_eq!(rows[2][3], "fork200");
+    }
+
+    #[test]
+    fn it_rotates_the_history_file_at_the_maximum_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = CheckHistory::new(dir.path().join("history.csv"), 150);
+        for height in 1..=3 {
+            history.append(Utc::now(), &report(&[(height, true)])).unwrap();
+        }
+
+        // The header and two rows exceed the maximum size, so the third check starts a new file
+        let rotated = fs::read_to_string(history.rotated_path()).unwrap();
+        assert_eq!(rotated.lines().count(), 3);
+        let current = fs::read_to_string(history.path()).unwrap();
+        let lines = current.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], HEADER);
+        assert!(lines[1].contains(",3,hash3,hash3,true"));
+    }
+}
@@ -20,43 +20,366 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{cmp::min, str::FromStr, time::Duration};
+use std::{
+    cmp::{max, min},
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    net::SocketAddr,
+    path::PathBuf,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
 
-use futures::future;
+use chrono::{NaiveDate, Utc};
+use futures::{future, StreamExt};
 use hickory_client::{
     client::{AsyncDnssecClient, ClientHandle},
+    error::{ClientError, ClientErrorKind},
     proto::{
+        error::{ProtoError, ProtoErrorKind},
         iocompat::AsyncIoTokioAsStd,
-        rr::dnssec::{public_key::Rsa, SigSigner, TrustAnchor},
+        rr::dnssec::{public_key::Rsa, rdata::DNSSECRData, SigSigner, TrustAnchor},
         xfer::DnsMultiplexer,
     },
     rr::{DNSClass, Name, RData, Record, RecordType},
     tcp::TcpClientStream,
 };
-use log::{debug, error, info, trace, warn};
+use log::{debug, error, info, log_enabled, trace, warn, Level};
 use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+use tari_common_types::types::PublicKey;
 use tari_p2p::Network;
-use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
+use tari_service_framework::{
+    async_trait,
+    reply_channel,
+    reply_channel::{SenderService, TransportChannelError},
+    Service,
+    ServiceInitializationError,
+    ServiceInitializer,
+    ServiceInitializerContext,
+};
 use tari_shutdown::ShutdownSignal;
 use tari_utilities::hex::Hex;
+use thiserror::Error;
 use tokio::{net::TcpStream as TokioTcpStream, sync::watch, time, time::MissedTickBehavior};
 
 use super::LocalNodeCommsInterface;
 use crate::base_node::comms_interface::CommsInterfaceError;
 
+mod checkpoint_file;
+pub use checkpoint_file::CheckpointFile;
+
+mod history;
+pub use history::CheckHistory;
+
 const LOG_TARGET: &str = "c::bn::tari_pulse";
+/// The TariPulse settings. The base node sets these from the `tari_pulse_*` settings of its config (e.g.
+/// `tari_pulse_log_raw_answers`), and settings that are omitted there keep their defaults.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct TariPulseConfig {
+    /// Run the checkpoint checks. When disabled the service handle is still registered and always reports that
+    /// checkpoints passed, e.g. for private or offline networks without DNS checkpoints.
+    pub enabled: bool,
+    #[serde(with = "serializers::seconds")]
     pub check_interval: Duration,
     pub network: Network,
+    /// Verify every checkpoint published in DNS rather than only the highest one. Checkpoints above the local chain
+    /// tip are reported as unavailable instead of failing the check.
+    pub verify_all_checkpoints: bool,
+    /// Log every raw TXT record received from DNS at debug level before it is parsed
+    pub log_raw_answers: bool,
+    /// The DNS resolvers to query, in order. The next resolver is only tried if it cannot be reached or DNSSEC
+    /// validation fails.
+    pub dns_resolvers: Vec<SocketAddr>,
+    /// How long to wait after startup before the first check, giving the node time to start syncing
+    #[serde(with = "serializers::seconds")]
+    pub initial_delay: Duration,
+    /// The DNS class of the checkpoint records
+    #[serde(with = "dns_str")]
+    pub query_class: DNSClass,
+    /// The DNS record type that checkpoints are published in
+    #[serde(with = "dns_str")]
+    pub query_record_type: RecordType,
+    /// The minimum RSA modulus size, in bits, a DNSSEC trust anchor must have to be trusted. Set to 0 to accept any
+    /// key size.
+    pub min_trust_anchor_rsa_bits: usize,
+    /// When set, a warning is logged if DNSSEC validation still relies on the original root anchor (key tag 20326)
+    /// after this date, e.g. the end of a KSK rollover. The anchor that validated each query is logged at debug level.
+    pub legacy_trust_anchor_deadline: Option<NaiveDate>,
+    /// When set, a warning is logged at startup if the current date is past this date, as a reminder to check that
+    /// the trust anchors compiled into this build are still current before DNSSEC validation starts failing
+    pub anchor_review_date: Option<NaiveDate>,
+    /// The number of consecutive checks that must report a checkpoint mismatch before the node is flagged as having
+    /// failed the checkpoints. Any passing check resets the count.
+    pub consecutive_failures_before_notify: usize,
+    /// When set, the result of every check is appended to this CSV file as an audit trail of fork detections
+    pub history_file: Option<PathBuf>,
+    /// The size in bytes at which the history file is rotated
+    pub history_file_max_size: u64,
+    /// Checkpoints more than this many blocks above the local chain tip are rejected, so that a bogus height cannot
+    /// become the checkpoint that is compared against
+    pub max_checkpoint_height_above_tip: u64,
+    /// The maximum number of CNAME records that are followed to resolve the checkpoint records. Longer chains (e.g.
+    /// CNAME loops) are rejected.
+    pub max_cname_depth: usize,
+    /// A local file of `height:hash` checkpoints that is used when the DNS checkpoints cannot be fetched, e.g. in
+    /// air-gapped or DNS-blocked environments
+    pub checkpoint_file: Option<PathBuf>,
+    /// When set, the checkpoint file must be signed with this key
+    pub checkpoint_file_public_key: Option<PublicKey>,
+    /// Use the checkpoint file instead of querying DNS
+    pub prefer_file: bool,
+    /// Count a checkpoint whose local header is missing even though it is below the local chain tip as a failed
+    /// check, rather than only logging it. Checkpoints above the tip (e.g. while syncing) are always skipped quietly.
+    pub fail_on_missing_header_below_tip: bool,
 }
 
 impl Default for TariPulseConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             check_interval: Duration::from_secs(120),
             network: Network::default(),
+            verify_all_checkpoints: false,
+            log_raw_answers: false,
+            dns_resolvers: vec![
+                SocketAddr::from(([1, 1, 1, 1], 53)),
+                SocketAddr::from(([8, 8, 8, 8], 53)),
+                SocketAddr::from(([9, 9, 9, 9], 53)),
+            ],
+            initial_delay: Duration::from_secs(180),
+            query_class: DNSClass::IN,
+            query_record_type: RecordType::TXT,
+            min_trust_anchor_rsa_bits: 2048,
+            legacy_trust_anchor_deadline: None,
+            anchor_review_date: None,
+            consecutive_failures_before_notify: 3,
+            history_file: None,
+            history_file_max_size: 10 * 1024 * 1024,
+            max_checkpoint_height_above_tip: 1_000,
+            max_cname_depth: 8,
+            checkpoint_file: None,
+            checkpoint_file_public_key: None,
+            prefer_file: false,
+            fail_on_missing_header_below_tip: false,
+        }
+    }
+}
+
+/// (De)serializes DNS classes and record types using their textual representation, e.g. "IN" or "TXT"
+mod dns_str {
+    use std::{fmt::Display, str::FromStr};
+
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T: Display, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The check schedule for a specific network, so that nodes on multiple networks can be configured in one place
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TariPulseProfile {
+    /// The interval between checks on this network
+    #[serde(with = "serializers::seconds")]
+    pub check_interval: Duration,
+    /// The number of consecutive failed checks on this network before the node is flagged as having failed the
+    /// checkpoints
+    pub consecutive_failures_before_notify: usize,
+    /// How long to wait after startup before the first check on this network
+    #[serde(with = "serializers::seconds")]
+    pub initial_delay: Duration,
+}
+
+impl TariPulseProfile {
+    /// Returns the config with the check schedule of this profile applied
+    pub fn apply(&self, config: TariPulseConfig) -> TariPulseConfig {
+        TariPulseConfig {
+            check_interval: self.check_interval,
+            consecutive_failures_before_notify: self.consecutive_failures_before_notify,
+            initial_delay: self.initial_delay,
+            ..config
+        }
+    }
+}
+
+impl Default for TariPulseProfile {
+    fn default() -> Self {
+        let config = TariPulseConfig::default();
+        Self {
+            check_interval: config.check_interval,
+            consecutive_failures_before_notify: config.consecutive_failures_before_notify,
+            initial_delay: config.initial_delay,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PulseError {
+    #[error("DNSSEC validation failed: {0}")]
+    Dnssec(String),
+    #[error("DNS error: {0}")]
+    Dns(String),
+    #[error("Could not connect to DNS resolver {resolver}: {reason}")]
+    ResolverUnreachable { resolver: SocketAddr, reason: String },
+    #[error("No DNSSEC trust anchor meets the minimum strength of {min_rsa_bits} bits")]
+    NoTrustAnchor { min_rsa_bits: usize },
+    #[error("CNAME chain for {name} exceeds the maximum depth of {max_depth}")]
+    CnameChainTooLong { name: String, max_depth: usize },
+    #[error("Checkpoint file {0}")]
+    CheckpointFile(String),
+    #[error("Header at checkpoint height {height} is not synced yet, the local tip is at {tip}")]
+    HeaderNotYetSynced { height: u64, tip: u64 },
+    #[error("Header at checkpoint height {height} is missing below the local tip at {tip}")]
+    HeaderMissingBelowTip { height: u64, tip: u64 },
+    #[error("No checkpoints found")]
+    NoCheckpoints,
+    #[error("Checkpoint check failed: {0}")]
+    CheckFailed(String),
+    #[error("Transport channel error: {0}")]
+    TransportChannelError(#[from] TransportChannelError),
+}
+
+/// Distinguish DNSSEC validation failures, which another resolver may not exhibit (e.g. one that strips RRSIG
+/// records), from other DNS errors
+fn classify_dns_error(err: ClientError) -> PulseError {
+    let is_dnssec_error = match err.kind() {
+        ClientErrorKind::Proto(err) => is_dnssec_proto_error(err),
+        _ => false,
+    };
+    if is_dnssec_error {
+        PulseError::Dnssec(err.to_string())
+    } else {
+        PulseError::Dns(err.to_string())
+    }
+}
+
+fn is_dnssec_proto_error(err: &ProtoError) -> bool {
+    const DNSSEC_ERROR_HINTS: &[&str] = &["rrsig", "dnssec", "dnskey", "nsec", "not validate", "bogus"];
+    match err.kind() {
+        ProtoErrorKind::RrsigsNotPresent { .. } => true,
+        // The DNSSEC handle reports the other validation failures as plain messages
+        ProtoErrorKind::Message(msg) => DNSSEC_ERROR_HINTS.iter().any(|hint| msg.to_lowercase().contains(hint)),
+        ProtoErrorKind::Msg(msg) => DNSSEC_ERROR_HINTS.iter().any(|hint| msg.to_lowercase().contains(hint)),
+        _ => false,
+    }
+}
+
+/// The result of comparing the DNS checkpoints against the local chain
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckpointReport {
+    /// The checkpoint heights for which no local header is available yet
+    pub unavailable: Vec<u64>,
+    /// The checkpoints that could be compared to a local header
+    pub checked: Vec<CheckedCheckpoint>,
+}
+
+/// A DNS checkpoint that was compared to the local chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedCheckpoint {
+    pub height: u64,
+    pub checkpoint_hash: String,
+    pub local_hash: String,
+    pub passed: bool,
+}
+
+impl CheckpointReport {
+    /// Returns true if at least one checkpoint was compared to the local chain and did not match
+    pub fn has_mismatch(&self) -> bool {
+        self.checked.iter().any(|checkpoint| !checkpoint.passed)
+    }
+
+    /// The height of every checkpoint that was compared to the local chain, and whether the hashes matched
+    pub fn results(&self) -> Vec<(u64, bool)> {
+        self.checked
+            .iter()
+            .map(|checkpoint| (checkpoint.height, checkpoint.passed))
+            .collect()
+    }
+
+    /// The status of the lowest checkpoint that did not match the local chain, or `Passed` if there is none
+    pub fn status(&self) -> CheckpointStatus {
+        self.checked
+            .iter()
+            .filter(|checkpoint| !checkpoint.passed)
+            .min_by_key(|checkpoint| checkpoint.height)
+            .map_or(CheckpointStatus::Passed, |checkpoint| CheckpointStatus::HashMismatch {
+                height: checkpoint.height,
+                local_hash: checkpoint.local_hash.clone(),
+                dns_hash: checkpoint.checkpoint_hash.clone(),
+            })
+    }
+}
+
+/// The outcome of a checkpoint check, as published to the `TariPulseHandle`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CheckpointStatus {
+    /// The local chain matches the checkpoints, or no check has failed yet
+    #[default]
+    Passed,
+    /// The local chain has no header at the checkpoint height although it is below the local tip
+    HeightMismatch { local: u64, dns: u64 },
+    /// The checkpoint height is above the local tip, i.e. the node has not synced up to the checkpoint yet. This is
+    /// not a failure.
+    NotYetSynced { local: u64, dns: u64 },
+    /// The local header at the checkpoint height does not match the checkpoint, i.e. the node is on a fork
+    HashMismatch {
+        height: u64,
+        local_hash: String,
+        dns_hash: String,
+    },
+    /// No checkpoints could be parsed from DNS or the checkpoint file
+    NoCheckpoints,
+}
+
+impl CheckpointStatus {
+    /// Returns true if the node did not pass the checkpoints. Not finding any checkpoints, or not having synced up to
+    /// them yet, is not a failure.
+    pub fn has_failed(&self) -> bool {
+        matches!(
+            self,
+            CheckpointStatus::HeightMismatch { .. } | CheckpointStatus::HashMismatch { .. }
+        )
+    }
+}
+
+/// Counts consecutive failed checkpoint checks, so that a single transient mismatch (e.g. during a short reorg) does
+/// not flag the node as forked
+#[derive(Debug, Clone)]
+struct ConsecutiveFailures {
+    count: usize,
+    threshold: usize,
+}
+
+impl ConsecutiveFailures {
+    fn new(threshold: usize) -> Self {
+        Self {
+            count: 0,
+            threshold: max(threshold, 1),
+        }
+    }
+
+    /// Records the outcome of a check and returns true if the failed checkpoints should be notified
+    fn record(&mut self, failed: bool) -> bool {
+        if failed {
+            self.count = self.count.saturating_add(1);
+            self.count >= self.threshold
+        } else {
+            self.count = 0;
+            false
         }
     }
 }
@@ -76,39 +399,80 @@ pub struct TariPulseService {
     dns_name: Name,
     config: TariPulseConfig,
     shutdown_signal: ShutdownSignal,
+    /// Set once the validating trust anchors have been logged, so that the root DNSKEY set is only queried once
+    validating_anchors_logged: AtomicBool,
 }
 
 impl TariPulseService {
     pub async fn new(config: TariPulseConfig, shutdown_signal: ShutdownSignal) -> Result<Self, anyhow::Error> {
         let dns_name: Name = get_network_dns_name(config.clone().network);
         info!(target: LOG_TARGET, "Tari Pulse Service initialized with DNS name: {}", dns_name);
-        Ok(Self {
+        let service = Self {
             dns_name,
             config,
             shutdown_signal,
-        })
+            validating_anchors_logged: AtomicBool::new(false),
+        };
+        info!(
+            target: LOG_TARGET,
+            "Loaded DNSSEC trust anchors with key tags {:?}",
+            service.trust_anchor_key_tags()
+        );
+        if let Some(review_date) = service.config.anchor_review_date {
+            if is_anchor_review_due(review_date, Utc::now().date_naive()) {
+                warn!(
+                    target: LOG_TARGET,
+                    "The DNSSEC trust anchors of this build were due for review on {}, update the node if the root \
+                     KSK has rolled over since",
+                    review_date
+                );
+            }
+        }
+        Ok(service)
+    }
+
+    /// The key tags of the root anchors that are trusted, i.e. that meet `min_trust_anchor_rsa_bits`
+    pub fn trust_anchor_key_tags(&self) -> Vec<u16> {
+        accepted_trust_anchors(ROOT_ANCHORS, self.config.min_trust_anchor_rsa_bits)
+            .iter()
+            .filter_map(|(name, _)| name.parse().ok())
+            .collect()
     }
 
     pub fn default_trust_anchor() -> TrustAnchor {
-        const ROOT_ANCHOR_ORIG: &[u8] = include_bytes!("20326.rsa");
-        const ROOT_ANCHOR_CURRENT: &[u8] = include_bytes!("38696.rsa");
+        Self::trust_anchor(TariPulseConfig::default().min_trust_anchor_rsa_bits).expect("Invalid root anchors")
+    }
+
+    /// Builds a trust anchor from the root anchors whose RSA keys are at least `min_rsa_bits` long
+    pub fn trust_anchor(min_rsa_bits: usize) -> Result<TrustAnchor, PulseError> {
+        let accepted = accepted_trust_anchors(ROOT_ANCHORS, min_rsa_bits);
+        if accepted.is_empty() {
+            return Err(PulseError::NoTrustAnchor { min_rsa_bits });
+        }
 
         let mut anchor = TrustAnchor::new();
-        anchor.insert_trust_anchor(&Rsa::from_public_bytes(ROOT_ANCHOR_ORIG).expect("Invalid ROOT_ANCHOR_ORIG"));
-        anchor.insert_trust_anchor(&Rsa::from_public_bytes(ROOT_ANCHOR_CURRENT).expect("Invalid ROOT_ANCHOR_CURRENT"));
-        anchor
+        for (name, public_key) in accepted {
+            let key = Rsa::from_public_bytes(public_key)
+                .map_err(|e| PulseError::Dnssec(format!("Invalid trust anchor {}: {}", name, e)))?;
+            anchor.insert_trust_anchor(&key);
+        }
+        Ok(anchor)
     }
 
-    async fn get_dns_client(&self) -> Result<AsyncDnssecClient, anyhow::Error> {
+    async fn get_dns_client(&self, resolver: SocketAddr) -> Result<AsyncDnssecClient, PulseError> {
         let timeout: Duration = Duration::from_secs(5);
-        let trust_anchor = Self::default_trust_anchor();
+        let trust_anchor = Self::trust_anchor(self.config.min_trust_anchor_rsa_bits)?;
 
-        let (stream, handle) = TcpClientStream::<AsyncIoTokioAsStd<TokioTcpStream>>::new(([1, 1, 1, 1], 53).into());
+        let (stream, handle) = TcpClientStream::<AsyncIoTokioAsStd<TokioTcpStream>>::new(resolver);
         let dns_muxer = DnsMultiplexer::<_, SigSigner>::with_timeout(stream, handle, timeout, None);
         let (client, bg) = AsyncDnssecClient::builder(dns_muxer)
             .trust_anchor(trust_anchor)
             .build()
-            .await?;
+            .await
+            .map_err(|e| PulseError::ResolverUnreachable {
+                resolver,
+                reason: e.to_string(),
+            })?;
 
         tokio::spawn(bg);
 
@@ -118,15 +482,21 @@ impl TariPulseService {
     pub async fn run(
         &mut self,
         mut base_node_service: LocalNodeCommsInterface,
-        notify_passed_checkpoints: watch::Sender<bool>,
+        notify_checkpoint_status: watch::Sender<CheckpointStatus>,
+        mut check_requests: reply_channel::Receiver<(), Result<CheckpointStatus, PulseError>>,
     ) {
-        let mut interval = time::interval(self.config.check_interval);
-        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let interval = check_interval(&self.config);
         tokio::pin!(interval);
         let mut shutdown_signal = self.shutdown_signal.clone();
         let mut count = 0u64;
         let mut skip_ticks = 0;
         let mut skipped_ticks = 0;
+        let mut failures = ConsecutiveFailures::new(self.config.consecutive_failures_before_notify);
+        let history = self
+            .config
+            .history_file
+            .clone()
+            .map(|path| CheckHistory::new(path, self.config.history_file_max_size));
 
         loop {
             tokio::select! {
@@ -138,26 +508,104 @@ impl TariPulseService {
                         debug!(target: LOG_TARGET, "Skipping {} of {} ticks", skipped_ticks, skip_ticks);
                         continue;
                     }
-                    let passed_checkpoints = {
+                    let checkpoint_report = {
                         match self.passed_checkpoints(&mut base_node_service).await {
-                            Ok(passed) => {
+                            Ok(report) => {
                                 skip_ticks = 0;
                                 skipped_ticks = 0;
-                                passed
+                                report
                             },
                             Err(err) => {
-                                warn!(target: LOG_TARGET, "Failed to check if node has passed checkpoints: {:?}", err);
-                                skip_ticks = min(skip_ticks + 1, 30 * 60 / self.config.check_interval.as_secs());
-                                skipped_ticks = 0;
+                                match err.downcast_ref::<PulseError>() {
+                                    Some(PulseError::HeaderNotYetSynced { height, tip }) => {
+                                        debug!(
+                                            target: LOG_TARGET,
+                                            "Checkpoint at height {} is above the local tip at {}, skipping the check",
+                                            height,
+                                            tip
+                                        );
+                                    },
+                                    Some(PulseError::HeaderMissingBelowTip { height, tip }) => {
+                                        error!(
+                                            target: LOG_TARGET,
+                                            "Local header for checkpoint at height {} is missing below the local tip at {}",
+                                            height,
+                                            tip
+                                        );
+                                        if self.config.fail_on_missing_header_below_tip {
+                                            let status = if failures.record(true) {
+                                                CheckpointStatus::HeightMismatch { local: *tip, dns: *height }
+                                            } else {
+                                                CheckpointStatus::Passed
+                                            };
+                                            notify_checkpoint_status
+                                                .send(status)
+                                                .expect("Channel should be open");
+                                        }
+                                    },
+                                    Some(PulseError::NoCheckpoints) => {
+                                        warn!(target: LOG_TARGET, "No checkpoints found");
+                                        // A failed check is still reported until a check passes
+                                        notify_checkpoint_status.send_if_modified(|status| {
+                                            if status.has_failed() || *status == CheckpointStatus::NoCheckpoints {
+                                                return false;
+                                            }
+                                            *status = CheckpointStatus::NoCheckpoints;
+                                            true
+                                        });
+                                    },
+                                    _ => {
+                                        warn!(target: LOG_TARGET, "Failed to check if node has passed checkpoints: {:?}", err);
+                                        skip_ticks = min(skip_ticks + 1, 30 * 60 / self.config.check_interval.as_secs());
+                                        skipped_ticks = 0;
+                                    },
+                                }
                                 continue;
                             },
                         }
                     };
 
-                    notify_passed_checkpoints
-                        .send(!passed_checkpoints)
+                    if !checkpoint_report.unavailable.is_empty() {
+                        debug!(
+                            target: LOG_TARGET,
+                            "Local headers not yet available for checkpoints at heights {:?}",
+                            checkpoint_report.unavailable
+                        );
+                    }
+                    if let Some(history) = history.as_ref() {
+                        if let Err(err) = history.append_async(Utc::now(), checkpoint_report.clone()).await {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Failed to write to checkpoint history file {}: {}",
+                                history.path().display(),
+                                err
+                            );
+                        }
+                    }
+                    let has_mismatch = checkpoint_report.has_mismatch();
+                    let notify = failures.record(has_mismatch);
+                    if has_mismatch && !notify {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Checkpoint mismatch {} of {} before the node is flagged as having failed checkpoints",
+                            failures.count,
+                            failures.threshold
+                        );
+                    }
+                    let status = if notify {
+                        checkpoint_report.status()
+                    } else {
+                        CheckpointStatus::Passed
+                    };
+                    notify_checkpoint_status
+                        .send(status)
                         .expect("Channel should be open");
                 },
+                Some(request_context) = check_requests.next() => {
+                    let (_, reply_tx) = request_context.split();
+                    let result = self.check_now(&mut base_node_service).await;
+                    let _result = reply_tx.send(result);
+                },
                 _ = shutdown_signal.wait() => {
                     info!(
                         target: LOG_TARGET,
@@ -169,88 +617,539 @@ impl TariPulseService {
         }
     }
 
+    /// Checks the checkpoints out-of-band, returning the status of the check. The result is not counted towards
+    /// `consecutive_failures_before_notify` and the periodic checks continue on their own schedule.
+    async fn check_now(
+        &mut self,
+        base_node_service: &mut LocalNodeCommsInterface,
+    ) -> Result<CheckpointStatus, PulseError> {
+        let report = match self.passed_checkpoints(base_node_service).await {
+            Ok(report) => report,
+            Err(err) => {
+                return match err.downcast::<PulseError>() {
+                    Ok(PulseError::HeaderNotYetSynced { height, tip }) => {
+                        Ok(CheckpointStatus::NotYetSynced { local: tip, dns: height })
+                    },
+                    Ok(PulseError::HeaderMissingBelowTip { height, tip }) => {
+                        Ok(CheckpointStatus::HeightMismatch { local: tip, dns: height })
+                    },
+                    Ok(PulseError::NoCheckpoints) => Ok(CheckpointStatus::NoCheckpoints),
+                    Ok(err) => Err(err),
+                    Err(err) => Err(PulseError::CheckFailed(err.to_string())),
+                };
+            },
+        };
+        debug!(target: LOG_TARGET, "On-demand checkpoint check: {:?}", report.results());
+        Ok(report.status())
+    }
+
     async fn passed_checkpoints(
         &mut self,
         base_node_service: &mut LocalNodeCommsInterface,
-    ) -> Result<bool, anyhow::Error> {
-        let dns_checkpoints = self.fetch_checkpoints().await?;
+    ) -> Result<CheckpointReport, anyhow::Error> {
+        let tip = base_node_service.get_metadata().await?.best_block_height();
+        let dns_checkpoints = self.fetch_checkpoints(tip).await?;
+        if dns_checkpoints.is_empty() {
+            return Err(PulseError::NoCheckpoints.into());
+        }
+        if self.config.verify_all_checkpoints {
+            return verify_checkpoints(base_node_service, &dns_checkpoints).await;
+        }
 
-        let max_height_block = dns_checkpoints
-            .iter()
-            .max_by(|a, b| a.0.cmp(&b.0))
-            .ok_or(CommsInterfaceError::InternalError("No checkpoints found".to_string()))?;
-        let local_checkpoints = self.get_node_block(base_node_service, max_height_block.0).await?;
+        let max_height_block = dns_checkpoints.last().ok_or(PulseError::NoCheckpoints)?;
+        let local_checkpoints = get_node_block(base_node_service, max_height_block.0).await?;
         let passed = local_checkpoints.1 == max_height_block.1;
         trace!(
             target: LOG_TARGET, "Passed checkpoints: {}, DNS: ({}, {}), Local: ({}, {})",
             passed, max_height_block.0, max_height_block.1, local_checkpoints.0, local_checkpoints.1
         );
-        Ok(passed)
+        Ok(CheckpointReport {
+            unavailable: vec![],
+            checked: vec![CheckedCheckpoint {
+                height: max_height_block.0,
+                checkpoint_hash: max_height_block.1.clone(),
+                local_hash: local_checkpoints.1,
+                passed,
+            }],
+        })
     }
 
-    async fn get_node_block(
-        &mut self,
-        base_node_service: &mut LocalNodeCommsInterface,
-        block_height: u64,
-    ) -> Result<(u64, String), anyhow::Error> {
-        let historical_block = base_node_service
-            .get_header(block_height)
-            .await
-            .and_then(|header| match header {
-                Some(header) => Ok((header.height(), header.hash().to_hex())),
-                None => {
-                    error!(target: LOG_TARGET, "Header not found for height: {}", block_height);
-                    Err(CommsInterfaceError::InternalError("Header not found".to_string()))
-                },
-            })?;
+    /// Fetches the checkpoints, sorted by height, rejecting those more than `max_checkpoint_height_above_tip` blocks
+    /// above the local `tip`
+    async fn fetch_checkpoints(&self, tip: u64) -> Result<Vec<(u64, String)>, PulseError> {
+        let checkpoint_file = self
+            .config
+            .checkpoint_file
+            .clone()
+            .map(|path| CheckpointFile::new(path, self.config.checkpoint_file_public_key.clone()));
+        let checkpoints = fetch_with_file_fallback(checkpoint_file.as_ref(), self.config.prefer_file, || {
+            query_resolvers(&self.config.dns_resolvers, |resolver| self.query_checkpoints(resolver))
+        })
+        .await?;
+        Ok(sanitize_checkpoints(
+            checkpoints,
+            tip.saturating_add(self.config.max_checkpoint_height_above_tip),
+        ))
+    }
 
-        Ok(historical_block)
+    async fn query_checkpoints(&self, resolver: SocketAddr) -> Result<Vec<(u64, String)>, PulseError> {
+        let client = self.get_dns_client(resolver).await?;
+        let (canonical_name, answers) = resolve_cname_chain(
+            self.dns_name.clone(),
+            self.config.query_record_type,
+            self.config.max_cname_depth,
+            |name| {
+                let mut client = client.clone();
+                let query = client.query(name, self.config.query_class, self.config.query_record_type);
+                async move {
+                    let response = query.await.map_err(classify_dns_error)?;
+                    Ok(response.answers().to_vec())
+                }
+            },
+        )
+        .await?;
+        self.log_validating_anchors(&client, resolver).await;
+        if canonical_name != self.dns_name {
+            debug!(
+                target: LOG_TARGET,
+                "Resolved checkpoints for {} via CNAME at {}", self.dns_name, canonical_name
+            );
+        }
+        Ok(parse_checkpoint_answers(
+            &answers,
+            self.config.query_record_type,
+            self.config.log_raw_answers,
+        ))
     }
 
-    async fn fetch_checkpoints(&mut self) -> Result<Vec<(u64, String)>, anyhow::Error> {
-        let mut client = self.get_dns_client().await?;
-        let query = client.query(self.dns_name.clone(), DNSClass::IN, RecordType::TXT);
-        let response = query.await?;
-        let answers: &[Record] = response.answers();
-        let checkpoints: Vec<(u64, String)> = answers
-            .iter()
-            .filter_map(|record| {
-                if let RData::TXT(txt) = record.data() {
-                    let ascii_txt = txt.txt_data().iter().fold(String::new(), |mut acc, bytes| {
-                        acc.push_str(&String::from_utf8_lossy(bytes));
-                        acc
-                    });
-                    let (height, hash) = ascii_txt.split_once(':')?;
-                    return Some((height.parse().ok()?, hash.to_string()));
+    /// Logs which root anchors signed the root DNSKEY set, and so validated the checkpoint query, warning if the
+    /// legacy anchor is still relied on after the configured deadline. This needs an extra query, so it is only done
+    /// once, and only if debug logging is enabled or the deadline has passed. Failures are only logged, as they do
+    /// not affect the checkpoint check.
+    async fn log_validating_anchors(&self, client: &AsyncDnssecClient, resolver: SocketAddr) {
+        let today = Utc::now().date_naive();
+        let deadline_passed = self
+            .config
+            .legacy_trust_anchor_deadline
+            .is_some_and(|deadline| today > deadline);
+        if self.validating_anchors_logged.load(Ordering::Relaxed) ||
+            !(deadline_passed || log_enabled!(target: LOG_TARGET, Level::Debug))
+        {
+            return;
+        }
+        let mut client = client.clone();
+        let response = match client.query(Name::root(), DNSClass::IN, RecordType::DNSKEY).await {
+            Ok(response) => response,
+            Err(e) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Could not determine the validating trust anchor using resolver {}: {}", resolver, e
+                );
+                return;
+            },
+        };
+        self.validating_anchors_logged.store(true, Ordering::Relaxed);
+        let accepted = accepted_trust_anchors(ROOT_ANCHORS, self.config.min_trust_anchor_rsa_bits);
+        let anchors = validating_anchors(&accepted, &root_signing_key_tags(response.answers()));
+        debug!(
+            target: LOG_TARGET,
+            "DNSSEC validated by trust anchor(s) {:?} using resolver {}", anchors, resolver
+        );
+        if relies_on_legacy_anchor(&anchors, self.config.legacy_trust_anchor_deadline, today) {
+            warn!(
+                target: LOG_TARGET,
+                "DNSSEC validation still relies on the legacy trust anchor {} after the rollover deadline",
+                LEGACY_ROOT_ANCHOR
+            );
+        }
+    }
+}
+
+/// The DNSSEC root anchors, as RSA public keys in DNSKEY wire format, named by their key tag
+const ROOT_ANCHORS: &[(&str, &[u8])] = &[
+    ("20326", include_bytes!("20326.rsa")),
+    ("38696", include_bytes!("38696.rsa")),
+];
+
+/// The root anchor that is retired once the KSK rollover to its successor completes
+const LEGACY_ROOT_ANCHOR: &str = "20326";
+
+/// The key tags of the keys that signed the root DNSKEY set in `answers`
+fn root_signing_key_tags(answers: &[Record]) -> Vec<u16> {
+    answers
+        .iter()
+        .filter(|record| record.name().is_root())
+        .filter_map(|record| match record.data() {
+            RData::DNSSEC(DNSSECRData::RRSIG(sig)) if sig.type_covered() == RecordType::DNSKEY => Some(sig.key_tag()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The names of the `anchors` whose key tag is one of `signing_key_tags`, in the order of `anchors`
+fn validating_anchors<'a>(anchors: &[(&'a str, &[u8])], signing_key_tags: &[u16]) -> Vec<&'a str> {
+    anchors
+        .iter()
+        .filter(|(name, _)| {
+            name.parse::<u16>()
+                .is_ok_and(|key_tag| signing_key_tags.contains(&key_tag))
+        })
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Whether the trust anchors are overdue for review on `today`
+fn is_anchor_review_due(review_date: NaiveDate, today: NaiveDate) -> bool {
+    today > review_date
+}
+
+/// Whether validation relies only on the legacy root anchor after `deadline`
+fn relies_on_legacy_anchor(validating_anchors: &[&str], deadline: Option<NaiveDate>, today: NaiveDate) -> bool {
+    deadline.is_some_and(|deadline| today > deadline) && validating_anchors == [LEGACY_ROOT_ANCHOR]
+}
+
+/// Returns the anchors whose RSA modulus is at least `min_rsa_bits` long. Anchors that cannot be parsed or are too
+/// weak are logged and skipped.
+fn accepted_trust_anchors<'a>(anchors: &[(&'a str, &'a [u8])], min_rsa_bits: usize) -> Vec<(&'a str, &'a [u8])> {
+    anchors
+        .iter()
+        .filter(|(name, public_key)| match rsa_modulus_bits(public_key) {
+            Some(bits) if bits >= min_rsa_bits => {
+                debug!(target: LOG_TARGET, "Accepted DNSSEC trust anchor {} ({} bits)", name, bits);
+                true
+            },
+            Some(bits) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Rejected DNSSEC trust anchor {}: {} bits is below the minimum of {} bits", name, bits, min_rsa_bits
+                );
+                false
+            },
+            None => {
+                warn!(target: LOG_TARGET, "Rejected DNSSEC trust anchor {}: malformed RSA key", name);
+                false
+            },
+        })
+        .copied()
+        .collect()
+}
+
+/// The size of the modulus of an RSA public key in DNSKEY wire format (RFC 3110), or None if the key is malformed
+fn rsa_modulus_bits(public_key: &[u8]) -> Option<usize> {
+    let (exponent_len, rest) = match public_key.split_first()? {
+        (0, rest) => {
+            let len = rest.get(..2)?;
+            (usize::from(u16::from_be_bytes([len[0], len[1]])), &rest[2..])
+        },
+        (len, rest) => (usize::from(*len), rest),
+    };
+    let modulus = rest.get(exponent_len..)?;
+    let leading_zeros = modulus.iter().take_while(|b| **b == 0).count();
+    let significant = &modulus[leading_zeros..];
+    let first = significant.first()?;
+    Some((significant.len() - 1) * 8 + (8 - first.leading_zeros() as usize))
+}
+
+/// Runs `query` against each resolver in turn, moving on to the next resolver only when the resolver cannot be
+/// reached or DNSSEC validation fails. An error is only returned once every resolver has failed.
+async fn query_resolvers<T, F, Fut>(resolvers: &[SocketAddr], mut query: F) -> Result<T, PulseError>
+where
+    F: FnMut(SocketAddr) -> Fut,
+    Fut: Future<Output = Result<T, PulseError>>,
+{
+    let mut last_error = PulseError::Dns("No DNS resolvers configured".to_string());
+    for resolver in resolvers {
+        match query(*resolver).await {
+            Ok(result) => {
+                debug!(target: LOG_TARGET, "Queried checkpoints using resolver {}", resolver);
+                return Ok(result);
+            },
+            Err(err @ PulseError::ResolverUnreachable { .. }) => {
+                warn!(target: LOG_TARGET, "{}", err);
+                last_error = err;
+            },
+            Err(PulseError::Dnssec(err)) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "DNSSEC validation failed using resolver {}: {}", resolver, err
+                );
+                last_error = PulseError::Dnssec(err);
+            },
+            Err(err) => return Err(err),
+        }
+    }
+    Err(last_error)
+}
+
+/// Queries `name` for records of `record_type`, following CNAME records to the canonical name. A CNAME chain is first
+/// followed through the records of an answer, and the target is only queried when the answer does not include its
+/// records. Returns the canonical name and its answers, or an error if the chain is longer than `max_depth`, which
+/// also guards against CNAME loops.
+async fn resolve_cname_chain<F, Fut>(
+    name: Name,
+    record_type: RecordType,
+    max_depth: usize,
+    mut query: F,
+) -> Result<(Name, Vec<Record>), PulseError>
+where
+    F: FnMut(Name) -> Fut,
+    Fut: Future<Output = Result<Vec<Record>, PulseError>>,
+{
+    let mut current = name.clone();
+    let mut depth = 0;
+    loop {
+        let answers = query(current.clone()).await?;
+        let mut followed = false;
+        loop {
+            if answers
+                .iter()
+                .any(|record| record.record_type() == record_type && *record.name() == current)
+            {
+                return Ok((current, answers));
+            }
+            let target = answers.iter().find_map(|record| match record.data() {
+                RData::CNAME(cname) if *record.name() == current => Some(cname.0.clone()),
+                _ => None,
+            });
+            let Some(target) = target else {
+                break;
+            };
+            depth += 1;
+            if depth > max_depth {
+                return Err(PulseError::CnameChainTooLong {
+                    name: name.to_string(),
+                    max_depth,
+                });
+            }
+            trace!(target: LOG_TARGET, "Following CNAME {} -> {}", current, target);
+            current = target;
+            followed = true;
+        }
+        if !followed {
+            return Ok((current, answers));
+        }
+    }
+}
+
+/// Fetches the checkpoints from DNS, falling back to the checkpoint file if DNS fails. With `prefer_file` the
+/// checkpoint file is used without querying DNS.
+async fn fetch_with_file_fallback<F, Fut>(
+    checkpoint_file: Option<&CheckpointFile>,
+    prefer_file: bool,
+    query_dns: F,
+) -> Result<Vec<(u64, String)>, PulseError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<Vec<(u64, String)>, PulseError>>,
+{
+    let Some(checkpoint_file) = checkpoint_file else {
+        return query_dns().await;
+    };
+    if prefer_file {
+        return checkpoint_file.load();
+    }
+    match query_dns().await {
+        Ok(checkpoints) => Ok(checkpoints),
+        Err(err) => {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to fetch DNS checkpoints ({}), using checkpoint file {}",
+                err,
+                checkpoint_file.path().display()
+            );
+            checkpoint_file.load()
+        },
+    }
+}
+
+/// Parses `height:hash` checkpoints from the answers of the given record type. Answers of any other type are ignored.
+fn parse_checkpoint_answers(answers: &[Record], record_type: RecordType, log_raw_answers: bool) -> Vec<(u64, String)> {
+    answers
+        .iter()
+        .filter(|record| record.record_type() == record_type)
+        .filter_map(|record| {
+            let chunks = checkpoint_data_chunks(record.data())?;
+            if log_raw_answers {
+                for bytes in &chunks {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Raw {} record for {}: hex: {}, utf8: {}",
+                        record_type,
+                        record.name(),
+                        bytes.to_hex(),
+                        String::from_utf8_lossy(bytes)
+                    );
                 }
-                None
-            })
-            .collect();
+            }
+            let ascii_txt = chunks.iter().fold(String::new(), |mut acc, bytes| {
+                acc.push_str(&String::from_utf8_lossy(bytes));
+                acc
+            });
+            let (height, hash) = ascii_txt.split_once(':')?;
+            Some((height.parse().ok()?, hash.to_string()))
+        })
+        .collect()
+}
+
+/// Sorts the checkpoints by height, keeping the last of any duplicate heights, and drops those above `max_height`
+fn sanitize_checkpoints(checkpoints: Vec<(u64, String)>, max_height: u64) -> Vec<(u64, String)> {
+    let mut sanitized = BTreeMap::new();
+    for (height, hash) in checkpoints {
+        if height > max_height {
+            warn!(
+                target: LOG_TARGET,
+                "Rejected checkpoint at height {} above the maximum height of {}", height, max_height
+            );
+            continue;
+        }
+        if let Some(previous) = sanitized.insert(height, hash) {
+            debug!(
+                target: LOG_TARGET,
+                "Replaced duplicate checkpoint at height {} (previous hash {})", height, previous
+            );
+        }
+    }
+    sanitized.into_iter().collect()
+}
 
-        Ok(checkpoints)
+/// The raw checkpoint data carried by a record. TXT records may split the data across several character strings.
+fn checkpoint_data_chunks(data: &RData) -> Option<Vec<&[u8]>> {
+    match data {
+        RData::TXT(txt) => Some(txt.txt_data().iter().map(|bytes| bytes.as_ref()).collect()),
+        RData::NULL(null) => Some(vec![null.anything()]),
+        _ => None,
     }
 }
 
+/// The interval at which checks are run. The first tick is delayed by `initial_delay`.
+fn check_interval(config: &TariPulseConfig) -> time::Interval {
+    let mut interval = time::interval_at(time::Instant::now() + config.initial_delay, config.check_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    interval
+}
+
+/// Compares each of the given checkpoints against the local chain. Checkpoints for which the local node does not
+/// have a header yet (e.g. while syncing) are reported as unavailable rather than treated as an error.
+async fn verify_checkpoints(
+    base_node_service: &mut LocalNodeCommsInterface,
+    dns_checkpoints: &[(u64, String)],
+) -> Result<CheckpointReport, anyhow::Error> {
+    let mut report = CheckpointReport::default();
+    for (height, dns_hash) in dns_checkpoints {
+        match get_local_block_hash(base_node_service, *height).await? {
+            Some(local_hash) => {
+                let passed = local_hash == *dns_hash;
+                trace!(
+                    target: LOG_TARGET, "Checkpoint at height {}: passed: {}, DNS: {}, Local: {}",
+                    height, passed, dns_hash, local_hash
+                );
+                report.checked.push(CheckedCheckpoint {
+                    height: *height,
+                    checkpoint_hash: dns_hash.clone(),
+                    local_hash,
+                    passed,
+                });
+            },
+            None => report.unavailable.push(*height),
+        }
+    }
+    Ok(report)
+}
+
+/// Fetches the hash of the local header at the given checkpoint height. A missing header is reported as
+/// [PulseError::HeaderNotYetSynced] if the height is above the local tip, and as [PulseError::HeaderMissingBelowTip]
+/// otherwise.
+async fn get_node_block(
+    base_node_service: &mut LocalNodeCommsInterface,
+    block_height: u64,
+) -> Result<(u64, String), anyhow::Error> {
+    if let Some(hash) = get_local_block_hash(base_node_service, block_height).await? {
+        return Ok((block_height, hash));
+    }
+    let tip = base_node_service.get_metadata().await?.best_block_height();
+    if block_height > tip {
+        Err(PulseError::HeaderNotYetSynced {
+            height: block_height,
+            tip,
+        }
+        .into())
+    } else {
+        Err(PulseError::HeaderMissingBelowTip {
+            height: block_height,
+            tip,
+        }
+        .into())
+    }
+}
+
+async fn get_local_block_hash(
+    base_node_service: &mut LocalNodeCommsInterface,
+    block_height: u64,
+) -> Result<Option<String>, CommsInterfaceError> {
+    let header = base_node_service.get_header(block_height).await?;
+    Ok(header.map(|header| header.hash().to_hex()))
+}
+
 #[derive(Clone)]
 pub struct TariPulseHandle {
     pub shutdown_signal: ShutdownSignal,
-    pub failed_checkpoints_notifier: watch::Receiver<bool>,
+    pub checkpoint_status: watch::Receiver<CheckpointStatus>,
+    check_requests: SenderService<(), Result<CheckpointStatus, PulseError>>,
 }
 
 impl TariPulseHandle {
-    pub fn get_failed_checkpoints_notifier(&self) -> watch::Ref<'_, bool> {
-        self.failed_checkpoints_notifier.borrow()
+    /// The status of the latest periodic checkpoint check. A failed status is only reported once
+    /// `consecutive_failures_before_notify` checks have failed.
+    pub fn status(&self) -> CheckpointStatus {
+        self.checkpoint_status.borrow().clone()
+    }
+
+    /// Checks the checkpoints immediately rather than waiting for the next periodic check, returning the status of
+    /// the check. When the service is disabled the node always passes.
+    pub async fn check_now(&self) -> Result<CheckpointStatus, PulseError> {
+        self.check_requests.clone().call(()).await?
     }
 }
 
 pub struct TariPulseServiceInitializer {
-    interval: Duration,
-    network: Network,
+    config: TariPulseConfig,
+    profiles: HashMap<Network, TariPulseProfile>,
 }
 
 impl TariPulseServiceInitializer {
     pub fn new(interval: Duration, network: Network) -> Self {
-        Self { interval, network }
+        Self::with_config(TariPulseConfig {
+            check_interval: interval,
+            network,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: TariPulseConfig) -> Self {
+        Self {
+            config,
+            profiles: HashMap::new(),
+        }
+    }
+
+    /// Use the check schedule of the profile for the configured network, if there is one
+    pub fn with_profiles(mut self, profiles: HashMap<Network, TariPulseProfile>) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// The config for the configured network, with its profile applied
+    fn network_config(&self) -> TariPulseConfig {
+        match self.profiles.get(&self.config.network) {
+            Some(profile) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Using the Tari Pulse profile for {}: {:?}", self.config.network, profile
+                );
+                profile.apply(self.config.clone())
+            },
+            None => self.config.clone(),
+        }
     }
 }
 
@@ -259,22 +1158,31 @@ impl ServiceInitializer for TariPulseServiceInitializer {
     async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
         info!(target: LOG_TARGET, "Initializing Tari Pulse Service");
         let shutdown_signal = context.get_shutdown_signal();
-        let (sender, receiver) = watch::channel(false);
+        let (sender, receiver) = watch::channel(CheckpointStatus::Passed);
+        let (check_sender, mut check_requests) = reply_channel::unbounded();
         context.register_handle(TariPulseHandle {
             shutdown_signal: shutdown_signal.clone(),
-            failed_checkpoints_notifier: receiver,
+            checkpoint_status: receiver,
+            check_requests: check_sender,
         });
-        let config = TariPulseConfig {
-            check_interval: self.interval,
-            network: self.network,
-        };
+        if !self.config.enabled {
+            info!(target: LOG_TARGET, "Tari Pulse Service is disabled, checkpoints will not be checked");
+            context.spawn_until_shutdown(move |_| async move {
+                while let Some(request_context) = check_requests.next().await {
+                    let (_, reply_tx) = request_context.split();
+                    let _result = reply_tx.send(Ok(CheckpointStatus::Passed));
+                }
+            });
+            return Ok(());
+        }
+        let config = self.network_config();
 
         context.spawn_when_ready(move |handles| async move {
             let base_node_service = handles.expect_handle::<LocalNodeCommsInterface>();
             let mut tari_pulse_service = TariPulseService::new(config, shutdown_signal.clone())
                 .await
                 .expect("Should be able to get the service");
-            let tari_pulse_service = tari_pulse_service.run(base_node_service, sender);
+            let tari_pulse_service = tari_pulse_service.run(base_node_service, sender, check_requests);
             futures::pin_mut!(tari_pulse_service);
             future::select(tari_pulse_service, shutdown_signal).await;
             info!(target: LOG_TARGET, "Tari Pulse Service shutdown");
@@ -283,3 +1191,680 @@ impl ServiceInitializer for TariPulseServiceInitializer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use futures::StreamExt;
+    use hickory_client::rr::rdata::{CNAME, NULL, TXT};
+    use tari_common_types::chain_metadata::ChainMetadata;
+    use tari_service_framework::{reply_channel, RegisterHandle, StackBuilder};
+    use tari_shutdown::Shutdown;
+    use tokio::{net::TcpListener, sync::broadcast, task};
+
+    use super::*;
+    use crate::{
+        base_node::comms_interface::{NodeCommsRequest, NodeCommsResponse},
+        blocks::{BlockHeader, BlockHeaderAccumulatedData, ChainHeader},
+    };
+
+    /// Spawns a mock base node that serves headers from the given chain, i.e. a node that has synced up to the tip of
+    /// `chain`.
+    fn spawn_mock_chain(chain: Vec<ChainHeader>) -> LocalNodeCommsInterface {
+        let (request_sender, mut request_receiver) = reply_channel::unbounded();
+        let (block_sender, _block_receiver) = reply_channel::unbounded();
+        let (block_event_sender, _) = broadcast::channel(1);
+        task::spawn(async move {
+            while let Some(request_context) = request_receiver.next().await {
+                let (request, reply_tx) = request_context.split();
+                let response = match request {
+                    NodeCommsRequest::FetchHeaders(range) => Ok(NodeCommsResponse::BlockHeaders(
+                        chain.iter().filter(|h| range.contains(&h.height())).cloned().collect(),
+                    )),
+                    NodeCommsRequest::GetChainMetadata => {
+                        let tip = chain.last().expect("chain is not empty");
+                        Ok(NodeCommsResponse::ChainMetadata(
+                            ChainMetadata::new(tip.height(), *tip.hash(), 0, 0, 1.into(), tip.timestamp()).unwrap(),
+                        ))
+                    },
+                    _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+                };
+                let _result = reply_tx.send(response);
+            }
+        });
+        LocalNodeCommsInterface::new(request_sender, block_sender, block_event_sender)
+    }
+
+    fn create_chain(tip_height: u64) -> Vec<ChainHeader> {
+        let mut header = BlockHeader::new(0);
+        let mut chain = Vec::new();
+        loop {
+            let accumulated_data = BlockHeaderAccumulatedData {
+                hash: header.hash(),
+                ..Default::default()
+            };
+            chain.push(ChainHeader::try_construct(header.clone(), accumulated_data).unwrap());
+            if header.height == tip_height {
+                return chain;
+            }
+            header = BlockHeader::from_previous(&header);
+        }
+    }
+
+    #[tokio::test]
+    async fn it_reports_unavailable_checkpoints_on_a_partially_synced_chain() {
+        let chain = create_chain(5);
+        let checkpoints = vec![
+            (2, chain[2].hash().to_hex()),
+            (4, chain[4].hash().to_hex()),
+            (8, "00".repeat(32)),
+            (10, "00".repeat(32)),
+        ];
+        let mut base_node_service = spawn_mock_chain(chain);
+
+        let report = verify_checkpoints(&mut base_node_service, &checkpoints).await.unwrap();
+        assert_eq!(report.results(), vec![(2, true), (4, true)]);
+        assert_eq!(report.unavailable, vec![8, 10]);
+        assert!(!report.has_mismatch());
+    }
+
+    #[tokio::test]
+    async fn it_reports_a_header_above_the_tip_as_not_yet_synced() {
+        let mut base_node_service = spawn_mock_chain(create_chain(5));
+
+        let err = get_node_block(&mut base_node_service, 8).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PulseError>(),
+            Some(PulseError::HeaderNotYetSynced { height: 8, tip: 5 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_reports_a_header_missing_below_the_tip() {
+        let mut chain = create_chain(5);
+        chain.remove(3);
+        let mut base_node_service = spawn_mock_chain(chain);
+
+        let (height, _) = get_node_block(&mut base_node_service, 4).await.unwrap();
+        assert_eq!(height, 4);
+        let err = get_node_block(&mut base_node_service, 3).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PulseError>(),
+            Some(PulseError::HeaderMissingBelowTip { height: 3, tip: 5 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_reports_a_mismatch_among_available_checkpoints() {
+        let chain = create_chain(5);
+        let checkpoints = vec![
+            (2, chain[2].hash().to_hex()),
+            (4, "00".repeat(32)),
+            (8, "00".repeat(32)),
+        ];
+        let mut base_node_service = spawn_mock_chain(chain);
+
+        let report = verify_checkpoints(&mut base_node_service, &checkpoints).await.unwrap();
+        assert_eq!(report.results(), vec![(2, true), (4, false)]);
+        assert_eq!(report.unavailable, vec![8]);
+        assert!(report.has_mismatch());
+    }
+
+    #[test]
+    fn it_parses_checkpoints_regardless_of_raw_answer_logging() {
+        let name = get_network_dns_name(Network::LocalNet);
+        let answers = ["100:abcdef", "not a checkpoint", "200:012345"]
+            .into_iter()
+            .map(|txt| Record::from_rdata(name.clone(), 60, RData::TXT(TXT::new(vec![txt.to_string()]))))
+            .collect::<Vec<_>>();
+
+        let expected = vec![(100, "abcdef".to_string()), (200, "012345".to_string())];
+        assert_eq!(parse_checkpoint_answers(&answers, RecordType::TXT, false), expected);
+        assert_eq!(parse_checkpoint_answers(&answers, RecordType::TXT, true), expected);
+    }
+
+    #[test]
+    fn it_chooses_the_highest_checkpoint_within_range() {
+        let name = get_network_dns_name(Network::LocalNet);
+        let answers = ["200:aaaaaa", "100:abcdef", "999999999:bogus", "200:012345", "1005:fedcba"]
+            .into_iter()
+            .map(|txt| Record::from_rdata(name.clone(), 60, RData::TXT(TXT::new(vec![txt.to_string()]))))
+            .collect::<Vec<_>>();
+
+        let checkpoints = sanitize_checkpoints(parse_checkpoint_answers(&answers, RecordType::TXT, false), 1000);
+        assert_eq!(checkpoints, vec![(100, "abcdef".to_string()), (200, "012345".to_string())]);
+        assert_eq!(checkpoints.last(), Some(&(200, "012345".to_string())));
+    }
+
+    #[test]
+    fn it_only_parses_checkpoints_from_the_configured_record_type() {
+        let name = get_network_dns_name(Network::LocalNet);
+        let answers = vec![
+            Record::from_rdata(name.clone(), 60, RData::TXT(TXT::new(vec!["100:abcdef".to_string()]))),
+            Record::from_rdata(name, 60, RData::NULL(NULL::with(b"200:012345".to_vec()))),
+        ];
+
+        assert_eq!(TariPulseConfig::default().query_record_type, RecordType::TXT);
+        assert_eq!(parse_checkpoint_answers(&answers, RecordType::TXT, false), vec![(
+            100,
+            "abcdef".to_string()
+        )]);
+        assert_eq!(parse_checkpoint_answers(&answers, RecordType::NULL, false), vec![(
+            200,
+            "012345".to_string()
+        )]);
+    }
+
+    #[test]
+    fn it_deserializes_the_record_type_and_defaults_the_remaining_settings() {
+        let config: TariPulseConfig = toml::from_str(
+            r#"
+            check_interval = 60
+            query_record_type = "NULL"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.check_interval, Duration::from_secs(60));
+        assert_eq!(config.query_record_type, RecordType::NULL);
+        assert_eq!(config.query_class, DNSClass::IN);
+        assert_eq!(config.initial_delay, TariPulseConfig::default().initial_delay);
+
+        assert!(toml::from_str::<TariPulseConfig>("query_record_type = \"BOGUS\"").is_err());
+    }
+
+    #[test]
+    fn it_classifies_dns_errors_by_their_kind() {
+        let dnssec_errors: [ClientError; 3] = [
+            ProtoError::from(ProtoErrorKind::RrsigsNotPresent {
+                name: Name::root(),
+                record_type: RecordType::TXT,
+            })
+            .into(),
+            ProtoError::from(ProtoErrorKind::Message("could not validate all rrsets")).into(),
+            ProtoError::from(ProtoErrorKind::Msg("no DNSKEY found for example.com.".to_string())).into(),
+        ];
+        for err in dnssec_errors {
+            assert!(matches!(classify_dns_error(err), PulseError::Dnssec(_)));
+        }
+
+        let other_errors: [ClientError; 3] = [
+            ClientErrorKind::Timeout.into(),
+            ProtoError::from(io::Error::from(io::ErrorKind::ConnectionRefused)).into(),
+            // Only the errors of the DNSSEC validating handle are inspected
+            ClientErrorKind::Msg("nsec lookup failed".to_string()).into(),
+        ];
+        for err in other_errors {
+            assert!(matches!(classify_dns_error(err), PulseError::Dns(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn it_retries_the_next_resolver_when_dnssec_validation_fails() {
+        let unsigned = SocketAddr::from(([127, 0, 0, 1], 5301));
+        let signed = SocketAddr::from(([127, 0, 0, 2], 5302));
+        let mut queried = Vec::new();
+        let result = query_resolvers(&[unsigned, signed], |resolver| {
+            queried.push(resolver);
+            async move {
+                if resolver == unsigned {
+                    Err(classify_dns_error(
+                        ProtoError::from(ProtoErrorKind::RrsigsNotPresent {
+                            name: Name::root(),
+                            record_type: RecordType::TXT,
+                        })
+                        .into(),
+                    ))
+                } else {
+                    Ok(vec![(100u64, "abcdef".to_string())])
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, vec![(100, "abcdef".to_string())]);
+        assert_eq!(queried, vec![unsigned, signed]);
+    }
+
+    #[tokio::test]
+    async fn it_only_reports_a_dnssec_error_once_all_resolvers_fail_validation() {
+        let resolvers = [
+            SocketAddr::from(([127, 0, 0, 1], 5301)),
+            SocketAddr::from(([127, 0, 0, 2], 5302)),
+        ];
+        let mut attempts = 0;
+        let result = query_resolvers::<(), _, _>(&resolvers, |_| {
+            attempts += 1;
+            async {
+                Err(classify_dns_error(
+                    ProtoError::from(ProtoErrorKind::Message("could not validate all rrsets")).into(),
+                ))
+            }
+        })
+        .await;
+        assert!(matches!(result, Err(PulseError::Dnssec(_))));
+        assert_eq!(attempts, 2);
+
+        // Other DNS errors are not retried against another resolver
+        let mut attempts = 0;
+        let result = query_resolvers::<(), _, _>(&resolvers, |_| {
+            attempts += 1;
+            async {
+                Err(classify_dns_error(
+                    ProtoError::from(io::Error::from(io::ErrorKind::ConnectionRefused)).into(),
+                ))
+            }
+        })
+        .await;
+        assert!(matches!(result, Err(PulseError::Dns(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn it_retries_the_next_resolver_when_a_resolver_is_unreachable() {
+        let unreachable = SocketAddr::from(([127, 0, 0, 1], 5301));
+        let reachable = SocketAddr::from(([127, 0, 0, 2], 5302));
+        let mut queried = Vec::new();
+        let result = query_resolvers(&[unreachable, reachable], |resolver| {
+            queried.push(resolver);
+            async move {
+                if resolver == unreachable {
+                    Err(PulseError::ResolverUnreachable {
+                        resolver,
+                        reason: "connection refused".to_string(),
+                    })
+                } else {
+                    Ok(vec![(100u64, "abcdef".to_string())])
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, vec![(100, "abcdef".to_string())]);
+        assert_eq!(queried, vec![unreachable, reachable]);
+
+        // An error is returned once every resolver is unreachable
+        let result = query_resolvers::<(), _, _>(&[unreachable, reachable], |resolver| async move {
+            Err(PulseError::ResolverUnreachable {
+                resolver,
+                reason: "connection refused".to_string(),
+            })
+        })
+        .await;
+        assert!(matches!(result, Err(PulseError::ResolverUnreachable { resolver, .. }) if resolver == reachable));
+    }
+
+    #[test]
+    fn it_uses_the_profile_of_the_configured_network() {
+        let esmeralda = TariPulseProfile {
+            check_interval: Duration::from_secs(60),
+            consecutive_failures_before_notify: 5,
+            initial_delay: Duration::from_secs(30),
+        };
+        let mainnet = TariPulseProfile {
+            check_interval: Duration::from_secs(600),
+            consecutive_failures_before_notify: 2,
+            initial_delay: Duration::from_secs(300),
+        };
+        let profiles = HashMap::from([(Network::Esmeralda, esmeralda.clone()), (Network::MainNet, mainnet)]);
+
+        let initializer = TariPulseServiceInitializer::new(Duration::from_secs(120), Network::Esmeralda)
+            .with_profiles(profiles.clone());
+        let config = initializer.network_config();
+        assert_eq!(config.network, Network::Esmeralda);
+        assert_eq!(config.check_interval, esmeralda.check_interval);
+        assert_eq!(
+            config.consecutive_failures_before_notify,
+            esmeralda.consecutive_failures_before_notify
+        );
+        assert_eq!(config.initial_delay, esmeralda.initial_delay);
+
+        // Networks without a profile keep the configured schedule
+        let initializer =
+            TariPulseServiceInitializer::new(Duration::from_secs(120), Network::Igor).with_profiles(profiles);
+        let config = initializer.network_config();
+        let defaults = TariPulseConfig::default();
+        assert_eq!(config.check_interval, Duration::from_secs(120));
+        assert_eq!(
+            config.consecutive_failures_before_notify,
+            defaults.consecutive_failures_before_notify
+        );
+        assert_eq!(config.initial_delay, defaults.initial_delay);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_check_before_the_initial_delay() {
+        let mut interval = check_interval(&TariPulseConfig {
+            initial_delay: Duration::from_millis(300),
+            ..Default::default()
+        });
+        assert!(time::timeout(Duration::from_millis(100), interval.tick())
+            .await
+            .is_err());
+        assert!(time::timeout(Duration::from_secs(1), interval.tick()).await.is_ok());
+
+        // Without an initial delay the first check happens immediately
+        let mut interval = check_interval(&TariPulseConfig {
+            initial_delay: Duration::ZERO,
+            ..Default::default()
+        });
+        assert!(time::timeout(Duration::from_millis(100), interval.tick()).await.is_ok());
+    }
+
+    async fn dns_connection_attempted(enabled: bool) -> bool {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = TariPulseConfig {
+            enabled,
+            dns_resolvers: vec![listener.local_addr().unwrap()],
+            initial_delay: Duration::ZERO,
+            ..Default::default()
+        };
+        let shutdown = Shutdown::new();
+        let handles = StackBuilder::new(shutdown.to_signal())
+            .add_initializer(RegisterHandle::new(spawn_mock_chain(create_chain(5))))
+            .add_initializer(TariPulseServiceInitializer::with_config(config))
+            .build()
+            .await
+            .unwrap();
+        let tari_pulse = handles.expect_handle::<TariPulseHandle>();
+        assert_eq!(tari_pulse.status(), CheckpointStatus::Passed);
+
+        time::timeout(Duration::from_millis(500), listener.accept())
+            .await
+            .is_ok()
+    }
+
+    #[tokio::test]
+    async fn it_does_not_query_dns_when_disabled() {
+        assert!(!dns_connection_attempted(false).await);
+        // Sanity check that the service does connect to the resolver when enabled
+        assert!(dns_connection_attempted(true).await);
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_the_next_resolver_when_one_is_unreachable() {
+        // Nothing listens on the port of a dropped listener, so connecting to it is refused
+        let unreachable = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = TariPulseConfig {
+            dns_resolvers: vec![unreachable, listener.local_addr().unwrap()],
+            initial_delay: Duration::ZERO,
+            ..Default::default()
+        };
+        let shutdown = Shutdown::new();
+        let _handles = StackBuilder::new(shutdown.to_signal())
+            .add_initializer(RegisterHandle::new(spawn_mock_chain(create_chain(5))))
+            .add_initializer(TariPulseServiceInitializer::with_config(config))
+            .build()
+            .await
+            .unwrap();
+
+        assert!(time::timeout(Duration::from_secs(5), listener.accept()).await.is_ok());
+    }
+
+    #[test]
+    fn it_rejects_trust_anchors_below_the_minimum_strength() {
+        // A 512-bit key with exponent 65537
+        let mut weak_key = vec![3, 1, 0, 1, 0x80];
+        weak_key.extend_from_slice(&[0xff; 63]);
+        assert_eq!(rsa_modulus_bits(&weak_key), Some(512));
+
+        let anchors = [("weak", weak_key.as_slice()), ROOT_ANCHORS[1]];
+        let accepted = accepted_trust_anchors(&anchors, 2048);
+        assert_eq!(accepted.iter().map(|(name, _)| *name).collect::<Vec<_>>(), vec![
+            "38696"
+        ]);
+        assert_eq!(accepted_trust_anchors(&anchors, 0).len(), 2);
+
+        // The root anchors meet the default minimum
+        let min_rsa_bits = TariPulseConfig::default().min_trust_anchor_rsa_bits;
+        assert_eq!(
+            accepted_trust_anchors(ROOT_ANCHORS, min_rsa_bits).len(),
+            ROOT_ANCHORS.len()
+        );
+        assert!(matches!(
+            TariPulseService::trust_anchor(4096),
+            Err(PulseError::NoTrustAnchor { min_rsa_bits: 4096 })
+        ));
+    }
+
+    #[test]
+    fn it_reports_the_trust_anchor_that_validated_a_query() {
+        assert_eq!(validating_anchors(ROOT_ANCHORS, &[38696]), vec!["38696"]);
+        // Both anchors sign the root DNSKEY set during a rollover
+        assert_eq!(validating_anchors(ROOT_ANCHORS, &[38696, 20326]), vec!["20326", "38696"]);
+        assert!(validating_anchors(ROOT_ANCHORS, &[12345]).is_empty());
+
+        let deadline = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let before = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        let after = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        assert!(relies_on_legacy_anchor(&["20326"], Some(deadline), after));
+        assert!(!relies_on_legacy_anchor(&["20326"], Some(deadline), before));
+        assert!(!relies_on_legacy_anchor(&["20326"], None, after));
+        assert!(!relies_on_legacy_anchor(&["20326", "38696"], Some(deadline), after));
+        assert!(!relies_on_legacy_anchor(&["38696"], Some(deadline), after));
+    }
+
+    #[tokio::test]
+    async fn it_reports_the_loaded_trust_anchors() {
+        let shutdown = Shutdown::new();
+        let service = TariPulseService::new(TariPulseConfig::default(), shutdown.to_signal())
+            .await
+            .unwrap();
+        assert_eq!(service.trust_anchor_key_tags(), vec![20326, 38696]);
+
+        let service = TariPulseService::new(
+            TariPulseConfig {
+                min_trust_anchor_rsa_bits: 4096,
+                ..Default::default()
+            },
+            shutdown.to_signal(),
+        )
+        .await
+        .unwrap();
+        assert!(service.trust_anchor_key_tags().is_empty());
+
+        let review_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert!(!is_anchor_review_due(review_date, review_date));
+        assert!(is_anchor_review_due(review_date, review_date.succ_opt().unwrap()));
+    }
+
+    #[test]
+    fn it_only_notifies_after_consecutive_failures() {
+        let report = |results: &[(u64, bool)]| CheckpointReport {
+            checked: results
+                .iter()
+                .map(|&(height, passed)| CheckedCheckpoint {
+                    height,
+                    checkpoint_hash: String::new(),
+                    local_hash: String::new(),
+                    passed,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let mismatch = report(&[(100, true), (200, false)]);
+        let passed = report(&[(100, true), (200, true)]);
+        // Intermittent mismatches never reach the threshold
+        let mut failures = ConsecutiveFailures::new(TariPulseConfig::default().consecutive_failures_before_notify);
+        let checks = [&mismatch, &mismatch, &passed, &mismatch, &passed, &mismatch, &mismatch];
+        assert!(checks.iter().all(|report| !failures.record(report.has_mismatch())));
+
+        // Only the third consecutive mismatch is notified, and a pass clears it again
+        assert!(failures.record(mismatch.has_mismatch()));
+        assert!(failures.record(mismatch.has_mismatch()));
+        assert!(!failures.record(passed.has_mismatch()));
+        assert!(!failures.record(mismatch.has_mismatch()));
+
+        // A threshold of 0 behaves like 1
+        let mut failures = ConsecutiveFailures::new(0);
+        assert!(failures.record(true));
+    }
+
+    /// A mock resolver that answers from the given records and records the names it was queried for
+    async fn resolve_with_mock(
+        name: Name,
+        records: &[Record],
+        max_depth: usize,
+    ) -> (Result<(Name, Vec<Record>), PulseError>, Vec<Name>) {
+        let mut queried = Vec::new();
+        let result = resolve_cname_chain(name, RecordType::TXT, max_depth, |name| {
+            queried.push(name.clone());
+            let answers = records
+                .iter()
+                .filter(|record| *record.name() == name)
+                .cloned()
+                .collect::<Vec<_>>();
+            async move { Ok(answers) }
+        })
+        .await;
+        (result, queried)
+    }
+
+    #[tokio::test]
+    async fn it_falls_back_to_the_checkpoint_file_when_dns_fails() {
+        let chain = create_chain(5);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoints.txt");
+        std::fs::write(
+            &path,
+            format!("2:{}\n4:{}\n", chain[2].hash().to_hex(), chain[4].hash().to_hex()),
+        )
+        .unwrap();
+        let checkpoint_file = CheckpointFile::new(&path, None);
+        let query_count = AtomicUsize::new(0);
+        let dns_queries = &query_count;
+        let failing_dns = move || async move {
+            dns_queries.fetch_add(1, Ordering::SeqCst);
+            Err(PulseError::Dns("connection refused".to_string()))
+        };
+
+        let checkpoints = fetch_with_file_fallback(Some(&checkpoint_file), false, failing_dns)
+            .await
+            .unwrap();
+        assert_eq!(dns_queries.load(Ordering::SeqCst), 1);
+        let mut base_node_service = spawn_mock_chain(chain);
+        let report = verify_checkpoints(&mut base_node_service, &checkpoints).await.unwrap();
+        assert_eq!(report.results(), vec![(2, true), (4, true)]);
+        assert!(!report.has_mismatch());
+
+        // DNS is not queried when the file is preferred, and without a file the DNS error is returned
+        let checkpoints = fetch_with_file_fallback(Some(&checkpoint_file), true, failing_dns)
+            .await
+            .unwrap();
+        assert_eq!(checkpoints.len(), 2);
+        assert_eq!(dns_queries.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            fetch_with_file_fallback(None, true, failing_dns).await,
+            Err(PulseError::Dns(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_checks_the_checkpoints_on_demand() {
+        let chain = create_chain(5);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoints.txt");
+        std::fs::write(&path, format!("4:{}\n", chain[4].hash().to_hex())).unwrap();
+        let config = TariPulseConfig {
+            check_interval: Duration::from_secs(3600),
+            initial_delay: Duration::from_secs(3600),
+            checkpoint_file: Some(path.clone()),
+            prefer_file: true,
+            ..Default::default()
+        };
+        let shutdown = Shutdown::new();
+        let handles = StackBuilder::new(shutdown.to_signal())
+            .add_initializer(RegisterHandle::new(spawn_mock_chain(chain.clone())))
+            .add_initializer(TariPulseServiceInitializer::with_config(config))
+            .build()
+            .await
+            .unwrap();
+        let tari_pulse = handles.expect_handle::<TariPulseHandle>();
+        assert_eq!(tari_pulse.check_now().await.unwrap(), CheckpointStatus::Passed);
+
+        std::fs::write(&path, format!("4:{}\n", chain[3].hash().to_hex())).unwrap();
+        assert_eq!(tari_pulse.check_now().await.unwrap(), CheckpointStatus::HashMismatch {
+            height: 4,
+            local_hash: chain[4].hash().to_hex(),
+            dns_hash: chain[3].hash().to_hex(),
+        });
+
+        // A checkpoint above the local tip is reported as not yet synced rather than as a failure
+        std::fs::write(&path, format!("9:{}\n", chain[3].hash().to_hex())).unwrap();
+        let status = tari_pulse.check_now().await.unwrap();
+        assert_eq!(status, CheckpointStatus::NotYetSynced { local: 5, dns: 9 });
+        assert!(!status.has_failed());
+        // An on-demand check does not count towards the periodic failure notifications
+        assert_eq!(tari_pulse.status(), CheckpointStatus::Passed);
+    }
+
+    #[tokio::test]
+    async fn it_follows_cname_chains_to_the_checkpoint_records() {
+        let name = get_network_dns_name(Network::LocalNet);
+        let target = Name::from_str("checkpoints.example.com.").unwrap();
+        let cname = Record::from_rdata(name.clone(), 60, RData::CNAME(CNAME(target.clone())));
+        let txt = Record::from_rdata(target.clone(), 60, RData::TXT(TXT::new(vec!["100:abcdef".to_string()])));
+
+        // The resolver only returns the CNAME, so the target is queried
+        let (result, queried) = resolve_with_mock(name.clone(), &[cname.clone(), txt.clone()], 8).await;
+        let (canonical_name, answers) = result.unwrap();
+        assert_eq!(canonical_name, target);
+        assert_eq!(queried, vec![name.clone(), target.clone()]);
+        assert_eq!(parse_checkpoint_answers(&answers, RecordType::TXT, false), vec![(
+            100,
+            "abcdef".to_string()
+        )]);
+
+        // The chain and the records are returned in a single answer
+        let mut num_queries = 0;
+        let (canonical_name, answers) = resolve_cname_chain(name.clone(), RecordType::TXT, 8, |_| {
+            num_queries += 1;
+            let answers = vec![cname.clone(), txt.clone()];
+            async move { Ok(answers) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(canonical_name, target);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(num_queries, 1);
+
+        // Names without a CNAME resolve to themselves
+        let direct = Record::from_rdata(name.clone(), 60, RData::TXT(TXT::new(vec!["100:abcdef".to_string()])));
+        let (result, queried) = resolve_with_mock(name.clone(), &[direct], 8).await;
+        assert_eq!(result.unwrap().0, name);
+        assert_eq!(queried, vec![name]);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_cname_chains_that_exceed_the_maximum_depth() {
+        let a = Name::from_str("a.example.com.").unwrap();
+        let b = Name::from_str("b.example.com.").unwrap();
+        let c = Name::from_str("c.example.com.").unwrap();
+        let chain = [
+            Record::from_rdata(a.clone(), 60, RData::CNAME(CNAME(b.clone()))),
+            Record::from_rdata(b.clone(), 60, RData::CNAME(CNAME(c.clone()))),
+            Record::from_rdata(c.clone(), 60, RData::TXT(TXT::new(vec!["100:abcdef".to_string()]))),
+        ];
+        assert_eq!(resolve_with_mock(a.clone(), &chain, 2).await.0.unwrap().0, c);
+        assert!(matches!(
+            resolve_with_mock(a.clone(), &chain, 1).await.0,
+            Err(PulseError::CnameChainTooLong { max_depth: 1, .. })
+        ));
+
+        // A CNAME loop is cut off at the maximum depth
+        let cname_loop = [
+            Record::from_rdata(a.clone(), 60, RData::CNAME(CNAME(b.clone()))),
+            Record::from_rdata(b.clone(), 60, RData::CNAME(CNAME(a.clone()))),
+        ];
+        let (result, queried) = resolve_with_mock(a, &cname_loop, 8).await;
+        assert!(matches!(
+            result,
+            Err(PulseError::CnameChainTooLong { max_depth: 8, .. })
+        ));
+        assert_eq!(queried.len(), 9);
+    }
+}
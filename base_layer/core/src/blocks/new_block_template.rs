@@ -85,6 +85,12 @@ impl NewBlockTemplate {
             is_mempool_in_sync: false,
         }
     }
+
+    /// A concise one-line description of this template for mining UIs, e.g.
+    /// `RandomX block #12345 on 0a1b2c3d.. diff=1000`
+    pub fn summary(&self) -> String {
+        format!("{} diff={}", self.header.summary(), self.target_difficulty)
+    }
 }
 
 impl Display for NewBlockTemplate {
@@ -20,14 +20,24 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::fmt::{Display, Error, Formatter};
+use std::{
+    fmt::{Display, Error, Formatter},
+    mem::size_of,
+};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::{BlockHash, PrivateKey};
+use tari_crypto::keys::SecretKey;
 use tari_utilities::hex::Hex;
 
 use crate::{blocks::block_header::BlockHeader, proof_of_work::ProofOfWork};
+
+/// The size of the length prefix borsh writes for variable length byte sequences
+const BORSH_LENGTH_PREFIX_SIZE: usize = size_of::<u32>();
+/// The number of hex characters of a hash shown in a template summary
+const SUMMARY_HASH_LEN: usize = 8;
+
 /// The NewBlockHeaderTemplate is used for the construction of a new mineable block. It contains all the metadata for
 /// the block that the Base Node is able to complete on behalf of a Miner.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize)]
@@ -69,6 +79,29 @@ impl NewBlockHeaderTemplate {
             pow: Default::default(),
         }
     }
+
+    /// The length of the borsh serialization of this template, computed without serializing it
+    pub fn serialized_size(&self) -> usize {
+        let private_key_size = BORSH_LENGTH_PREFIX_SIZE + PrivateKey::KEY_LEN;
+        size_of::<u16>() + // version
+            size_of::<u64>() + // height
+            BlockHash::byte_size() + // prev_hash
+            2 * private_key_size + // total_kernel_offset, total_script_offset
+            size_of::<u8>() + // pow.pow_algo
+            BORSH_LENGTH_PREFIX_SIZE + self.pow.pow_data.len() // pow.pow_data
+    }
+
+    /// A concise one-line description of this template, e.g. `RandomX block #12345 on 0a1b2c3d..`. The `Display`
+    /// implementation remains the verbose form used for logging.
+    pub fn summary(&self) -> String {
+        let prev_hash = self.prev_hash.to_hex();
+        format!(
+            "{} block #{} on {}..",
+            self.pow.pow_algo,
+            self.height,
+            &prev_hash[..SUMMARY_HASH_LEN.min(prev_hash.len())]
+        )
+    }
 }
 
 impl Display for NewBlockHeaderTemplate {
@@ -86,3 +119,47 @@ impl Display for NewBlockHeaderTemplate {
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::proof_of_work::{PowAlgorithm, PowData};
+
+    #[test]
+    fn it_estimates_the_serialized_size() {
+        let template = NewBlockHeaderTemplate::empty();
+        assert_eq!(template.serialized_size(), borsh::to_vec(&template).unwrap().len());
+
+        let template = NewBlockHeaderTemplate {
+            version: 1,
+            height: 123_456,
+            prev_hash: BlockHash::from([1u8; 32]),
+            total_kernel_offset: PrivateKey::random(&mut OsRng),
+            total_script_offset: PrivateKey::random(&mut OsRng),
+            pow: ProofOfWork {
+                pow_algo: PowAlgorithm::RandomX,
+                pow_data: PowData::try_from(vec![7u8; 100]).unwrap(),
+            },
+        };
+        assert_eq!(template.serialized_size(), borsh::to_vec(&template).unwrap().len());
+    }
+
+    #[test]
+    fn it_summarises_the_template_on_one_line() {
+        let template = NewBlockHeaderTemplate {
+            height: 12_345,
+            prev_hash: BlockHash::from([0xab; 32]),
+            pow: ProofOfWork {
+                pow_algo: PowAlgorithm::RandomX,
+                pow_data: PowData::default(),
+            },
+            ..NewBlockHeaderTemplate::empty()
+        };
+        let summary = template.summary();
+        assert_eq!(summary, "RandomX block #12345 on abababab..");
+        assert!(!summary.contains('\n'));
+        assert!(!summary.contains(&template.prev_hash.to_hex()));
+    }
+}
@@ -29,7 +29,7 @@ use std::{
     convert::{TryFrom, TryInto},
     fmt,
     fmt::{Display, Formatter},
-    mem::size_of,
+    mem::{self, size_of},
 };
 
 use blake2::Blake2b;
@@ -45,6 +45,7 @@ use digest::{consts::U32, generic_array::GenericArray, FixedOutput};
 use num_traits::{FromPrimitive, ToBytes};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
 use tari_common_types::{
     tari_address::{TariAddress, TARI_ADDRESS_INTERNAL_DUAL_SIZE, TARI_ADDRESS_INTERNAL_SINGLE_SIZE},
     types::{Commitment, PrivateKey},
@@ -75,6 +76,11 @@ const MAX_ENCRYPTED_DATA_SIZE: usize = 256 + STATIC_ENCRYPTED_DATA_SIZE_TOTAL;
 // Number of hex characters of encrypted data to display on each side of ellipsis when truncating
 const DISPLAY_CUTOFF: usize = 16;
 
+/// Encrypted value, mask and payment id of an output.
+///
+/// Note: this type implements `Zeroize` but not `ZeroizeOnDrop`, so its contents are not scrubbed when it is dropped.
+/// Use [EncryptedData::wipe] to explicitly clear values that are held for a long time, e.g. when evicting them from a
+/// cache.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize, Zeroize)]
 pub struct EncryptedData {
     #[serde(with = "tari_utilities::serde::hex")]
@@ -135,6 +141,31 @@ impl TxType {
     fn as_bytes(&self) -> Vec<u8> {
         vec![self.as_u8()]
     }
+
+    /// The higher-level category of outputs with this transaction type
+    pub fn category(&self) -> OutputCategory {
+        match self {
+            TxType::PaymentToOther => OutputCategory::Payment,
+            TxType::PaymentToSelf | TxType::CoinSplit | TxType::CoinJoin => OutputCategory::Internal,
+            TxType::Burn => OutputCategory::Burn,
+            TxType::ValidatorNodeRegistration | TxType::CodeTemplateRegistration => OutputCategory::Registration,
+            TxType::ClaimAtomicSwap | TxType::HtlcAtomicSwapRefund => OutputCategory::AtomicSwap,
+            TxType::ImportedUtxoNoneRewindable => OutputCategory::Imported,
+        }
+    }
+
+    /// Create a `PaymentId::Open` with this transaction type and no user data
+    pub fn into_open(self) -> PaymentId {
+        self.into_open_with(Vec::new())
+    }
+
+    /// Create a `PaymentId::Open` with this transaction type and the given user data
+    pub fn into_open_with(self, user_data: Vec<u8>) -> PaymentId {
+        PaymentId::Open {
+            user_data,
+            tx_type: self,
+        }
+    }
 }
 
 impl Display for TxType {
@@ -154,6 +185,132 @@ impl Display for TxType {
     }
 }
 
+/// A higher-level grouping of transaction types, used to categorize outputs for display and accounting
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum OutputCategory {
+    /// A payment to another wallet
+    Payment,
+    /// Funds moved within the wallet, i.e. payments to self, coin splits and coin joins
+    Internal,
+    /// Burnt funds
+    Burn,
+    /// Validator node and code template registrations
+    Registration,
+    /// Atomic swap claims and refunds
+    AtomicSwap,
+    /// Outputs imported into the wallet
+    Imported,
+}
+
+/// The metadata fields of a 'PaymentId::TransactionInfo' that are packed into a fixed number of bits when serialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetadataField {
+    Fee,
+    Weight,
+    InputsCount,
+    OutputsCount,
+}
+
+/// A field that differs between two payment ids, as reported by 'PaymentId::diff'
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The field name, as used when displaying the payment id
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// The fields of a 'PaymentId::TransactionInfo', as passed to 'PaymentId::transaction_info'
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionInfoFields {
+    pub recipient_address: TariAddress,
+    pub sender_one_sided: bool,
+    pub amount: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub weight: u64,
+    pub inputs_count: usize,
+    pub outputs_count: usize,
+    pub tx_type: TxType,
+    pub user_data: Vec<u8>,
+}
+
+/// A payment id parsed by 'PaymentId::from_bytes_preserving_unknown', together with the trailing bytes of the payload
+/// that the parsed payment id does not reproduce, e.g. fields added by a newer version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreservedPaymentId {
+    payment_id: PaymentId,
+    /// The encoding the payload was detected to be in
+    encoding: PaymentIdEncoding,
+    /// The length of the payload prefix that the parsed payment id serializes to unchanged in `encoding`
+    known_len: usize,
+    unknown: Vec<u8>,
+}
+
+impl PreservedPaymentId {
+    /// The payment id as understood by this version
+    pub fn payment_id(&self) -> &PaymentId {
+        &self.payment_id
+    }
+
+    /// The encoding the payment id was parsed from
+    pub fn encoding(&self) -> PaymentIdEncoding {
+        self.encoding
+    }
+
+    /// The trailing bytes that the payment id does not represent
+    pub fn unknown(&self) -> &[u8] {
+        &self.unknown
+    }
+
+    pub fn into_payment_id(self) -> PaymentId {
+        self.payment_id
+    }
+
+    /// Serialize to exactly the bytes the payment id was parsed from
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.payment_id.to_bytes_with(self.encoding);
+        bytes.truncate(self.known_len);
+        bytes.extend_from_slice(&self.unknown);
+        bytes
+    }
+}
+
+/// The serialized format of a payment id. Payment ids are always read by detecting the format, so that nodes can
+/// keep writing legacy payment ids during a network transition and switch to the versioned format later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PaymentIdEncoding {
+    /// The unversioned format, where the variant is inferred from the length and content of the bytes
+    #[default]
+    Legacy = 0,
+    /// The legacy bytes, prefixed with a marker, the format version and an explicit variant tag.
+    /// Note: V1 is decode-only for now. Outputs are built with the legacy encoding, and V1 is only written when it is
+    /// passed to `EncryptedData::encrypt_data_with_encoding` explicitly, so that nodes can read it before it is used.
+    V1 = 1,
+}
+
+impl PaymentIdEncoding {
+    const V1_HEADER_SIZE: usize = PaymentIdEncoding::V1_PREFIX.len() + 1;
+    const V1_PREFIX: [u8; 3] = [0xff, b'P', 1];
+
+    /// The number of bytes the encoding adds to the legacy serialized payment id
+    fn header_size(self) -> usize {
+        match self {
+            PaymentIdEncoding::Legacy => 0,
+            PaymentIdEncoding::V1 => PaymentIdEncoding::V1_HEADER_SIZE,
+        }
+    }
+
+    /// Detect the encoding of serialized payment id bytes. Legacy `U64`, `U256` and `TransactionInfo` payment ids
+    /// start with arbitrary bytes, so bytes are only detected as V1 if they also decode as the tagged variant.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if PaymentId::from_v1_bytes(bytes).is_some() {
+            PaymentIdEncoding::V1
+        } else {
+            PaymentIdEncoding::Legacy
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
 pub enum PaymentId {
     /// No payment ID.
@@ -187,28 +344,54 @@ pub enum PaymentId {
         tx_type: TxType,
         user_data: Vec<u8>,
     },
+    /// Only the transaction type, without any user data, serialized as a single byte. An `Open` payment ID with
+    /// empty 'user_data' has the same serialized form and is read back as this variant.
+    /// Note: Declared last so that the serde variant indexes of the other variants, e.g. in pending transactions
+    /// stored by the wallet, are unchanged.
+    TypeOnly(TxType),
 }
 
 impl PaymentId {
+    /// Packed into 4 bytes
+    const MAX_META_DATA_FEE: u64 = 2u64.pow(32) - 1;
+    /// Packed into 2 bytes less 1 bit, used for 'sender_one_sided'
+    const MAX_META_DATA_INPUTS_COUNT: usize = 2usize.pow(15) - 1;
+    /// Packed into 2 bytes less 4 bits, used for 'tx_type'
+    const MAX_META_DATA_OUTPUTS_COUNT: usize = 2usize.pow(12) - 1;
+    /// Packed into 2 bytes
+    const MAX_META_DATA_WEIGHT: u64 = 2u64.pow(16) - 1;
+    /// The maximum length of the 'user_data' of a 'PaymentId::Open' in the legacy encoding, i.e. the encryption ceiling
+    /// minus the 'tx_type'
+    const MAX_OPEN_USER_DATA_SIZE: usize = PaymentId::MAX_SIZE - 1;
+    /// The maximum serialized size of a payment id, including any encoding header, that can still be encrypted
+    const MAX_SIZE: usize = MAX_ENCRYPTED_DATA_SIZE - STATIC_ENCRYPTED_DATA_SIZE_TOTAL;
     const SIZE_META_DATA: usize = 10;
     const SIZE_VALUE_AND_META_DATA: usize = SIZE_VALUE + PaymentId::SIZE_META_DATA;
 
+    /// The serialized size, same as `serialized_len`
     pub fn get_size(&self) -> usize {
+        self.serialized_len()
+    }
+
+    /// The exact number of bytes returned by `to_bytes`, computed without serializing
+    pub fn serialized_len(&self) -> usize {
+        const SIZE_TX_TYPE: usize = 1;
         match self {
             PaymentId::Empty => 0,
             PaymentId::U64(_) => SIZE_VALUE,
             PaymentId::U256(_) => SIZE_U256,
-            PaymentId::Open { user_data, .. } => user_data.len() + 1,
+            PaymentId::TypeOnly(_) => SIZE_TX_TYPE,
+            PaymentId::Open { user_data, .. } => SIZE_TX_TYPE + user_data.len(),
             PaymentId::AddressAndData {
                 sender_address,
                 user_data,
                 ..
-            } => sender_address.get_size() + user_data.len() + 1,
+            } => sender_address.get_size() + SIZE_TX_TYPE + user_data.len(),
             PaymentId::TransactionInfo {
                 recipient_address,
                 user_data,
                 ..
-            } => recipient_address.get_size() + PaymentId::SIZE_VALUE_AND_META_DATA + user_data.len(),
+            } => PaymentId::SIZE_VALUE_AND_META_DATA + recipient_address.get_size() + user_data.len(),
         }
     }
 
@@ -219,8 +402,14 @@ impl PaymentId {
         }
     }
 
+    /// The category of the output this payment id belongs to, derived from its transaction type
+    pub fn category(&self) -> OutputCategory {
+        self.get_type().category()
+    }
+
     pub fn get_type(&self) -> TxType {
         match self {
+            PaymentId::TypeOnly(tx_type) |
             PaymentId::Open { tx_type, .. } |
             PaymentId::AddressAndData { tx_type, .. } |
             PaymentId::TransactionInfo { tx_type, .. } => tx_type.clone(),
@@ -228,6 +417,20 @@ impl PaymentId {
         }
     }
 
+    /// Normalize equivalent forms of a payment id so that semantically equal payment ids compare equal:
+    /// - `Open` with empty 'user_data' becomes `TypeOnly` with the same 'tx_type', as both serialize identically.
+    /// - `TypeOnly` with the default 'tx_type' (including the above) becomes `Empty`, as neither carries any data.
+    ///
+    /// All other payment ids are returned unchanged. The canonical form may serialize differently, so it should be
+    /// used for comparisons rather than to re-encrypt an existing payment id.
+    pub fn canonical(self) -> PaymentId {
+        match self {
+            PaymentId::Open { user_data, tx_type } if user_data.is_empty() => PaymentId::TypeOnly(tx_type).canonical(),
+            PaymentId::TypeOnly(tx_type) if tx_type == TxType::default() => PaymentId::Empty,
+            payment_id => payment_id,
+        }
+    }
+
     /// Helper function to set the 'recipient_address' of a 'PaymentId::TransactionInfo'
     pub fn transaction_info_set_address(&mut self, address: TariAddress) {
         if let PaymentId::TransactionInfo { recipient_address, .. } = self {
@@ -235,8 +438,9 @@ impl PaymentId {
         }
     }
 
-    /// Helper function to convert a 'PaymentId::Open' or 'PaymentId::Empty' to a 'PaymentId::AddressAndData', with the
-    /// optional 'tx_type' only applicable to 'PaymentId::Open', otherwise 'payment_id' is kept as is.
+    /// Helper function to convert a 'PaymentId::Open', 'PaymentId::TypeOnly' or 'PaymentId::Empty' to a
+    /// 'PaymentId::AddressAndData', with the optional 'tx_type' only applicable to 'PaymentId::Empty', otherwise
+    /// 'payment_id' is kept as is.
     pub fn add_sender_address(
         payment_id: PaymentId,
         sender_address: TariAddress,
@@ -248,6 +452,11 @@ impl PaymentId {
                 tx_type,
                 user_data,
             },
+            PaymentId::TypeOnly(tx_type) => PaymentId::AddressAndData {
+                sender_address,
+                tx_type,
+                user_data: vec![],
+            },
             PaymentId::Empty => PaymentId::AddressAndData {
                 sender_address,
                 tx_type: tx_type.unwrap_or_default(),
@@ -257,6 +466,128 @@ impl PaymentId {
         }
     }
 
+    /// Like 'add_sender_address', but first checks that 'sender_address' is for the 'expected' network so that an
+    /// address for another network is never embedded in the payment id
+    pub fn with_validated_sender_address(
+        payment_id: PaymentId,
+        sender_address: TariAddress,
+        expected: Network,
+    ) -> Result<PaymentId, PaymentIdError> {
+        let network = sender_address.network();
+        if network != expected {
+            return Err(PaymentIdError::AddressNetworkMismatch { expected, network });
+        }
+        Ok(PaymentId::add_sender_address(payment_id, sender_address, None))
+    }
+
+    /// Returns the network of the address embedded in the payment id, if any
+    pub fn address_network(&self) -> Option<Network> {
+        match self {
+            PaymentId::AddressAndData { sender_address, .. } => Some(sender_address.network()),
+            PaymentId::TransactionInfo { recipient_address, .. } => Some(recipient_address.network()),
+            _ => None,
+        }
+    }
+
+    /// Checks that the address embedded in the payment id, if any, is for the `expected` network. An address for
+    /// another network is a red flag, e.g. a testnet address in an output received on mainnet.
+    pub fn validate_address_network(&self, expected: Network) -> Result<(), PaymentIdError> {
+        match self.address_network() {
+            Some(network) if network != expected => Err(PaymentIdError::AddressNetworkMismatch { expected, network }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the metadata fields that exceed their packed size and would be zeroed when serialized. Only a
+    /// 'PaymentId::TransactionInfo' has metadata fields.
+    pub fn metadata_overflows(&self) -> Vec<MetadataField> {
+        let mut overflows = Vec::new();
+        if let PaymentId::TransactionInfo {
+            fee,
+            weight,
+            inputs_count,
+            outputs_count,
+            ..
+        } = self
+        {
+            if fee.as_u64() > PaymentId::MAX_META_DATA_FEE {
+                overflows.push(MetadataField::Fee);
+            }
+            if *weight > PaymentId::MAX_META_DATA_WEIGHT {
+                overflows.push(MetadataField::Weight);
+            }
+            if *inputs_count > PaymentId::MAX_META_DATA_INPUTS_COUNT {
+                overflows.push(MetadataField::InputsCount);
+            }
+            if *outputs_count > PaymentId::MAX_META_DATA_OUTPUTS_COUNT {
+                overflows.push(MetadataField::OutputsCount);
+            }
+        }
+        overflows
+    }
+
+    /// Lists the fields that differ between this payment id and `other`, with this payment id's value as `old` and
+    /// `other`'s value as `new`. Payment ids of different variants are reported as a single 'variant' difference.
+    pub fn diff(&self, other: &PaymentId) -> Vec<FieldDiff> {
+        if mem::discriminant(self) != mem::discriminant(other) {
+            return vec![FieldDiff {
+                field: "variant",
+                old: self.to_string(),
+                new: other.to_string(),
+            }];
+        }
+        self.fields()
+            .into_iter()
+            .zip(other.fields())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((field, old), (_, new))| FieldDiff { field, old, new })
+            .collect()
+    }
+
+    /// The named fields of the payment id with their display values
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        match self {
+            PaymentId::Empty => vec![],
+            PaymentId::U64(v) => vec![("u64", v.to_string())],
+            PaymentId::U256(v) => vec![("u256", v.to_string())],
+            PaymentId::TypeOnly(tx_type) => vec![("type", tx_type.to_string())],
+            PaymentId::Open { user_data, tx_type } => vec![
+                ("type", tx_type.to_string()),
+                ("data", PaymentId::stringify_bytes(user_data)),
+            ],
+            PaymentId::AddressAndData {
+                sender_address,
+                tx_type,
+                user_data,
+            } => vec![
+                ("sender_address", sender_address.to_base58()),
+                ("type", tx_type.to_string()),
+                ("data", PaymentId::stringify_bytes(user_data)),
+            ],
+            PaymentId::TransactionInfo {
+                recipient_address,
+                sender_one_sided,
+                amount,
+                fee,
+                weight,
+                inputs_count,
+                outputs_count,
+                tx_type,
+                user_data,
+            } => vec![
+                ("recipient_address", recipient_address.to_base58()),
+                ("sender_one_sided", sender_one_sided.to_string()),
+                ("amount", amount.to_string()),
+                ("fee", fee.to_string()),
+                ("weight", weight.to_string()),
+                ("inputs_count", inputs_count.to_string()),
+                ("outputs_count", outputs_count.to_string()),
+                ("type", tx_type.to_string()),
+                ("data", PaymentId::stringify_bytes(user_data)),
+            ],
+        }
+    }
+
     // This method is infallible; any out-of-bound values will be zeroed.
     fn pack_meta_data(&self) -> Vec<u8> {
         if let PaymentId::TransactionInfo {
@@ -272,21 +603,25 @@ impl PaymentId {
             let mut bytes = Vec::with_capacity(10);
             // Zero out-of-bound values
             // - Use 4 bytes for 'fee', max value: 4,294,967,295
-            let fee = if fee.as_u64() > 2u64.pow(32) - 1 {
+            let fee = if fee.as_u64() > PaymentId::MAX_META_DATA_FEE {
                 0
             } else {
                 fee.as_u64()
             };
             // - Use 2 bytes for 'weight', max value: 65,535
-            let weight = if *weight > 2u64.pow(16) - 1 { 0 } else { *weight };
+            let weight = if *weight > PaymentId::MAX_META_DATA_WEIGHT {
+                0
+            } else {
+                *weight
+            };
             // - Use 2 bytes less 1 bit for 'inputs_count', max value: 32,767, and 1 bit for 'sender_one_sided'
-            let inputs_count = if *inputs_count > 2usize.pow(15) - 1 {
+            let inputs_count = if *inputs_count > PaymentId::MAX_META_DATA_INPUTS_COUNT {
                 0
             } else {
                 *inputs_count
             };
             // - Use 2 bytes less 4 bits for 'outputs_count', max value: 4,095, and 3 bits for 'tx_meta_data'
-            let outputs_count = if *outputs_count > 2usize.pow(12) - 1 {
+            let outputs_count = if *outputs_count > PaymentId::MAX_META_DATA_OUTPUTS_COUNT {
                 0
             } else {
                 *outputs_count
@@ -333,7 +668,7 @@ impl PaymentId {
 
     pub fn user_data_as_bytes(&self) -> Vec<u8> {
         match &self {
-            PaymentId::Empty => vec![],
+            PaymentId::Empty | PaymentId::TypeOnly(_) => vec![],
             PaymentId::U64(v) => v.to_le_bytes().to_vec(),
             PaymentId::U256(v) => {
                 let bytes: &mut [u8] = &mut [0; SIZE_U256];
@@ -346,6 +681,62 @@ impl PaymentId {
         }
     }
 
+    /// Serialize the payment id in the given encoding. `to_bytes` is the legacy encoding.
+    pub fn to_bytes_with(&self, encoding: PaymentIdEncoding) -> Vec<u8> {
+        match encoding {
+            PaymentIdEncoding::Legacy => self.to_bytes(),
+            PaymentIdEncoding::V1 => {
+                let mut bytes = PaymentIdEncoding::V1_PREFIX.to_vec();
+                bytes.push(self.v1_tag());
+                bytes.extend_from_slice(&self.to_bytes());
+                bytes
+            },
+        }
+    }
+
+    /// The exact number of bytes returned by `to_bytes_with` for the given encoding
+    pub fn serialized_len_with(&self, encoding: PaymentIdEncoding) -> usize {
+        encoding.header_size() + self.serialized_len()
+    }
+
+    /// The maximum length of the 'user_data' of a 'PaymentId::Open' that can still be encrypted in the given encoding
+    pub fn max_open_user_data_size(encoding: PaymentIdEncoding) -> usize {
+        PaymentId::MAX_OPEN_USER_DATA_SIZE - encoding.header_size()
+    }
+
+    fn v1_tag(&self) -> u8 {
+        match self {
+            PaymentId::Empty => 0,
+            PaymentId::U64(_) => 1,
+            PaymentId::U256(_) => 2,
+            PaymentId::TypeOnly(_) => 3,
+            PaymentId::Open { .. } => 4,
+            PaymentId::AddressAndData { .. } => 5,
+            PaymentId::TransactionInfo { .. } => 6,
+        }
+    }
+
+    /// Decode V1 encoded bytes, returning `None` if the bytes are not a valid V1 payment id
+    fn from_v1_bytes(bytes: &[u8]) -> Option<Self> {
+        let (&tag, payload) = bytes.strip_prefix(&PaymentIdEncoding::V1_PREFIX[..])?.split_first()?;
+        let payment_id = match (tag, payload.len()) {
+            (0, 0) => PaymentId::Empty,
+            (1, SIZE_VALUE) | (2, SIZE_U256) | (3, 1) | (5, _) | (6, _) => PaymentId::from_legacy_bytes(payload),
+            // The tag distinguishes an open payment id without user data from `TypeOnly`
+            (4, len) if len > 0 => PaymentId::Open {
+                user_data: payload[1..].to_vec(),
+                tx_type: TxType::from_u8(payload[0]),
+            },
+            _ => return None,
+        };
+        // Reject payloads that the legacy decoding reads as a different variant
+        if payment_id.v1_tag() == tag {
+            Some(payment_id)
+        } else {
+            None
+        }
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         match self {
             PaymentId::Empty => Vec::new(),
@@ -355,6 +746,7 @@ impl PaymentId {
                 v.to_little_endian(&mut bytes);
                 bytes
             },
+            PaymentId::TypeOnly(tx_type) => tx_type.as_bytes(),
             PaymentId::Open { user_data, tx_type } => {
                 let mut bytes = tx_type.as_bytes();
                 bytes.extend_from_slice(user_data);
@@ -385,10 +777,39 @@ impl PaymentId {
         }
     }
 
-    #[allow(clippy::too_many_lines)]
+    /// Deserialize a payment id, detecting whether it is in the legacy or the V1 encoding. 'user_data' beyond what can
+    /// be encrypted is truncated, like 'open_truncated', so that the payment id can always be re-encrypted in the
+    /// encoding it was read in.
     pub fn from_bytes(bytes: &[u8]) -> Self {
+        match PaymentId::from_v1_bytes(bytes) {
+            Some(payment_id) => payment_id.truncated_to_max_size(PaymentIdEncoding::V1),
+            None => PaymentId::from_legacy_bytes(bytes).truncated_to_max_size(PaymentIdEncoding::Legacy),
+        }
+    }
+
+    /// Truncate the 'user_data' so that the payment id serialized in the given encoding is at most
+    /// `PaymentId::MAX_SIZE` bytes
+    fn truncated_to_max_size(mut self, encoding: PaymentIdEncoding) -> Self {
+        let excess = self.serialized_len_with(encoding).saturating_sub(PaymentId::MAX_SIZE);
+        if excess > 0 {
+            if let PaymentId::Open { user_data, .. } |
+            PaymentId::AddressAndData { user_data, .. } |
+            PaymentId::TransactionInfo { user_data, .. } = &mut self
+            {
+                user_data.truncate(user_data.len().saturating_sub(excess));
+            }
+        }
+        self
+    }
+
+    /// Decode the legacy encoding. A single byte, e.g. an `Open` payment id without user data already on chain, is read
+    /// as `TypeOnly` with the same transaction type. Both serialize to the same byte and carry the same data, so the
+    /// payment id is re-serialized unchanged and its type and (empty) user data read the same.
+    #[allow(clippy::too_many_lines)]
+    fn from_legacy_bytes(bytes: &[u8]) -> Self {
         match bytes.len() {
             0 => PaymentId::Empty,
+            1 => PaymentId::TypeOnly(TxType::from_u8(bytes[0])),
             SIZE_VALUE => {
                 let bytes: [u8; SIZE_VALUE] = bytes.try_into().expect("Cannot fail, as we already test the length");
                 let v = u64::from_le_bytes(bytes);
@@ -506,6 +927,25 @@ impl PaymentId {
         }
     }
 
+    /// Parse a payment id like 'from_bytes', but capture the trailing bytes that the parsed payment id cannot
+    /// represent instead of dropping or reinterpreting them, so that they can be re-serialized unchanged
+    pub fn from_bytes_preserving_unknown(bytes: &[u8]) -> PreservedPaymentId {
+        let payment_id = PaymentId::from_bytes(bytes);
+        let encoding = PaymentIdEncoding::detect(bytes);
+        let known_len = payment_id
+            .to_bytes_with(encoding)
+            .iter()
+            .zip(bytes)
+            .take_while(|(a, b)| a == b)
+            .count();
+        PreservedPaymentId {
+            payment_id,
+            encoding,
+            known_len,
+            unknown: bytes[known_len..].to_vec(),
+        }
+    }
+
     /// Helper function to convert a byte slice to a string for the open and data variants
     pub fn stringify_bytes(bytes: &[u8]) -> String {
         String::from_utf8_lossy(bytes).to_string()
@@ -517,12 +957,76 @@ impl PaymentId {
             PaymentId::Empty => self.to_string(),
             PaymentId::U64(v) => format!("{}", v),
             PaymentId::U256(v) => format!("{}", v),
+            PaymentId::TypeOnly(_) => String::new(),
             PaymentId::Open { user_data, .. } => PaymentId::stringify_bytes(user_data),
             PaymentId::AddressAndData { user_data, .. } => PaymentId::stringify_bytes(user_data),
             PaymentId::TransactionInfo { user_data, .. } => PaymentId::stringify_bytes(user_data),
         }
     }
 
+    /// Create a `PaymentId::AddressAndData` directly from its fields, without a round trip through its bytes.
+    /// Fails if the payment id would be too large to encrypt in the given encoding.
+    pub fn address_and_data(
+        sender_address: TariAddress,
+        tx_type: TxType,
+        user_data: Vec<u8>,
+        encoding: PaymentIdEncoding,
+    ) -> Result<Self, PaymentIdError> {
+        PaymentId::AddressAndData {
+            sender_address,
+            tx_type,
+            user_data,
+        }
+        .validate_size(encoding)
+    }
+
+    /// Create a `PaymentId::TransactionInfo` directly from its fields, without a round trip through its bytes.
+    /// Fails if a metadata field exceeds its packed size or if the payment id would be too large to encrypt in the
+    /// given encoding.
+    pub fn transaction_info(
+        fields: TransactionInfoFields,
+        encoding: PaymentIdEncoding,
+    ) -> Result<Self, PaymentIdError> {
+        let TransactionInfoFields {
+            recipient_address,
+            sender_one_sided,
+            amount,
+            fee,
+            weight,
+            inputs_count,
+            outputs_count,
+            tx_type,
+            user_data,
+        } = fields;
+        let payment_id = PaymentId::TransactionInfo {
+            recipient_address,
+            sender_one_sided,
+            amount,
+            fee,
+            weight,
+            inputs_count,
+            outputs_count,
+            tx_type,
+            user_data,
+        };
+        let overflows = payment_id.metadata_overflows();
+        if !overflows.is_empty() {
+            return Err(PaymentIdError::MetadataOverflow(overflows));
+        }
+        payment_id.validate_size(encoding)
+    }
+
+    fn validate_size(self, encoding: PaymentIdEncoding) -> Result<Self, PaymentIdError> {
+        let size = self.serialized_len_with(encoding);
+        if size > PaymentId::MAX_SIZE {
+            return Err(PaymentIdError::TooLarge {
+                size,
+                max: PaymentId::MAX_SIZE,
+            });
+        }
+        Ok(self)
+    }
+
     /// Helper function to create a `PaymentId::Open` from a string and the transaction type
     pub fn open(s: &str, tx_type: TxType) -> Self {
         PaymentId::Open {
@@ -530,6 +1034,22 @@ impl PaymentId {
             tx_type,
         }
     }
+
+    /// Helper function to create a `PaymentId::Open` from a string and the transaction type, truncating the string to
+    /// the maximum length that can be encrypted in the given encoding. Returns the payment id and whether truncation
+    /// occurred.
+    pub fn open_truncated(s: &str, tx_type: TxType, encoding: PaymentIdEncoding) -> (Self, bool) {
+        let max_len = PaymentId::max_open_user_data_size(encoding);
+        if s.len() <= max_len {
+            return (PaymentId::open(s, tx_type), false);
+        }
+        // Do not split a multi-byte character
+        let mut end = max_len;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        (PaymentId::open(&s[..end], tx_type), true)
+    }
 }
 
 impl Display for PaymentId {
@@ -538,6 +1058,7 @@ impl Display for PaymentId {
             PaymentId::Empty => write!(f, "None"),
             PaymentId::U64(v) => write!(f, "u64({v})"),
             PaymentId::U256(v) => write!(f, "u256({v})"),
+            PaymentId::TypeOnly(tx_type) => write!(f, "type({})", tx_type),
             PaymentId::Open { user_data, tx_type } => {
                 write!(f, "type({}), data({})", tx_type, PaymentId::stringify_bytes(user_data))
             },
@@ -596,11 +1117,33 @@ impl EncryptedData {
         mask: &PrivateKey,
         payment_id: PaymentId,
     ) -> Result<EncryptedData, EncryptedDataError> {
-        // Encode the value and mask
-        let mut bytes = Zeroizing::new(vec![0; SIZE_VALUE + SIZE_MASK + payment_id.get_size()]);
+        EncryptedData::encrypt_data_with_encoding(
+            encryption_key,
+            commitment,
+            value,
+            mask,
+            payment_id,
+            PaymentIdEncoding::Legacy,
+        )
+    }
+
+    /// Encrypt the value, mask and payment id like `encrypt_data`, serializing the payment id in the given encoding
+    pub fn encrypt_data_with_encoding(
+        encryption_key: &PrivateKey,
+        commitment: &Commitment,
+        value: MicroMinotari,
+        mask: &PrivateKey,
+        payment_id: PaymentId,
+        encoding: PaymentIdEncoding,
+    ) -> Result<EncryptedData, EncryptedDataError> {
+        EncryptedData::size_for_payment_id(&payment_id, encoding)?;
+        // Encode the value, mask and payment id
+        let payment_id_bytes = Zeroizing::new(payment_id.to_bytes_with(encoding));
+        let payment_id_size = payment_id_bytes.len();
+        let mut bytes = Zeroizing::new(vec![0; SIZE_VALUE + SIZE_MASK + payment_id_size]);
         bytes[..SIZE_VALUE].clone_from_slice(value.as_u64().to_le_bytes().as_ref());
         bytes[SIZE_VALUE..SIZE_VALUE + SIZE_MASK].clone_from_slice(mask.as_bytes());
-        bytes[SIZE_VALUE + SIZE_MASK..].clone_from_slice(&payment_id.to_bytes());
+        bytes[SIZE_VALUE + SIZE_MASK..].clone_from_slice(&payment_id_bytes);
 
         // Produce a secure random nonce
         let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
@@ -613,10 +1156,10 @@ impl EncryptedData {
         let tag = cipher.encrypt_in_place_detached(&nonce, ENCRYPTED_DATA_AAD, bytes.as_mut_slice())?;
 
         // Put everything together: nonce, ciphertext, tag
-        let mut data = vec![0; STATIC_ENCRYPTED_DATA_SIZE_TOTAL + payment_id.get_size()];
+        let mut data = vec![0; STATIC_ENCRYPTED_DATA_SIZE_TOTAL + payment_id_size];
         data[..SIZE_TAG].clone_from_slice(&tag);
         data[SIZE_TAG..SIZE_TAG + SIZE_NONCE].clone_from_slice(&nonce);
-        data[SIZE_TAG + SIZE_NONCE..SIZE_TAG + SIZE_NONCE + SIZE_VALUE + SIZE_MASK + payment_id.get_size()]
+        data[SIZE_TAG + SIZE_NONCE..SIZE_TAG + SIZE_NONCE + SIZE_VALUE + SIZE_MASK + payment_id_size]
             .clone_from_slice(bytes.as_slice());
 
         Ok(Self {
@@ -678,6 +1221,31 @@ impl EncryptedData {
         })
     }
 
+    /// Assemble encrypted data from its tag, nonce and ciphertext, as produced by `encrypt_data`
+    pub fn from_parts(tag: &Tag, nonce: &XNonce, ciphertext: &[u8]) -> Result<Self, EncryptedDataError> {
+        if ciphertext.len() < SIZE_VALUE + SIZE_MASK {
+            return Err(EncryptedDataError::IncorrectLength(format!(
+                "Expected ciphertext to be at least {}, got {}",
+                SIZE_VALUE + SIZE_MASK,
+                ciphertext.len()
+            )));
+        }
+        if ciphertext.len() > MAX_ENCRYPTED_DATA_SIZE - SIZE_TAG - SIZE_NONCE {
+            return Err(EncryptedDataError::IncorrectLength(format!(
+                "Expected ciphertext to be at most {}, got {}",
+                MAX_ENCRYPTED_DATA_SIZE - SIZE_TAG - SIZE_NONCE,
+                ciphertext.len()
+            )));
+        }
+
+        // Put everything together: tag, nonce, ciphertext
+        let mut data = Vec::with_capacity(SIZE_TAG + SIZE_NONCE + ciphertext.len());
+        data.extend_from_slice(tag);
+        data.extend_from_slice(nonce);
+        data.extend_from_slice(ciphertext);
+        Self::from_bytes(&data)
+    }
+
     #[cfg(test)]
     pub fn from_vec_unsafe(data: Vec<u8>) -> Self {
         Self {
@@ -713,11 +1281,40 @@ impl EncryptedData {
         }
     }
 
+    /// Returns the total size of the encrypted data that `encrypt_data_with_encoding` produces for the payment id in
+    /// the given encoding, or an error if the payment id is too large to be encrypted
+    pub fn size_for_payment_id(
+        payment_id: &PaymentId,
+        encoding: PaymentIdEncoding,
+    ) -> Result<usize, EncryptedDataError> {
+        let size = STATIC_ENCRYPTED_DATA_SIZE_TOTAL + payment_id.serialized_len_with(encoding);
+        if size > MAX_ENCRYPTED_DATA_SIZE {
+            return Err(EncryptedDataError::TooLarge {
+                size,
+                max: MAX_ENCRYPTED_DATA_SIZE,
+            });
+        }
+        Ok(size)
+    }
+
     /// Returns the size of the payment id
     pub fn get_payment_id_size(&self) -> usize {
         // the length should always at least be the static total size, the extra len is the payment id
         self.data.len().saturating_sub(STATIC_ENCRYPTED_DATA_SIZE_TOTAL)
     }
+
+    /// Zeroizes the encrypted data contents and resets it to the default (all zero) encrypted data
+    pub fn wipe(&mut self) {
+        self.data.zeroize();
+        *self = Self::default();
+    }
+
+    /// Returns true if this is the default (all zero) placeholder rather than real encrypted data, e.g. for outputs
+    /// whose encrypted data has not been set
+    pub fn is_default_placeholder(&self) -> bool {
+        let bytes = self.as_bytes();
+        bytes.len() == STATIC_ENCRYPTED_DATA_SIZE_TOTAL && bytes.iter().all(|b| *b == 0)
+    }
 }
 
 impl Hex for EncryptedData {
@@ -747,6 +1344,18 @@ pub enum EncryptedDataError {
     ByteArrayError(String),
     #[error("Incorrect length: {0}")]
     IncorrectLength(String),
+    #[error("Encrypted data is {size} bytes, which exceeds the maximum of {max} bytes")]
+    TooLarge { size: usize, max: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PaymentIdError {
+    #[error("Payment id address is for network {network}, expected {expected}")]
+    AddressNetworkMismatch { expected: Network, network: Network },
+    #[error("Payment id is {size} bytes, which exceeds the maximum of {max} bytes")]
+    TooLarge { size: usize, max: usize },
+    #[error("Payment id metadata fields exceed their packed size: {0:?}")]
+    MetadataOverflow(Vec<MetadataField>),
 }
 
 impl From<ByteArrayError> for EncryptedDataError {
@@ -1190,6 +1799,8 @@ mod test {
              outputs_count(4095), type(PaymentToSelf), data(Hello World!!! 11-22-33)"
         );
 
+        assert!(payment_id_1.metadata_overflows().is_empty());
+        assert!(payment_id_2.metadata_overflows().is_empty());
         let payment_id_1_bytes = payment_id_1.to_bytes();
         let payment_id_2_bytes = payment_id_2.to_bytes();
 
@@ -1229,6 +1840,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_builds_from_parts_and_decrypts_correctly() {
+        let payment_id = PaymentId::Open {
+            user_data: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            tx_type: TxType::PaymentToOther,
+        };
+        let (value, mask) = (u64::MAX, PrivateKey::random(&mut OsRng));
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(value));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let encrypted_data =
+            EncryptedData::encrypt_data(&encryption_key, &commitment, value.into(), &mask, payment_id.clone()).unwrap();
+
+        let bytes = encrypted_data.as_bytes();
+        let tag = Tag::from_slice(&bytes[..SIZE_TAG]);
+        let nonce = XNonce::from_slice(&bytes[SIZE_TAG..SIZE_TAG + SIZE_NONCE]);
+        let ciphertext = &bytes[SIZE_TAG + SIZE_NONCE..];
+        let from_parts = EncryptedData::from_parts(tag, nonce, ciphertext).unwrap();
+        assert_eq!(from_parts, encrypted_data);
+
+        let (decrypted_value, decrypted_mask, decrypted_payment_id) =
+            EncryptedData::decrypt_data(&encryption_key, &commitment, &from_parts).unwrap();
+        assert_eq!(decrypted_value, MicroMinotari::from(value));
+        assert_eq!(decrypted_mask, mask);
+        assert_eq!(decrypted_payment_id, payment_id);
+
+        assert!(EncryptedData::from_parts(tag, nonce, &ciphertext[..SIZE_VALUE]).is_err());
+        assert!(EncryptedData::from_parts(tag, nonce, &[0u8; MAX_ENCRYPTED_DATA_SIZE]).is_err());
+    }
+
     #[test]
     fn it_gets_useable_payment_id_data() {
         let payment_id = PaymentId::Empty;
@@ -1272,4 +1912,792 @@ mod test {
             PaymentId::stringify_bytes(&payment_id.user_data_as_bytes())
         );
     }
+
+    #[test]
+    fn it_computes_the_encrypted_data_size_for_payment_ids() {
+        let address = TariAddress::from_base58(
+            "f425UWsDp714RiN53c1G6ek57rfFnotB5NCMyrn4iDgbR8i2sXVHa4xSsedd66o9KmkRgErQnyDdCaAdNLzcKrj7eUb",
+        )
+        .unwrap();
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(1u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        for payment_id in [
+            PaymentId::Empty,
+            PaymentId::U64(156486946518564),
+            PaymentId::U256(U256::from(123456789u64)),
+            PaymentId::TypeOnly(TxType::Burn),
+            PaymentId::open("Hello World!!!", TxType::PaymentToOther),
+            PaymentId::AddressAndData {
+                sender_address: address.clone(),
+                tx_type: TxType::PaymentToSelf,
+                user_data: vec![1; 10],
+            },
+            PaymentId::TransactionInfo {
+                recipient_address: address.clone(),
+                sender_one_sided: true,
+                amount: MicroMinotari::from(123456),
+                fee: MicroMinotari::from(123),
+                weight: 19000,
+                inputs_count: 712,
+                outputs_count: 3,
+                tx_type: TxType::CoinSplit,
+                user_data: vec![1; 10],
+            },
+            // The largest payment id that can be encrypted
+            PaymentId::open(&"a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE), TxType::PaymentToOther),
+        ] {
+            let size = EncryptedData::size_for_payment_id(&payment_id, PaymentIdEncoding::Legacy).unwrap();
+            assert_eq!(size, STATIC_ENCRYPTED_DATA_SIZE_TOTAL + payment_id.serialized_len());
+            let encrypted_data =
+                EncryptedData::encrypt_data(&encryption_key, &commitment, MicroMinotari::from(1), &mask, payment_id)
+                    .unwrap();
+            assert_eq!(encrypted_data.as_bytes().len(), size);
+        }
+
+        let payment_id = PaymentId::open(&"a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE), TxType::PaymentToOther);
+        assert_eq!(
+            EncryptedData::size_for_payment_id(&payment_id, PaymentIdEncoding::Legacy).unwrap(),
+            MAX_ENCRYPTED_DATA_SIZE
+        );
+        let payment_id = PaymentId::open(
+            &"a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE + 1),
+            TxType::PaymentToOther,
+        );
+        match EncryptedData::size_for_payment_id(&payment_id, PaymentIdEncoding::Legacy) {
+            Err(EncryptedDataError::TooLarge { size, max }) => {
+                assert_eq!(size, MAX_ENCRYPTED_DATA_SIZE + 1);
+                assert_eq!(max, MAX_ENCRYPTED_DATA_SIZE);
+            },
+            res => panic!("Expected a too large error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn it_truncates_open_payment_ids_to_the_encryptable_length() {
+        let at_limit = "a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE);
+        let (payment_id, truncated) =
+            PaymentId::open_truncated(&at_limit, TxType::PaymentToOther, PaymentIdEncoding::Legacy);
+        assert!(!truncated);
+        assert_eq!(payment_id, PaymentId::open(&at_limit, TxType::PaymentToOther));
+        assert_eq!(payment_id.get_size(), PaymentId::MAX_SIZE);
+
+        let beyond_limit = "a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE + 10);
+        let (payment_id, truncated) = PaymentId::open_truncated(&beyond_limit, TxType::Burn, PaymentIdEncoding::Legacy);
+        assert!(truncated);
+        assert_eq!(payment_id, PaymentId::open(&at_limit, TxType::Burn));
+
+        // Truncation never splits a multi-byte character
+        let multi_byte = "\u{20ac}".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE);
+        let (payment_id, truncated) =
+            PaymentId::open_truncated(&multi_byte, TxType::PaymentToOther, PaymentIdEncoding::Legacy);
+        assert!(truncated);
+        assert!(payment_id.get_size() <= PaymentId::MAX_SIZE);
+        assert!(std::str::from_utf8(&payment_id.user_data_as_bytes()).is_ok());
+
+        // The truncated payment id can be encrypted, the original cannot
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(1u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let (payment_id, _) =
+            PaymentId::open_truncated(&beyond_limit, TxType::PaymentToOther, PaymentIdEncoding::Legacy);
+        assert!(
+            EncryptedData::encrypt_data(&encryption_key, &commitment, MicroMinotari::from(1), &mask, payment_id)
+                .is_ok()
+        );
+        let payment_id = PaymentId::open(&beyond_limit, TxType::PaymentToOther);
+        assert!(
+            EncryptedData::encrypt_data(&encryption_key, &commitment, MicroMinotari::from(1), &mask, payment_id)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn it_truncates_deserialized_user_data_to_the_encryptable_length() {
+        let mut bytes = TxType::PaymentToOther.as_bytes();
+        bytes.extend_from_slice(&[b'a'; PaymentId::MAX_SIZE + 10]);
+        let payment_id = PaymentId::from_bytes(&bytes);
+        assert_eq!(payment_id.serialized_len(), PaymentId::MAX_SIZE);
+        assert_eq!(
+            payment_id,
+            PaymentId::open(&"a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE), TxType::PaymentToOther)
+        );
+
+        // The deserialized payment id can be encrypted, the over-limit one cannot
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(1u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        assert!(
+            EncryptedData::encrypt_data(&encryption_key, &commitment, MicroMinotari::from(1), &mask, payment_id)
+                .is_ok()
+        );
+        let payment_id = PaymentId::Open {
+            user_data: bytes[1..].to_vec(),
+            tx_type: TxType::PaymentToOther,
+        };
+        assert!(
+            EncryptedData::encrypt_data(&encryption_key, &commitment, MicroMinotari::from(1), &mask, payment_id)
+                .is_err()
+        );
+
+        // Payment ids within the limit are not truncated
+        assert_eq!(PaymentId::from_bytes(&bytes[..PaymentId::MAX_SIZE]).serialized_len(), PaymentId::MAX_SIZE);
+    }
+
+    #[test]
+    fn it_converts_tx_types_into_open_payment_ids() {
+        let payment_id = TxType::Burn.into_open();
+        assert_eq!(payment_id, PaymentId::Open {
+            user_data: vec![],
+            tx_type: TxType::Burn,
+        });
+        // Without user data it serializes like `TypeOnly`, which it is read back as
+        assert_eq!(
+            PaymentId::from_bytes(&payment_id.to_bytes()),
+            payment_id.clone().canonical()
+        );
+        assert_eq!(
+            PaymentId::from_bytes(&payment_id.to_bytes()),
+            PaymentId::TypeOnly(TxType::Burn)
+        );
+
+        let payment_id = TxType::CoinSplit.into_open_with(b"Hello World!!!".to_vec());
+        assert_eq!(payment_id, PaymentId::open("Hello World!!!", TxType::CoinSplit));
+        assert_eq!(PaymentId::from_bytes(&payment_id.to_bytes()), payment_id);
+    }
+
+    #[test]
+    fn it_decodes_existing_single_byte_payment_ids_as_type_only() {
+        for byte in 0..=u8::MAX {
+            let payment_id = PaymentId::from_bytes(&[byte]);
+            let tx_type = TxType::from_u8(byte);
+            assert_eq!(payment_id, PaymentId::TypeOnly(tx_type.clone()));
+            // The type and user data read the same as for the `Open` payment id with that serialized form
+            let open = tx_type.clone().into_open();
+            assert_eq!(payment_id.get_type(), open.get_type());
+            assert_eq!(payment_id.user_data_as_bytes(), open.user_data_as_bytes());
+            assert_eq!(payment_id.to_bytes(), open.to_bytes());
+            assert_eq!(payment_id.to_bytes(), tx_type.as_bytes());
+        }
+    }
+
+    #[test]
+    fn it_predicts_the_serialized_length() {
+        let single = TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap();
+        let dual = TariAddress::from_base58(
+            "f425UWsDp714RiN53c1G6ek57rfFnotB5NCMyrn4iDgbR8i2sXVHa4xSsedd66o9KmkRgErQnyDdCaAdNLzcKrj7eUb",
+        )
+        .unwrap();
+        let mut payment_ids = vec![
+            PaymentId::Empty,
+            PaymentId::U64(0),
+            PaymentId::U64(u64::MAX),
+            PaymentId::U256(U256::MAX),
+        ];
+        for user_data in [vec![], vec![1], vec![1; PaymentId::MAX_OPEN_USER_DATA_SIZE]] {
+            payment_ids.push(PaymentId::Open {
+                user_data: user_data.clone(),
+                tx_type: TxType::Burn,
+            });
+            for address in [&single, &dual] {
+                payment_ids.push(PaymentId::AddressAndData {
+                    sender_address: address.clone(),
+                    tx_type: TxType::PaymentToOther,
+                    user_data: user_data.clone(),
+                });
+                payment_ids.push(PaymentId::TransactionInfo {
+                    recipient_address: address.clone(),
+                    sender_one_sided: true,
+                    amount: MicroMinotari::from(u64::MAX),
+                    // Out-of-bound metadata is zeroed when packed, but still takes up the same space
+                    fee: MicroMinotari::from(u64::MAX),
+                    weight: u64::MAX,
+                    inputs_count: usize::MAX,
+                    outputs_count: usize::MAX,
+                    tx_type: TxType::CoinJoin,
+                    user_data: user_data.clone(),
+                });
+            }
+        }
+
+        for payment_id in payment_ids {
+            assert_eq!(
+                payment_id.serialized_len(),
+                payment_id.to_bytes().len(),
+                "{:?}",
+                payment_id
+            );
+        }
+    }
+
+    #[test]
+    fn it_maps_tx_types_to_output_categories() {
+        let mappings = [
+            (TxType::PaymentToOther, OutputCategory::Payment),
+            (TxType::PaymentToSelf, OutputCategory::Internal),
+            (TxType::Burn, OutputCategory::Burn),
+            (TxType::CoinSplit, OutputCategory::Internal),
+            (TxType::CoinJoin, OutputCategory::Internal),
+            (TxType::ValidatorNodeRegistration, OutputCategory::Registration),
+            (TxType::ClaimAtomicSwap, OutputCategory::AtomicSwap),
+            (TxType::HtlcAtomicSwapRefund, OutputCategory::AtomicSwap),
+            (TxType::CodeTemplateRegistration, OutputCategory::Registration),
+            (TxType::ImportedUtxoNoneRewindable, OutputCategory::Imported),
+        ];
+        // Every transaction type is covered
+        for value in 0..=0b1001u8 {
+            assert!(mappings.iter().any(|(tx_type, _)| tx_type.as_u8() == value));
+        }
+        for (tx_type, category) in mappings {
+            assert_eq!(tx_type.category(), category);
+            assert_eq!(tx_type.into_open().category(), category);
+        }
+        // Payment ids without a transaction type are payments
+        assert_eq!(PaymentId::Empty.category(), OutputCategory::Payment);
+        assert_eq!(PaymentId::U64(1).category(), OutputCategory::Payment);
+    }
+
+    #[test]
+    fn it_reports_metadata_fields_that_overflow() {
+        let payment_id =
+            |fee: u64, weight: u64, inputs_count: usize, outputs_count: usize| PaymentId::TransactionInfo {
+                recipient_address: TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap(),
+                sender_one_sided: false,
+                amount: MicroMinotari::from(1000),
+                fee: MicroMinotari::from(fee),
+                weight,
+                inputs_count,
+                outputs_count,
+                tx_type: TxType::PaymentToOther,
+                user_data: vec![],
+            };
+        let (fee, weight, inputs_count, outputs_count) = (4_294_967_295, 65_535, 32_767, 4_095);
+
+        assert!(payment_id(fee, weight, inputs_count, outputs_count)
+            .metadata_overflows()
+            .is_empty());
+        assert_eq!(
+            payment_id(fee + 1, weight, inputs_count, outputs_count).metadata_overflows(),
+            vec![MetadataField::Fee]
+        );
+        assert_eq!(
+            payment_id(fee, weight + 1, inputs_count, outputs_count).metadata_overflows(),
+            vec![MetadataField::Weight]
+        );
+        assert_eq!(
+            payment_id(fee, weight, inputs_count + 1, outputs_count).metadata_overflows(),
+            vec![MetadataField::InputsCount]
+        );
+        assert_eq!(
+            payment_id(fee, weight, inputs_count, outputs_count + 1).metadata_overflows(),
+            vec![MetadataField::OutputsCount]
+        );
+        assert_eq!(
+            payment_id(fee + 1, weight + 1, inputs_count + 1, outputs_count + 1).metadata_overflows(),
+            vec![
+                MetadataField::Fee,
+                MetadataField::Weight,
+                MetadataField::InputsCount,
+                MetadataField::OutputsCount
+            ]
+        );
+
+        // Only transaction info carries metadata
+        assert!(PaymentId::U64(u64::MAX).metadata_overflows().is_empty());
+    }
+
+    #[test]
+    fn it_diffs_transaction_info_that_overflowed_metadata() {
+        let payment_id = PaymentId::TransactionInfo {
+            recipient_address: TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap(),
+            sender_one_sided: false,
+            amount: MicroMinotari::from(1000),
+            fee: MicroMinotari::from(25),
+            weight: 65_535 + 1,
+            inputs_count: 2,
+            outputs_count: 4_095 + 1,
+            tx_type: TxType::PaymentToOther,
+            user_data: "round trip".as_bytes().to_vec(),
+        };
+        let round_tripped = PaymentId::from_bytes(&payment_id.to_bytes());
+
+        assert!(payment_id.diff(&payment_id).is_empty());
+        assert_eq!(payment_id.diff(&round_tripped), vec![
+            FieldDiff {
+                field: "weight",
+                old: "65536".to_string(),
+                new: "0".to_string(),
+            },
+            FieldDiff {
+                field: "outputs_count",
+                old: "4096".to_string(),
+                new: "0".to_string(),
+            },
+        ]);
+
+        let diffs = payment_id.diff(&PaymentId::U64(1));
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].field, "variant");
+        assert_eq!(diffs[0].new, "u64(1)");
+    }
+
+    #[test]
+    fn it_wipes_the_encrypted_data() {
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(123u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let payment_id = PaymentId::open("wipe me", TxType::PaymentToOther);
+        let mut encrypted_data =
+            EncryptedData::encrypt_data(&encryption_key, &commitment, 123.into(), &mask, payment_id).unwrap();
+        assert!(encrypted_data.as_bytes().iter().any(|b| *b != 0));
+
+        encrypted_data.wipe();
+        assert_eq!(encrypted_data.as_bytes().len(), STATIC_ENCRYPTED_DATA_SIZE_TOTAL);
+        assert!(encrypted_data.as_bytes().iter().all(|b| *b == 0));
+        assert_eq!(encrypted_data, EncryptedData::default());
+        assert!(EncryptedData::decrypt_data(&encryption_key, &commitment, &encrypted_data).is_err());
+    }
+
+    #[test]
+    fn it_detects_the_default_placeholder() {
+        assert!(EncryptedData::default().is_default_placeholder());
+
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(123u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let mut encrypted_data =
+            EncryptedData::encrypt_data(&encryption_key, &commitment, 123.into(), &mask, PaymentId::Empty).unwrap();
+        assert!(!encrypted_data.is_default_placeholder());
+
+        encrypted_data.wipe();
+        assert!(encrypted_data.is_default_placeholder());
+    }
+
+    #[test]
+    fn it_validates_the_network_of_the_embedded_address() {
+        let address = |network| TariAddress::new_single_address_with_interactive_only(Default::default(), network);
+        let sender = PaymentId::AddressAndData {
+            sender_address: address(Network::MainNet),
+            tx_type: TxType::PaymentToOther,
+            user_data: vec![1, 2, 3],
+        };
+        assert_eq!(sender.address_network(), Some(Network::MainNet));
+        assert!(sender.validate_address_network(Network::MainNet).is_ok());
+        assert_eq!(
+            sender.validate_address_network(Network::Esmeralda),
+            Err(PaymentIdError::AddressNetworkMismatch {
+                expected: Network::Esmeralda,
+                network: Network::MainNet
+            })
+        );
+
+        let recipient = PaymentId::TransactionInfo {
+            recipient_address: address(Network::Esmeralda),
+            amount: MicroMinotari::from(1),
+            fee: MicroMinotari::from(1),
+            weight: 1,
+            inputs_count: 1,
+            outputs_count: 1,
+            sender_one_sided: false,
+            tx_type: TxType::PaymentToOther,
+            user_data: vec![],
+        };
+        assert_eq!(recipient.address_network(), Some(Network::Esmeralda));
+        assert!(recipient.validate_address_network(Network::Esmeralda).is_ok());
+        assert_eq!(
+            recipient.validate_address_network(Network::MainNet),
+            Err(PaymentIdError::AddressNetworkMismatch {
+                expected: Network::MainNet,
+                network: Network::Esmeralda
+            })
+        );
+
+        // Payment ids without an address are always valid
+        assert_eq!(PaymentId::U64(1).address_network(), None);
+        assert!(PaymentId::Empty.validate_address_network(Network::MainNet).is_ok());
+    }
+
+    #[test]
+    fn it_only_embeds_a_sender_address_for_the_expected_network() {
+        let address = |network| TariAddress::new_single_address_with_interactive_only(Default::default(), network);
+        let payment_id = TxType::PaymentToOther.into_open_with(vec![1, 2, 3]);
+
+        let embedded =
+            PaymentId::with_validated_sender_address(payment_id.clone(), address(Network::MainNet), Network::MainNet)
+                .unwrap();
+        assert_eq!(
+            embedded,
+            PaymentId::add_sender_address(payment_id.clone(), address(Network::MainNet), None)
+        );
+        assert!(embedded.validate_address_network(Network::MainNet).is_ok());
+
+        assert_eq!(
+            PaymentId::with_validated_sender_address(payment_id, address(Network::Esmeralda), Network::MainNet),
+            Err(PaymentIdError::AddressNetworkMismatch {
+                expected: Network::MainNet,
+                network: Network::Esmeralda
+            })
+        );
+    }
+
+    #[test]
+    fn it_encodes_a_type_only_payment_id_in_a_single_byte() {
+        for tx_type in [
+            TxType::PaymentToOther,
+            TxType::PaymentToSelf,
+            TxType::Burn,
+            TxType::CoinSplit,
+        ] {
+            let payment_id = PaymentId::TypeOnly(tx_type.clone());
+            let bytes = payment_id.to_bytes();
+            assert_eq!(bytes, vec![tx_type.as_u8()]);
+            assert_eq!(payment_id.serialized_len(), 1);
+            assert_eq!(PaymentId::from_bytes(&bytes), payment_id);
+            assert_eq!(payment_id.get_type(), tx_type);
+
+            // An open payment id without user data has the same encoding
+            assert_eq!(
+                PaymentId::Open {
+                    user_data: vec![],
+                    tx_type: tx_type.clone()
+                }
+                .to_bytes(),
+                bytes
+            );
+        }
+
+        let payment_id = PaymentId::TypeOnly(TxType::PaymentToSelf);
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(123));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        let encrypted_data = EncryptedData::encrypt_data(
+            &encryption_key,
+            &commitment,
+            MicroMinotari::from(123),
+            &mask,
+            payment_id.clone(),
+        )
+        .unwrap();
+        let (_, _, decrypted_payment_id) =
+            EncryptedData::decrypt_data(&encryption_key, &commitment, &encrypted_data).unwrap();
+        assert_eq!(decrypted_payment_id, payment_id);
+    }
+
+    #[test]
+    fn it_keeps_the_serde_variant_indexes_of_stored_payment_ids() {
+        // Pending transactions are stored bincode-serialized by the wallet, so the variant indexes must not change
+        let address = TariAddress::from_base58(
+            "f425UWsDp714RiN53c1G6ek57rfFnotB5NCMyrn4iDgbR8i2sXVHa4xSsedd66o9KmkRgErQnyDdCaAdNLzcKrj7eUb",
+        )
+        .unwrap();
+        let payment_ids = [
+            (0u32, PaymentId::Empty),
+            (1, PaymentId::U64(12345)),
+            (2, PaymentId::U256(U256::from(67890u64))),
+            (3, PaymentId::open("Hello World!!!", TxType::PaymentToOther)),
+            (4, PaymentId::AddressAndData {
+                sender_address: address.clone(),
+                tx_type: TxType::PaymentToSelf,
+                user_data: vec![1; 10],
+            }),
+            (5, PaymentId::TransactionInfo {
+                recipient_address: address,
+                sender_one_sided: false,
+                amount: MicroMinotari::from(123456),
+                fee: MicroMinotari::from(123),
+                weight: 19000,
+                inputs_count: 2,
+                outputs_count: 3,
+                tx_type: TxType::CoinSplit,
+                user_data: vec![1; 10],
+            }),
+            (6, PaymentId::TypeOnly(TxType::Burn)),
+        ];
+        for (index, payment_id) in payment_ids {
+            let bytes = bincode::serialize(&payment_id).unwrap();
+            assert_eq!(bytes[..4], index.to_le_bytes(), "{:?}", payment_id);
+            assert_eq!(bincode::deserialize::<PaymentId>(&bytes).unwrap(), payment_id);
+        }
+    }
+
+    #[test]
+    fn it_still_reads_empty_payment_ids() {
+        assert_eq!(PaymentId::from_bytes(&[]), PaymentId::Empty);
+        assert!(PaymentId::Empty.to_bytes().is_empty());
+        assert_eq!(PaymentId::Empty.get_type(), TxType::default());
+    }
+
+    #[test]
+    fn it_preserves_unknown_trailing_bytes() {
+        let payment_id = PaymentId::TransactionInfo {
+            recipient_address: TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap(),
+            sender_one_sided: false,
+            amount: MicroMinotari::from(123456),
+            fee: MicroMinotari::from(123),
+            weight: 19000,
+            inputs_count: 712,
+            outputs_count: 3,
+            tx_type: TxType::PaymentToSelf,
+            user_data: vec![1, 2, 3],
+        };
+        let mut payload = payment_id.to_bytes();
+        payload.extend_from_slice(&[0xfe, 0xed, 0xfa, 0xce]);
+        let preserved = PaymentId::from_bytes_preserving_unknown(&payload);
+        assert_eq!(preserved.to_bytes(), payload);
+        assert_eq!(preserved.payment_id().get_type(), TxType::PaymentToSelf);
+
+        // A future flag in the high bits of the tx type is dropped by a plain parse, but preserved here
+        let payload = [0b0010_0001, 7, 8, 9];
+        assert_ne!(PaymentId::from_bytes(&payload).to_bytes(), payload.to_vec());
+        let preserved = PaymentId::from_bytes_preserving_unknown(&payload);
+        assert_eq!(preserved.payment_id(), &PaymentId::Open {
+            user_data: vec![7, 8, 9],
+            tx_type: TxType::PaymentToSelf
+        });
+        assert_eq!(preserved.unknown(), &payload[..]);
+        assert_eq!(preserved.to_bytes(), payload.to_vec());
+
+        // V1 payloads are re-serialized with their header
+        let mut payload = payment_id.to_bytes_with(PaymentIdEncoding::V1);
+        payload.extend_from_slice(&[0xfe, 0xed, 0xfa, 0xce]);
+        let preserved = PaymentId::from_bytes_preserving_unknown(&payload);
+        assert_eq!(preserved.encoding(), PaymentIdEncoding::V1);
+        assert_eq!(preserved.to_bytes(), payload);
+
+        let mut payload = PaymentIdEncoding::V1_PREFIX.to_vec();
+        payload.extend_from_slice(&[4, 0b0010_0001, 7, 8, 9]);
+        let preserved = PaymentId::from_bytes_preserving_unknown(&payload);
+        assert_eq!(preserved.encoding(), PaymentIdEncoding::V1);
+        assert_eq!(preserved.payment_id(), &PaymentId::Open {
+            user_data: vec![7, 8, 9],
+            tx_type: TxType::PaymentToSelf
+        });
+        assert_eq!(preserved.unknown(), &payload[PaymentIdEncoding::V1_HEADER_SIZE..]);
+        assert_eq!(preserved.to_bytes(), payload);
+
+        // Known payloads have no unknown bytes
+        let payload = PaymentId::U64(42).to_bytes();
+        let preserved = PaymentId::from_bytes_preserving_unknown(&payload);
+        assert!(preserved.unknown().is_empty());
+        assert_eq!(preserved.into_payment_id(), PaymentId::U64(42));
+    }
+
+    #[test]
+    fn it_canonicalizes_equivalent_payment_ids() {
+        // Payment ids without data and with the default transaction type are all equivalent to `Empty`
+        for payment_id in [
+            PaymentId::Empty,
+            PaymentId::TypeOnly(TxType::default()),
+            TxType::default().into_open(),
+            PaymentId::from_bytes(&TxType::default().into_open().to_bytes()),
+        ] {
+            assert_eq!(payment_id.canonical(), PaymentId::Empty);
+        }
+
+        // The transaction type is retained
+        assert_eq!(TxType::Burn.into_open().canonical(), PaymentId::TypeOnly(TxType::Burn));
+        assert_ne!(TxType::Burn.into_open().canonical(), PaymentId::Empty);
+
+        // Payment ids that carry data are unchanged
+        for payment_id in [
+            PaymentId::U64(0),
+            PaymentId::U256(U256::zero()),
+            TxType::default().into_open_with(vec![1, 2, 3]),
+            PaymentId::AddressAndData {
+                sender_address: TariAddress::default(),
+                tx_type: TxType::default(),
+                user_data: vec![],
+            },
+        ] {
+            assert_eq!(payment_id.clone().canonical(), payment_id);
+        }
+    }
+
+    #[test]
+    fn it_round_trips_payment_ids_in_both_encodings() {
+        let address = TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap();
+        let payment_ids = [
+            PaymentId::Empty,
+            PaymentId::U64(0xdead_beef),
+            PaymentId::U256(U256::from(1234567890u64)),
+            PaymentId::TypeOnly(TxType::Burn),
+            TxType::CoinSplit.into_open_with(b"hello".to_vec()),
+            PaymentId::AddressAndData {
+                sender_address: address.clone(),
+                tx_type: TxType::PaymentToOther,
+                user_data: b"data".to_vec(),
+            },
+            PaymentId::TransactionInfo {
+                recipient_address: address,
+                sender_one_sided: true,
+                amount: MicroMinotari::from(123456),
+                fee: MicroMinotari::from(123),
+                weight: 19000,
+                inputs_count: 712,
+                outputs_count: 3,
+                tx_type: TxType::PaymentToSelf,
+                user_data: vec![1, 2, 3],
+            },
+        ];
+        for payment_id in payment_ids {
+            for encoding in [PaymentIdEncoding::Legacy, PaymentIdEncoding::V1] {
+                let bytes = payment_id.to_bytes_with(encoding);
+                assert_eq!(bytes.len(), payment_id.serialized_len_with(encoding));
+                assert_eq!(PaymentIdEncoding::detect(&bytes), encoding, "{}", payment_id);
+                assert_eq!(PaymentId::from_bytes(&bytes), payment_id);
+            }
+        }
+        assert_eq!(
+            PaymentId::U64(7).to_bytes_with(PaymentIdEncoding::Legacy),
+            PaymentId::U64(7).to_bytes()
+        );
+
+        // Unlike the legacy encoding, V1 keeps an open payment id without user data distinct from `TypeOnly`
+        let payment_id = TxType::Burn.into_open();
+        let bytes = payment_id.to_bytes_with(PaymentIdEncoding::V1);
+        assert_eq!(PaymentId::from_bytes(&bytes), payment_id);
+    }
+
+    #[test]
+    fn it_constructs_payment_ids_from_fields_like_from_bytes() {
+        let address = TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap();
+        let payment_id = PaymentId::address_and_data(
+            address.clone(),
+            TxType::PaymentToOther,
+            b"data".to_vec(),
+            PaymentIdEncoding::Legacy,
+        )
+        .unwrap();
+        assert_eq!(payment_id, PaymentId::from_bytes(&payment_id.to_bytes()));
+
+        let fields = TransactionInfoFields {
+            recipient_address: address.clone(),
+            sender_one_sided: true,
+            amount: MicroMinotari::from(123456),
+            fee: MicroMinotari::from(123),
+            weight: 19000,
+            inputs_count: 712,
+            outputs_count: 3,
+            tx_type: TxType::PaymentToSelf,
+            user_data: vec![1, 2, 3],
+        };
+        let payment_id = PaymentId::transaction_info(fields.clone(), PaymentIdEncoding::Legacy).unwrap();
+        assert_eq!(payment_id, PaymentId::from_bytes(&payment_id.to_bytes()));
+
+        // Oversized user data is rejected rather than failing later when encrypting
+        let max = PaymentId::MAX_SIZE;
+        let err = PaymentId::address_and_data(
+            address.clone(),
+            TxType::default(),
+            vec![0; max],
+            PaymentIdEncoding::Legacy,
+        )
+        .unwrap_err();
+        assert_eq!(err, PaymentIdError::TooLarge {
+            size: address.get_size() + 1 + max,
+            max
+        });
+        let err = PaymentId::transaction_info(
+            TransactionInfoFields {
+                user_data: vec![0; max],
+                ..fields.clone()
+            },
+            PaymentIdEncoding::Legacy,
+        )
+        .unwrap_err();
+        assert!(matches!(err, PaymentIdError::TooLarge { .. }));
+
+        // Metadata that would be zeroed when serialized is rejected
+        let err = PaymentId::transaction_info(
+            TransactionInfoFields {
+                weight: PaymentId::MAX_META_DATA_WEIGHT + 1,
+                outputs_count: PaymentId::MAX_META_DATA_OUTPUTS_COUNT + 1,
+                ..fields
+            },
+            PaymentIdEncoding::Legacy,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            PaymentIdError::MetadataOverflow(vec![MetadataField::Weight, MetadataField::OutputsCount])
+        );
+    }
+
+    #[test]
+    fn it_checks_the_size_of_payment_ids_in_the_given_encoding() {
+        let encoding = PaymentIdEncoding::V1;
+        let max_len = PaymentId::max_open_user_data_size(encoding);
+        assert_eq!(max_len, PaymentId::MAX_OPEN_USER_DATA_SIZE - 4);
+
+        // A payment id that fits in the legacy encoding may not fit in V1, which adds a header
+        let payment_id = PaymentId::open(&"a".repeat(PaymentId::MAX_OPEN_USER_DATA_SIZE), TxType::PaymentToOther);
+        assert!(EncryptedData::size_for_payment_id(&payment_id, PaymentIdEncoding::Legacy).is_ok());
+        assert!(matches!(
+            EncryptedData::size_for_payment_id(&payment_id, encoding),
+            Err(EncryptedDataError::TooLarge { .. })
+        ));
+        let mask = PrivateKey::random(&mut OsRng);
+        let commitment = CommitmentFactory::default().commit(&mask, &PrivateKey::from(1u64));
+        let encryption_key = PrivateKey::random(&mut OsRng);
+        assert!(EncryptedData::encrypt_data_with_encoding(
+            &encryption_key,
+            &commitment,
+            MicroMinotari::from(1),
+            &mask,
+            payment_id.clone(),
+            encoding
+        )
+        .is_err());
+
+        // Truncation and validation leave room for the header
+        let (truncated, _) = PaymentId::open_truncated(&"a".repeat(PaymentId::MAX_SIZE), TxType::Burn, encoding);
+        assert_eq!(truncated.serialized_len_with(encoding), PaymentId::MAX_SIZE);
+        let encrypted = EncryptedData::encrypt_data_with_encoding(
+            &encryption_key,
+            &commitment,
+            MicroMinotari::from(1),
+            &mask,
+            truncated.clone(),
+            encoding,
+        )
+        .unwrap();
+        assert_eq!(encrypted.as_bytes().len(), MAX_ENCRYPTED_DATA_SIZE);
+        let (_, _, decrypted) = EncryptedData::decrypt_data(&encryption_key, &commitment, &encrypted).unwrap();
+        assert_eq!(decrypted, truncated);
+        let address = TariAddress::from_base58("f3S7XTiyKQauZpDUjdR8NbcQ33MYJigiWiS44ccZCxwAAjk").unwrap();
+        let user_data = vec![0; PaymentId::MAX_SIZE - address.get_size() - 1];
+        assert!(PaymentId::address_and_data(
+            address.clone(),
+            TxType::default(),
+            user_data.clone(),
+            PaymentIdEncoding::Legacy
+        )
+        .is_ok());
+        assert!(PaymentId::address_and_data(address, TxType::default(), user_data, encoding).is_err());
+
+        // Oversized V1 bytes are truncated to what can be re-encrypted in V1
+        let mut bytes =
+            PaymentId::open(&"a".repeat(PaymentId::MAX_SIZE), TxType::PaymentToOther).to_bytes_with(encoding);
+        bytes.truncate(PaymentId::MAX_SIZE + 10);
+        assert_eq!(PaymentId::from_bytes(&bytes).serialized_len_with(encoding), PaymentId::MAX_SIZE);
+    }
+
+    #[test]
+    fn it_detects_v1_payment_ids_among_legacy_ones() {
+        let v1 = TxType::PaymentToSelf
+            .into_open_with(vec![1, 2, 3, 4])
+            .to_bytes_with(PaymentIdEncoding::V1);
+        assert_eq!(&v1[..3], &[0xff, b'P', 1]);
+        assert_eq!(PaymentIdEncoding::detect(&v1), PaymentIdEncoding::V1);
+        // A legacy decoder reads the same bytes as a different payment id
+        assert_ne!(PaymentId::from_legacy_bytes(&v1), PaymentId::from_bytes(&v1));
+
+        // Legacy bytes that happen to start with the V1 prefix, but do not decode as V1, are still read as legacy
+        let bytes = [0xff, b'P', 1, 9, 0, 0, 0, 0];
+        assert_eq!(PaymentIdEncoding::detect(&bytes), PaymentIdEncoding::Legacy);
+        assert_eq!(PaymentId::from_bytes(&bytes), PaymentId::U64(u64::from_le_bytes(bytes)));
+        for bytes in [vec![], vec![1], vec![0xff, b'P'], vec![0xff, b'P', 1, 4]] {
+            assert_eq!(PaymentIdEncoding::detect(&bytes), PaymentIdEncoding::Legacy);
+        }
+    }
 }
@@ -26,7 +26,17 @@
 use blake2::Blake2b;
 use chacha20poly1305::Key;
 use digest::consts::U32;
-pub use encrypted_data::{EncryptedData, EncryptedDataError};
+pub use encrypted_data::{
+    EncryptedData,
+    EncryptedDataError,
+    FieldDiff,
+    MetadataField,
+    OutputCategory,
+    PaymentIdEncoding,
+    PaymentIdError,
+    PreservedPaymentId,
+    TransactionInfoFields,
+};
 pub use error::TransactionError;
 pub use kernel_builder::KernelBuilder;
 pub use kernel_features::KernelFeatures;
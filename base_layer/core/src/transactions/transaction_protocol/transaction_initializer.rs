@@ -431,7 +431,9 @@ where KM: TransactionKeyManagerInterface
                             inputs_count: num_inputs,
                             outputs_count: num_outputs + 1,
                             tx_type: if let Some(
-                                PaymentId::Open { tx_type, .. } | PaymentId::AddressAndData { tx_type, .. },
+                                PaymentId::TypeOnly(tx_type) |
+                                PaymentId::Open { tx_type, .. } |
+                                PaymentId::AddressAndData { tx_type, .. },
                             ) = self.payment_id.clone()
                             {
                                 tx_type
@@ -127,6 +127,11 @@ pub struct P2pConfig {
     /// If Some, enables periodic socket-level liveness checks
     #[serde(with = "serializers::optional_seconds")]
     pub listener_self_liveness_check_interval: Option<Duration>,
+    /// If Some, TCP keepalive probes are sent on connections that have been idle for this interval so that dead
+    /// connections are detected sooner. Only applies to the TCP transport and is ignored for the Tor, SOCKS5 and
+    /// memory transports.
+    #[serde(with = "serializers::optional_seconds")]
+    pub keepalive_probe_interval: Option<Duration>,
     /// CIDR for addresses allowed to enter into liveness check mode on the listener.
     pub listener_liveness_allowlist_cidrs: StringList,
     /// The address to bind on using the TCP transport _in addition to_ the primary transport. This is typically useful
@@ -163,6 +168,7 @@ impl Default for P2pConfig {
             allow_test_addresses: false,
             listener_liveness_max_sessions: 0,
             listener_self_liveness_check_interval: None,
+            keepalive_probe_interval: None,
             listener_liveness_allowlist_cidrs: StringList::default(),
             auxiliary_tcp_listener_address: None,
             rpc_max_simultaneous_sessions: 100,
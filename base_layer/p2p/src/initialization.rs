@@ -224,6 +224,17 @@ pub async fn spawn_comms_using_transport<F: Fn(TorIdentity) + Send + Sync + Unpi
     transport_config: TransportConfig,
     after_comms: F,
 ) -> Result<CommsNode, CommsInitializationError> {
+    if let Some(interval) = comms.keepalive_probe_interval() {
+        if transport_config.transport_type != TransportType::Tcp {
+            warn!(
+                target: LOG_TARGET,
+                "keepalive_probe_interval ({:.0?}) is only supported by the TCP transport and is ignored for the {:?} \
+                 transport",
+                interval,
+                transport_config.transport_type
+            );
+        }
+    }
     let comms = match transport_config.transport_type {
         TransportType::Memory => {
             debug!(target: LOG_TARGET, "Building in-memory comms stack");
@@ -244,6 +255,9 @@ pub async fn spawn_comms_using_transport<F: Fn(TorIdentity) + Send + Sync + Unpi
                     .unwrap_or("")
             );
             let mut transport = TcpWithTorTransport::new();
+            if let Some(interval) = comms.keepalive_probe_interval() {
+                transport.tcp_transport_mut().set_keepalive(interval);
+            }
             if let Some(addr) = config.tor_socks_address {
                 transport.set_tor_socks_proxy(SocksConfig {
                     proxy_address: addr,
@@ -599,7 +613,8 @@ impl ServiceInitializer for P2pInitializer {
             } else {
                 None
             })
-            .set_self_liveness_check(config.listener_self_liveness_check_interval);
+            .set_self_liveness_check(config.listener_self_liveness_check_interval)
+            .with_keepalive_probe_interval(config.keepalive_probe_interval);
 
         if config.allow_test_addresses || config.dht.peer_validator_config.allow_test_addresses {
             // The default is false, so ensure that both settings are true in this case
@@ -20,11 +20,15 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use tari_comms::peer_manager::NodeId;
 use tari_service_framework::reply_channel::SenderService;
-use tokio::sync::broadcast;
+use tokio::{
+    sync::{broadcast, broadcast::error::RecvError},
+    time,
+    time::Instant,
+};
 use tower::Service;
 
 use super::{error::LivenessError, state::Metadata};
@@ -142,6 +146,52 @@ impl LivenessHandle {
         }
     }
 
+    /// Send pings to a list of peers and wait up to `timeout` for their pongs. Returns the round-trip-time for each
+    /// peer, or `None` if the peer did not respond in time. Pongs that are missed because the event stream lagged are
+    /// reported as timed out, so callers that need to tell those apart, follow the other liveness events, or wait
+    /// on a shutdown signal should send the pings and read the event stream themselves.
+    pub async fn ping_and_collect(
+        &mut self,
+        node_ids: Vec<NodeId>,
+        timeout: Duration,
+    ) -> Result<HashMap<NodeId, Option<Duration>>, LivenessError> {
+        // Subscribe before sending so that no pong can be missed
+        let mut liveness_events = self.get_event_stream();
+        let sent_at = Instant::now();
+        let nonces = self.send_pings(node_ids.clone()).await?;
+        let mut results = node_ids
+            .iter()
+            .map(|node_id| (node_id.clone(), None))
+            .collect::<HashMap<_, _>>();
+        let mut pending = nonces.into_iter().zip(node_ids).collect::<HashMap<_, _>>();
+
+        let timeout_timer = time::sleep(timeout);
+        tokio::pin!(timeout_timer);
+        while !pending.is_empty() {
+            tokio::select! {
+                event = liveness_events.recv() => {
+                    match event {
+                        Ok(event) => {
+                            if let LivenessEvent::ReceivedPong(pong) = &*event {
+                                if pending.get(&pong.nonce) == Some(&pong.node_id) {
+                                    pending.remove(&pong.nonce);
+                                    let latency = pong.latency.unwrap_or_else(|| sent_at.elapsed());
+                                    results.insert(pong.node_id.clone(), Some(latency));
+                                }
+                            }
+                        },
+                        // Missed pongs are reported as timed out
+                        Err(RecvError::Lagged(_)) => {},
+                        Err(RecvError::Closed) => break,
+                    }
+                },
+                _ = &mut timeout_timer => break,
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Retrieve the global ping count
     pub async fn get_ping_count(&mut self) -> Result<usize, LivenessError> {
         match self.handle.call(LivenessRequest::GetPingCount).await?? {
@@ -206,3 +256,57 @@ impl LivenessHandle {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use futures::StreamExt;
+    use rand::rngs::OsRng;
+    use tari_comms::types::CommsPublicKey;
+    use tari_crypto::keys::PublicKey;
+    use tari_service_framework::reply_channel;
+    use tokio::task;
+
+    use super::*;
+    use crate::services::liveness::Metadata;
+
+    fn random_node_id() -> NodeId {
+        NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1)
+    }
+
+    #[tokio::test]
+    async fn it_collects_the_round_trip_time_of_each_peer() {
+        let (sender, mut requests) = reply_channel::unbounded();
+        let (event_publisher, _) = broadcast::channel(10);
+        let mut liveness = LivenessHandle::new(sender, event_publisher.clone());
+        let node_ids = (0..3).map(|_| random_node_id()).collect::<Vec<_>>();
+
+        // The liveness backend receives pongs from the first and last peers only
+        let responding = vec![(node_ids[0].clone(), 10), (node_ids[2].clone(), 12)];
+        task::spawn(async move {
+            let (request, reply) = requests.next().await.unwrap().split();
+            assert!(matches!(request, LivenessRequest::SendPings(ref ids) if ids.len() == 3));
+            reply.send(Ok(LivenessResponse::Ok(Some(vec![10, 11, 12])))).unwrap();
+            for (node_id, nonce) in responding {
+                let pong = PingPongEvent::new(node_id, Some(Duration::from_millis(nonce)), Metadata::new(), nonce);
+                event_publisher
+                    .send(Arc::new(LivenessEvent::ReceivedPong(Box::new(pong))))
+                    .unwrap();
+            }
+            // A pong with an unexpected nonce is ignored
+            let pong = PingPongEvent::new(random_node_id(), None, Metadata::new(), 99);
+            event_publisher
+                .send(Arc::new(LivenessEvent::ReceivedPong(Box::new(pong))))
+                .unwrap();
+        });
+
+        let results = liveness
+            .ping_and_collect(node_ids.clone(), Duration::from_millis(200))
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[&node_ids[0]], Some(Duration::from_millis(10)));
+        assert_eq!(results[&node_ids[1]], None);
+        assert_eq!(results[&node_ids[2]], Some(Duration::from_millis(12)));
+    }
+}
@@ -43,6 +43,7 @@ pub mod error;
 mod handle;
 pub use handle::{
     LivenessEvent,
+    LivenessEventReceiver,
     LivenessEventSender,
     LivenessHandle,
     LivenessRequest,
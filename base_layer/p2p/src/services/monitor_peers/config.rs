@@ -0,0 +1,215 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+/// Configuration for the peer monitoring service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MonitorPeersConfig {
+    /// The maximum number of peers to keep liveness stats for. When exceeded, the least recently updated peers are
+    /// evicted. (Default: 1000)
+    pub max_tracked_peers: usize,
+    /// When true, unresponsive peers that would be culled are only logged and not disconnected. (Default: false)
+    pub dry_run: bool,
+    /// When set, culled peers are re-dialed once after this cooldown to check whether they have recovered, before
+    /// being forgotten. (Default: None)
+    #[serde(with = "serializers::optional_seconds")]
+    pub redial_after: Option<Duration>,
+    /// The cull score added for each round since its last response in which a peer was connected but did not respond
+    /// to a ping. This is a stronger signal of a bad peer than not being connected. Must be greater than zero.
+    /// (Default: 3)
+    pub unresponsive_weight: u32,
+    /// The cull score added for each round since its last response in which a previously seen peer was not connected,
+    /// i.e. such a round counts as `disconnected_weight / unresponsive_weight` of an unresponsive round towards the
+    /// cull policy. (Default: 1)
+    pub disconnected_weight: u32,
+    /// When true, the monitored outbound peers are disconnected when the service shuts down rather than being left
+    /// to be cleaned up elsewhere. (Default: false)
+    pub disconnect_monitored_on_shutdown: bool,
+    /// When set, a peer that returns a pong carrying the nonce of a ping sent to another peer is banned for this
+    /// duration. Otherwise the echoed pong is only logged and ignored. (Default: None)
+    #[serde(with = "serializers::optional_seconds")]
+    pub nonce_echo_ban_duration: Option<Duration>,
+    /// The network is reported as unhealthy when the fraction of monitored peers that responded in a round is below
+    /// this ratio, or when there were no peers to monitor. Must be between 0 and 1. (Default: 0.5)
+    pub min_responsive_ratio: f64,
+    /// When true, inbound node connections are monitored and culled as well as outbound ones, e.g. so that seed nodes
+    /// free the resources of dead inbound peers. (Default: false)
+    pub monitor_inbound: bool,
+    /// Inbound peers may be behind NAT, so they are only culled once they failed to respond in this many consecutive
+    /// rounds, rather than by their cull score. Must be between 1 and the cull policy window. (Default: 5)
+    pub inbound_unresponsive_rounds: usize,
+}
+
+impl Default for MonitorPeersConfig {
+    fn default() -> Self {
+        Self {
+            max_tracked_peers: 1000,
+            dry_run: false,
+            redial_after: None,
+            unresponsive_weight: 3,
+            disconnected_weight: 1,
+            disconnect_monitored_on_shutdown: false,
+            nonce_echo_ban_duration: None,
+            min_responsive_ratio: 0.5,
+            monitor_inbound: false,
+            inbound_unresponsive_rounds: 5,
+        }
+    }
+}
+
+impl MonitorPeersConfig {
+    /// Checks that the configured ratio, weights and round counts can be used with the given cull policy
+    pub fn validate(&self, cull_policy: CullPolicy) -> Result<(), anyhow::Error> {
+        cull_policy.validate()?;
+        if !(0.0..=1.0).contains(&self.min_responsive_ratio) {
+            return Err(anyhow!(
+                "Monitor peers min_responsive_ratio ({}) must be between 0 and 1",
+                self.min_responsive_ratio
+            ));
+        }
+        if self.unresponsive_weight == 0 {
+            return Err(anyhow!("Monitor peers unresponsive_weight must be greater than zero"));
+        }
+        if self.inbound_unresponsive_rounds == 0 || self.inbound_unresponsive_rounds > cull_policy.window {
+            return Err(anyhow!(
+                "Monitor peers inbound_unresponsive_rounds ({}) must be between 1 and the cull policy window ({})",
+                self.inbound_unresponsive_rounds,
+                cull_policy.window
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The liveness history kept per peer and the number of consecutive failed rounds before a peer is culled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CullPolicy {
+    /// The number of most recent rounds of liveness stats kept per peer. Must be greater than zero. (Default: 7)
+    pub window: usize,
+    /// Outbound peers are culled once the rounds since their last response add up to this many unresponsive rounds,
+    /// i.e. once their cull score reaches `consecutive_failures * unresponsive_weight`. Rounds in which the peer was
+    /// not connected count for less, as configured by `disconnected_weight`. Must be between 1 and `window`.
+    /// (Default: 3)
+    pub consecutive_failures: usize,
+}
+
+impl CullPolicy {
+    /// Checks that the consecutive failures fit into the window of liveness stats
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.window == 0 {
+            return Err(anyhow!("Cull policy window must be greater than zero"));
+        }
+        if self.consecutive_failures == 0 || self.consecutive_failures > self.window {
+            return Err(anyhow!(
+                "Cull policy consecutive_failures ({}) must be between 1 and the window ({})",
+                self.consecutive_failures,
+                self.window
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for CullPolicy {
+    fn default() -> Self {
+        Self {
+            window: 7,
+            consecutive_failures: 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_validates_the_config_and_cull_policy() {
+        let config = MonitorPeersConfig::default();
+        assert!(config.validate(CullPolicy::default()).is_ok());
+
+        for cull_policy in [
+            CullPolicy {
+                window: 0,
+                consecutive_failures: 0,
+            },
+            CullPolicy {
+                window: 7,
+                consecutive_failures: 0,
+            },
+            CullPolicy {
+                window: 3,
+                consecutive_failures: 4,
+            },
+        ] {
+            assert!(cull_policy.validate().is_err());
+            assert!(config.validate(cull_policy).is_err());
+        }
+
+        for config in [
+            MonitorPeersConfig {
+                unresponsive_weight: 0,
+                ..Default::default()
+            },
+            MonitorPeersConfig {
+                inbound_unresponsive_rounds: 0,
+                ..Default::default()
+            },
+            MonitorPeersConfig {
+                inbound_unresponsive_rounds: 8,
+                ..Default::default()
+            },
+            MonitorPeersConfig {
+                min_responsive_ratio: 1.5,
+                ..Default::default()
+            },
+            MonitorPeersConfig {
+                min_responsive_ratio: f64::NAN,
+                ..Default::default()
+            },
+        ] {
+            assert!(config.validate(CullPolicy::default()).is_err());
+        }
+    }
+
+    #[test]
+    fn it_loads_the_inbound_monitoring_settings_from_config() {
+        let config: MonitorPeersConfig = toml::from_str(
+            r#"
+            monitor_inbound = true
+            inbound_unresponsive_rounds = 6
+            "#,
+        )
+        .unwrap();
+        assert!(config.monitor_inbound);
+        assert_eq!(config.inbound_unresponsive_rounds, 6);
+        assert_eq!(config.max_tracked_peers, MonitorPeersConfig::default().max_tracked_peers);
+        assert!(config.validate(CullPolicy::default()).is_ok());
+    }
+}
@@ -0,0 +1,160 @@
+//  Copyright 2025, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use tari_comms::{multiaddr::Multiaddr, peer_manager::NodeId};
+use tokio::sync::{broadcast, watch};
+
+/// Coarse location and network operator of a peer, derived from its address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerAnnotation {
+    /// A coarse location such as a country or region code
+    pub region: Option<String>,
+    /// The autonomous system number of the network the address belongs to
+    pub asn: Option<u32>,
+}
+
+/// Derives a `PeerAnnotation` from a peer address, for example from a geoip database. Returns `None` if the address
+/// is not recognised.
+#[derive(Clone)]
+pub struct PeerAnnotationLookup(Arc<dyn Fn(&Multiaddr) -> Option<PeerAnnotation> + Send + Sync>);
+
+impl PeerAnnotationLookup {
+    pub fn new<F>(lookup: F) -> Self
+    where F: Fn(&Multiaddr) -> Option<PeerAnnotation> + Send + Sync + 'static {
+        Self(Arc::new(lookup))
+    }
+
+    pub fn lookup(&self, address: &Multiaddr) -> Option<PeerAnnotation> {
+        (self.0)(address)
+    }
+}
+
+impl fmt::Debug for PeerAnnotationLookup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PeerAnnotationLookup")
+    }
+}
+
+/// The liveness of a monitored peer in a monitoring round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerLivenessSnapshot {
+    pub node_id: NodeId,
+    /// The address of the connection to the peer
+    pub address: Multiaddr,
+    /// True if the peer responded to the ping in this round
+    pub responsive: bool,
+    /// The round-trip-time of the ping, if known
+    pub latency: Option<Duration>,
+    /// `None` if no annotation lookup is configured or the lookup did not recognise the address
+    pub annotation: Option<PeerAnnotation>,
+}
+
+/// The liveness of all monitored peers at the end of a monitoring round
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonitorPeersSnapshot {
+    /// The monitoring round the snapshot was taken in, 0 before the first round has completed
+    pub round: u64,
+    pub peers: Vec<PeerLivenessSnapshot>,
+}
+
+/// The aggregate responsiveness of the monitored peers in a monitoring round
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetworkHealth {
+    /// The monitoring round the responsiveness was measured in, 0 before the first round has completed
+    pub round: u64,
+    /// The fraction of the monitored peers that responded to the ping in this round, or 0 if there were no peers to
+    /// monitor
+    pub responsive_ratio: f64,
+    /// True if the responsive ratio is below the configured minimum or there were no peers to monitor
+    pub network_unhealthy: bool,
+}
+
+/// The liveness of a monitored peer in a single monitoring round
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LivenessSample {
+    pub round: u64,
+    /// True if the peer was connected in this round
+    pub connected: bool,
+    /// True if the peer responded to the ping in this round
+    pub responsive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonitorPeersEvent {
+    /// An unresponsive peer is about to be disconnected. The samples are the liveness stats that led to the peer being
+    /// culled, oldest first.
+    PeerCulled {
+        node_id: NodeId,
+        samples: Vec<LivenessSample>,
+    },
+}
+
+pub type MonitorPeersEventSender = broadcast::Sender<Arc<MonitorPeersEvent>>;
+pub type MonitorPeersEventReceiver = broadcast::Receiver<Arc<MonitorPeersEvent>>;
+
+#[derive(Debug, Clone)]
+pub struct MonitorPeersHandle {
+    snapshots: watch::Receiver<MonitorPeersSnapshot>,
+    network_health: watch::Receiver<NetworkHealth>,
+    event_stream_sender: MonitorPeersEventSender,
+}
+
+impl MonitorPeersHandle {
+    pub(super) fn new(
+        snapshots: watch::Receiver<MonitorPeersSnapshot>,
+        network_health: watch::Receiver<NetworkHealth>,
+        event_stream_sender: MonitorPeersEventSender,
+    ) -> Self {
+        Self {
+            snapshots,
+            network_health,
+            event_stream_sender,
+        }
+    }
+
+    /// Returns an event stream for the peer monitoring service
+    pub fn get_event_stream(&self) -> MonitorPeersEventReceiver {
+        self.event_stream_sender.subscribe()
+    }
+
+    /// Returns a stream of the liveness snapshots taken at the end of each monitoring round
+    pub fn get_snapshot_stream(&self) -> watch::Receiver<MonitorPeersSnapshot> {
+        self.snapshots.clone()
+    }
+
+    /// Returns the most recent liveness snapshot
+    pub fn latest_snapshot(&self) -> MonitorPeersSnapshot {
+        self.snapshots.borrow().clone()
+    }
+
+    /// Returns a stream of the network health, updated at the end of each monitoring round
+    pub fn get_network_health_stream(&self) -> watch::Receiver<NetworkHealth> {
+        self.network_health.clone()
+    }
+
+    /// Returns the most recent network health
+    pub fn network_health(&self) -> NetworkHealth {
+        *self.network_health.borrow()
+    }
+}
@@ -20,6 +20,26 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod config;
+pub use config::{CullPolicy, MonitorPeersConfig};
+
+mod handle;
+pub use handle::{
+    LivenessSample,
+    MonitorPeersEvent,
+    MonitorPeersEventReceiver,
+    MonitorPeersEventSender,
+    MonitorPeersHandle,
+    MonitorPeersSnapshot,
+    NetworkHealth,
+    PeerAnnotation,
+    PeerAnnotationLookup,
+    PeerLivenessSnapshot,
+};
+
+#[cfg(feature = "metrics")]
+mod metrics;
+
 mod service;
 
 use std::{cmp::max, time::Duration};
@@ -27,6 +47,7 @@ use std::{cmp::max, time::Duration};
 use log::debug;
 use tari_comms::{async_trait, connectivity::ConnectivityRequester};
 use tari_service_framework::{ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
+use tokio::sync::{broadcast, watch};
 
 use crate::services::{
     liveness::{LivenessHandle, MAX_INFLIGHT_TTL},
@@ -38,21 +59,42 @@ const LOG_TARGET: &str = "p2p::services::monitor_peers";
 /// Initializer for the MonitorPeers service handle and service future.
 pub struct MonitorPeersInitializer {
     auto_ping_interval: Option<Duration>,
+    config: MonitorPeersConfig,
+    cull_policy: CullPolicy,
+    annotation_lookup: Option<PeerAnnotationLookup>,
 }
 
 impl MonitorPeersInitializer {
-    /// Create a new MonitorPeersInitializer from the inbound message subscriber
-    pub fn new(auto_ping_interval: Duration) -> Self {
+    /// Create a new MonitorPeersInitializer with the given auto ping interval and policy for culling unresponsive peers
+    pub fn new(auto_ping_interval: Duration, cull_policy: CullPolicy) -> Self {
         Self {
             auto_ping_interval: Some(auto_ping_interval),
+            config: MonitorPeersConfig::default(),
+            cull_policy,
+            annotation_lookup: None,
         }
     }
+
+    /// Set the configuration for the peer monitoring service
+    pub fn with_config(mut self, config: MonitorPeersConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Annotate the monitored peers in the liveness snapshots with the location derived from their address
+    pub fn with_annotation_lookup(mut self, lookup: PeerAnnotationLookup) -> Self {
+        self.annotation_lookup = Some(lookup);
+        self
+    }
 }
 
 impl Default for MonitorPeersInitializer {
     fn default() -> Self {
         Self {
             auto_ping_interval: Some(MAX_INFLIGHT_TTL),
+            config: MonitorPeersConfig::default(),
+            cull_policy: CullPolicy::default(),
+            annotation_lookup: None,
         }
     }
 }
@@ -61,6 +103,7 @@ impl Default for MonitorPeersInitializer {
 impl ServiceInitializer for MonitorPeersInitializer {
     async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
         debug!(target: LOG_TARGET, "Initializing Peer Monitoring Service");
+        self.config.validate(self.cull_policy)?;
 
         let auto_ping_interval = max(
             self.auto_ping_interval
@@ -68,6 +111,17 @@ impl ServiceInitializer for MonitorPeersInitializer {
                 .expect("Monitor peers service initialized more than once."),
             MAX_INFLIGHT_TTL,
         );
+        let config = self.config.clone();
+        let cull_policy = self.cull_policy;
+        let annotation_lookup = self.annotation_lookup.clone();
+        let (snapshot_tx, snapshot_rx) = watch::channel(MonitorPeersSnapshot::default());
+        let (network_health_tx, network_health_rx) = watch::channel(NetworkHealth::default());
+        let (event_publisher, _) = broadcast::channel(100);
+        context.register_handle(MonitorPeersHandle::new(
+            snapshot_rx,
+            network_health_rx,
+            event_publisher.clone(),
+        ));
 
         // Spawn the MonitorPeers service on the executor
         context.spawn_when_ready(move |handles| async move {
@@ -79,7 +133,13 @@ impl ServiceInitializer for MonitorPeersInitializer {
                 liveness,
                 handles.get_shutdown_signal(),
                 auto_ping_interval,
-            );
+                config,
+            )
+            .with_snapshots(snapshot_tx)
+            .with_network_health(network_health_tx)
+            .with_cull_policy(cull_policy)
+            .with_event_publisher(event_publisher)
+            .with_annotation_lookup(annotation_lookup);
             service.run().await;
             debug!(target: LOG_TARGET, "Monitor peers service has shut down");
         });
@@ -20,44 +20,74 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
 
-use futures::pin_mut;
 use log::*;
 use tari_comms::{
-    connection_manager::ConnectionDirection,
+    connection_manager::{ConnectionDirection, DisconnectReason},
     connectivity::ConnectivityRequester,
+    multiaddr::Multiaddr,
     peer_manager::NodeId,
     Minimized,
     PeerConnection,
 };
 use tari_shutdown::ShutdownSignal;
 use tokio::{
-    sync::broadcast::error::RecvError,
-    time::{self, Duration},
+    sync::{
+        broadcast::{self, error::RecvError},
+        watch,
+    },
+    task,
+    time::{self, Duration, Instant},
 };
 
 use crate::services::{
-    liveness::{LivenessEvent, LivenessHandle},
-    monitor_peers::LOG_TARGET,
+    liveness::{LivenessEvent, LivenessEventReceiver, LivenessHandle},
+    monitor_peers::{
+        CullPolicy,
+        LivenessSample,
+        MonitorPeersConfig,
+        MonitorPeersEvent,
+        MonitorPeersEventSender,
+        MonitorPeersSnapshot,
+        NetworkHealth,
+        PeerAnnotationLookup,
+        PeerLivenessSnapshot,
+        LOG_TARGET,
+    },
 };
 
-struct PeerLiveness<T, const MAX_SIZE: usize> {
+/// After the liveness event stream lags, wait at most this long for any remaining pongs
+const LAGGED_PONG_GRACE_PERIOD: Duration = Duration::from_secs(1);
+/// A monitoring round is abandoned if it takes longer than this multiple of the auto ping interval
+const ROUND_TIMEOUT_FACTOR: u32 = 5;
+/// The maximum time spent disconnecting the monitored peers on shutdown
+const SHUTDOWN_DISCONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct PeerLiveness<T> {
     vec: VecDeque<T>,
+    max_size: usize,
+    last_updated: u64,
 }
 
-impl<T, const MAX_SIZE: usize> PeerLiveness<T, MAX_SIZE> {
-    pub fn new() -> Self {
+impl<T> PeerLiveness<T> {
+    pub fn new(loop_count: u64, max_size: usize) -> Self {
         Self {
-            vec: VecDeque::with_capacity(MAX_SIZE),
+            vec: VecDeque::with_capacity(max_size),
+            max_size,
+            last_updated: loop_count,
         }
     }
 
-    pub fn push_pop(&mut self, item: T) {
-        if self.vec.len() == MAX_SIZE {
+    pub fn push_pop(&mut self, item: T, loop_count: u64) {
+        if self.vec.len() >= self.max_size {
             self.vec.pop_front();
         }
         self.vec.push_back(item);
+        self.last_updated = loop_count;
     }
 
     pub fn iter(&self) -> std::collections::vec_deque::Iter<T> {
@@ -71,17 +101,131 @@ struct Stats {
     loop_count: u64,
 }
 
+impl Stats {
+    pub fn to_sample(&self) -> LivenessSample {
+        LivenessSample {
+            round: self.loop_count,
+            connected: self.connected,
+            responsive: self.responsive,
+        }
+    }
+
+    /// The cull score of this round
+    pub fn cull_score(&self, config: &MonitorPeersConfig) -> u32 {
+        match (self.connected, self.responsive) {
+            (true, false) => config.unresponsive_weight,
+            (false, _) => config.disconnected_weight,
+            (true, true) => 0,
+        }
+    }
+}
+
+/// The number of most recent consecutive rounds in which the peer was connected but did not respond
+fn consecutive_unresponsive_rounds(stats: &PeerLiveness<Stats>) -> usize {
+    stats
+        .iter()
+        .rev()
+        .take_while(|s| s.connected && !s.responsive)
+        .count()
+}
+
+/// The weighted cull score of the rounds since the peer last responded, within the window of kept liveness stats
+fn cull_score(stats: &PeerLiveness<Stats>, config: &MonitorPeersConfig) -> u32 {
+    stats
+        .iter()
+        .rev()
+        .take_while(|s| !(s.connected && s.responsive))
+        .map(|s| s.cull_score(config))
+        .fold(0u32, u32::saturating_add)
+}
+
+/// The cull score of `consecutive_failures` unresponsive rounds, at which outbound peers are culled
+fn cull_score_threshold(config: &MonitorPeersConfig, cull_policy: CullPolicy) -> u32 {
+    u32::try_from(cull_policy.consecutive_failures)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(config.unresponsive_weight)
+}
+
 struct PeerPingPong {
-    expected_nonce: u64,
+    /// `None` if the ping could not be sent to the peer
+    expected_nonce: Option<u64>,
     received_nonce: Option<u64>,
+    latency: Option<Duration>,
     node_id: NodeId,
 }
 
+impl PeerPingPong {
+    /// True if a ping was sent to the peer and its pong has not been received yet
+    pub fn is_pending(&self) -> bool {
+        self.expected_nonce.is_some() && self.received_nonce.is_none()
+    }
+}
+
+/// Culled peers that are waiting for their cooldown to pass before being re-dialed once
+struct CulledPeers {
+    redial_after: Duration,
+    culled_at: HashMap<NodeId, Instant>,
+}
+
+impl CulledPeers {
+    fn new(redial_after: Duration) -> Self {
+        Self {
+            redial_after,
+            culled_at: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, node_id: NodeId, now: Instant) {
+        self.culled_at.insert(node_id, now);
+    }
+
+    /// Removes and returns the peers whose cooldown has passed at `now`
+    fn take_due(&mut self, now: Instant) -> Vec<NodeId> {
+        let due = self
+            .culled_at
+            .iter()
+            .filter(|(_, culled_at)| now.saturating_duration_since(**culled_at) >= self.redial_after)
+            .map(|(node_id, _)| node_id.clone())
+            .collect::<Vec<_>>();
+        for node_id in &due {
+            self.culled_at.remove(node_id);
+        }
+        due
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PongWaitResult {
+    /// All expected pongs were received, the round timed out or shutdown was signalled
+    Done,
+    /// The liveness event stream lagged during the round, so some pongs may have been missed
+    Lagged,
+    /// The liveness event stream has closed
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoundOutcome {
+    /// The peers were pinged and the liveness stats updated
+    Completed,
+    /// There was nothing to do this round, or a request failed
+    Skipped,
+    /// The liveness event stream has closed
+    Closed,
+}
+
 pub struct MonitorPeersService {
     comms: ConnectivityRequester,
     liveness_handle: LivenessHandle,
     shutdown_signal: ShutdownSignal,
     auto_ping_interval: Duration,
+    config: MonitorPeersConfig,
+    cull_policy: CullPolicy,
+    snapshot_tx: watch::Sender<MonitorPeersSnapshot>,
+    network_health_tx: watch::Sender<NetworkHealth>,
+    event_publisher: MonitorPeersEventSender,
+    annotation_lookup: Option<PeerAnnotationLookup>,
+    monitored_connections: Vec<PeerConnection>,
 }
 
 impl MonitorPeersService {
@@ -90,25 +234,63 @@ impl MonitorPeersService {
         liveness_handle: LivenessHandle,
         shutdown_signal: ShutdownSignal,
         auto_ping_interval: Duration,
+        config: MonitorPeersConfig,
     ) -> Self {
         Self {
             comms,
             liveness_handle,
             shutdown_signal,
             auto_ping_interval,
+            config,
+            cull_policy: CullPolicy::default(),
+            snapshot_tx: watch::channel(MonitorPeersSnapshot::default()).0,
+            network_health_tx: watch::channel(NetworkHealth::default()).0,
+            event_publisher: broadcast::channel(1).0,
+            annotation_lookup: None,
+            monitored_connections: Vec::new(),
         }
     }
 
+    /// Publish the liveness snapshot of each round to the given channel
+    pub fn with_snapshots(mut self, snapshot_tx: watch::Sender<MonitorPeersSnapshot>) -> Self {
+        self.snapshot_tx = snapshot_tx;
+        self
+    }
+
+    /// Publish the network health of each round to the given channel
+    pub fn with_network_health(mut self, network_health_tx: watch::Sender<NetworkHealth>) -> Self {
+        self.network_health_tx = network_health_tx;
+        self
+    }
+
+    /// Publish the events of the service, e.g. culled peers, to the given channel
+    pub fn with_event_publisher(mut self, event_publisher: MonitorPeersEventSender) -> Self {
+        self.event_publisher = event_publisher;
+        self
+    }
+
+    /// Cull unresponsive peers according to the given policy
+    pub fn with_cull_policy(mut self, cull_policy: CullPolicy) -> Self {
+        self.cull_policy = cull_policy;
+        self
+    }
+
+    /// Annotate the peers in the liveness snapshots using the given lookup
+    pub fn with_annotation_lookup(mut self, annotation_lookup: Option<PeerAnnotationLookup>) -> Self {
+        self.annotation_lookup = annotation_lookup;
+        self
+    }
+
     /// Monitor the liveness of outbound peer connections and disconnect those that do not respond to pings
     /// consecutively. The intent of the interval timer is to be significantly longer than the rate at which
     /// metadata is requested from peers.
-    #[allow(clippy::too_many_lines)]
     pub async fn run(mut self) {
         let mut interval_timer = time::interval(self.auto_ping_interval * 10);
-        let liveness_events = self.liveness_handle.get_event_stream();
-        pin_mut!(liveness_events);
+        let round_timeout = self.auto_ping_interval * ROUND_TIMEOUT_FACTOR;
+        let mut liveness_events = self.liveness_handle.get_event_stream();
 
-        let mut peer_liveness_stats: HashMap<NodeId, PeerLiveness<Stats, 7>> = HashMap::new();
+        let mut peer_liveness_stats: HashMap<NodeId, PeerLiveness<Stats>> = HashMap::new();
+        let mut culled_peers = self.config.redial_after.map(CulledPeers::new);
 
         let mut loop_count = 0u64;
         loop {
@@ -116,137 +298,427 @@ impl MonitorPeersService {
             tokio::select! {
                 biased;
                 _ = self.shutdown_signal.wait() => {
+                    if self.config.disconnect_monitored_on_shutdown {
+                        self.disconnect_monitored_peers().await;
+                    }
                     break;
                 }
 
                 _ = interval_timer.tick() => {
-                    trace!(target: LOG_TARGET, "Starting monitor peers round (iter {})", loop_count);
-                    let active_connections = match self.comms.get_active_connections().await {
-                        Ok(val) => val,
-                        Err(e) => {
-                            warn!(target: LOG_TARGET, "Failed to get active connections ({})", e);
-                            continue;
+                    let round = self.run_round(
+                        &mut liveness_events,
+                        &mut peer_liveness_stats,
+                        &mut culled_peers,
+                        loop_count,
+                    );
+                    // Bound the round so that a hung request cannot stall the service (or its shutdown) indefinitely
+                    match time::timeout(round_timeout, round).await {
+                        Ok(RoundOutcome::Completed) => {},
+                        Ok(RoundOutcome::Skipped) => {
+                            // No peers could be monitored, which is reported as an unhealthy network rather than
+                            // leaving the health of an earlier round in place
+                            self.network_health_tx.send_replace(NetworkHealth {
+                                round: loop_count,
+                                responsive_ratio: 0.0,
+                                network_unhealthy: true,
+                            });
+                        },
+                        Ok(RoundOutcome::Closed) => return,
+                        Err(_) => {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Monitor peers round did not complete within {:.0?}, skipping it (iter {})",
+                                round_timeout,
+                                loop_count
+                            );
                         },
-                    };
-                    let mut active_peer_connections = active_connections
-                        .iter()
-                        .filter(|p|p.peer_features().is_node() && p.direction() == ConnectionDirection::Outbound)
-                        .cloned()
-                        .collect::<Vec<_>>();
-                    if active_peer_connections.is_empty() {
-                        trace!(target: LOG_TARGET, "No active connections found");
-                        continue;
-                    }
-                    let active_peer_node_ids = active_peer_connections
-                        .iter()
-                        .map(|p|p.peer_node_id().clone())
-                        .collect::<Vec<_>>();
-
-                    let known_peer_connections = peer_liveness_stats.keys().cloned().collect::<Vec<_>>();
-                    for peer_id in &known_peer_connections {
-                        if !active_peer_node_ids.contains(peer_id) {
-                            // Prior connections not connected now are considered inactive and unresponsive
-                            peer_liveness_stats
-                                .entry(peer_id.clone())
-                                .and_modify(|item| item.push_pop(
-                                    Stats {connected: false, responsive: false, loop_count}
-                                ));
-                        }
-                    }
-                    for peer_id in &active_peer_node_ids {
-                        if !known_peer_connections.contains(peer_id) {
-                            // New connections are considered active and responsive
-                            peer_liveness_stats.insert( peer_id.clone(), PeerLiveness::new());
-                        }
                     }
+                },
+            }
+        }
+    }
 
-                    let mut peer_ping_pongs = match self.liveness_handle
-                        .send_pings(active_peer_node_ids.clone())
-                        .await
-                    {
-                        Ok(nonces) => active_peer_node_ids
-                            .iter()
-                            .zip(nonces.iter())
-                            .map(|(node_id, &nonce)| PeerPingPong {
-                                expected_nonce: nonce,
-                                received_nonce: None,
-                                node_id: node_id.clone(),
-                            })
-                            .collect::<Vec<_>>(),
+    /// Disconnects the outbound peers that were monitored in the last round, bounded by `SHUTDOWN_DISCONNECT_TIMEOUT`
+    async fn disconnect_monitored_peers(&mut self) {
+        let connections = std::mem::take(&mut self.monitored_connections);
+        debug!(
+            target: LOG_TARGET,
+            "Disconnecting {} monitored peer(s) on shutdown",
+            connections.len()
+        );
+        let disconnect_all = async {
+            for mut conn in connections {
+                if let Err(e) = conn
+                    .disconnect_with_reason(Minimized::No, DisconnectReason::Shutdown)
+                    .await
+                {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Error while disconnecting peer {} on shutdown: {}", conn.peer_node_id(), e
+                    );
+                }
+            }
+        };
+        if time::timeout(SHUTDOWN_DISCONNECT_TIMEOUT, disconnect_all)
+            .await
+            .is_err()
+        {
+            warn!(
+                target: LOG_TARGET,
+                "Monitored peers were not disconnected within {:.0?} of shutdown", SHUTDOWN_DISCONNECT_TIMEOUT
+            );
+        }
+    }
+
+    /// Pings the outbound (and, if configured, inbound) base node peers and culls those that are unresponsive
+    #[allow(clippy::too_many_lines)]
+    async fn run_round(
+        &mut self,
+        liveness_events: &mut LivenessEventReceiver,
+        peer_liveness_stats: &mut HashMap<NodeId, PeerLiveness<Stats>>,
+        culled_peers: &mut Option<CulledPeers>,
+        loop_count: u64,
+    ) -> RoundOutcome {
+        trace!(target: LOG_TARGET, "Starting monitor peers round (iter {})", loop_count);
+        if let Some(culled_peers) = culled_peers.as_mut() {
+            redial_culled_peers(&self.comms, &self.shutdown_signal, culled_peers, Instant::now());
+        }
+        let active_connections = match self.comms.get_active_connections().await {
+            Ok(val) => val,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to get active connections ({})", e);
+                return RoundOutcome::Skipped;
+            },
+        };
+        let mut active_peer_connections = active_connections
+            .iter()
+            .filter(|p| {
+                p.peer_features().is_node() &&
+                    (p.direction() == ConnectionDirection::Outbound || self.config.monitor_inbound)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        self.monitored_connections.clone_from(&active_peer_connections);
+        if active_peer_connections.is_empty() {
+            trace!(target: LOG_TARGET, "No active connections found");
+            return RoundOutcome::Skipped;
+        }
+        let active_peer_node_ids = active_peer_connections
+            .iter()
+            .map(|p| p.peer_node_id().clone())
+            .collect::<Vec<_>>();
+
+        let known_peer_connections = peer_liveness_stats.keys().cloned().collect::<Vec<_>>();
+        for peer_id in &known_peer_connections {
+            if !active_peer_node_ids.contains(peer_id) {
+                // Prior connections not connected now are considered inactive and unresponsive
+                peer_liveness_stats.entry(peer_id.clone()).and_modify(|item| {
+                    item.push_pop(
+                        Stats {
+                            connected: false,
+                            responsive: false,
+                            loop_count,
+                        },
+                        loop_count,
+                    )
+                });
+            }
+        }
+        for peer_id in &active_peer_node_ids {
+            if !known_peer_connections.contains(peer_id) {
+                // New connections are considered active and responsive
+                peer_liveness_stats.insert(peer_id.clone(), PeerLiveness::new(loop_count, self.cull_policy.window));
+            }
+        }
+
+        let mut peer_ping_pongs = self.send_pings(&active_peer_node_ids, loop_count).await;
+        if peer_ping_pongs.iter().all(|p| p.expected_nonce.is_none()) {
+            warn!(target: LOG_TARGET, "Failed to send pings to any peer (iter {})", loop_count);
+            return RoundOutcome::Skipped;
+        }
+
+        // Only listen for the expected pongs from the peers (ignore any other pongs)
+        let mut nonce_echoers = Vec::new();
+        let lagged = match wait_for_pongs(
+            liveness_events,
+            &mut peer_ping_pongs,
+            &mut nonce_echoers,
+            self.auto_ping_interval,
+            &mut self.shutdown_signal,
+            loop_count,
+        )
+        .await
+        {
+            PongWaitResult::Done => false,
+            PongWaitResult::Lagged => true,
+            PongWaitResult::Closed => return RoundOutcome::Closed,
+        };
+        if let Some(ban_duration) = self.config.nonce_echo_ban_duration {
+            if !self.config.dry_run {
+                ban_nonce_echoers(&mut self.comms, nonce_echoers, ban_duration).await;
+            }
+        }
+
+        let addresses = active_peer_connections
+            .iter()
+            .map(|conn| (conn.peer_node_id().clone(), conn.address().clone()))
+            .collect::<HashMap<_, _>>();
+        self.snapshot_tx.send_replace(round_snapshot(
+            loop_count,
+            &peer_ping_pongs,
+            &addresses,
+            self.annotation_lookup.as_ref(),
+        ));
+        // Pongs may have been missed in a lagged round, which would understate the network health
+        if !lagged {
+            self.network_health_tx.send_replace(network_health(
+                loop_count,
+                &peer_ping_pongs,
+                self.config.min_responsive_ratio,
+            ));
+        }
+
+        // Compare nonces and close connections for peers that did not respond multiple times
+        let culled = update_stats_and_cull_unresponsive_connections(
+            &peer_ping_pongs,
+            &mut active_peer_connections,
+            peer_liveness_stats,
+            loop_count,
+            lagged,
+            &self.config,
+            self.cull_policy,
+            &self.event_publisher,
+        )
+        .await;
+        #[cfg(feature = "metrics")]
+        update_round_metrics(&peer_ping_pongs, active_peer_connections.len(), culled.len());
+        if !self.config.dry_run {
+            self.monitored_connections
+                .retain(|conn| !culled.contains(conn.peer_node_id()));
+        }
+        if let Some(culled_peers) = culled_peers.as_mut() {
+            if !self.config.dry_run {
+                let now = Instant::now();
+                for node_id in culled {
+                    culled_peers.insert(node_id, now);
+                }
+            }
+        }
+
+        evict_least_recently_updated_peers(
+            peer_liveness_stats,
+            &active_peer_node_ids,
+            self.config.max_tracked_peers,
+        );
+        RoundOutcome::Completed
+    }
+
+    /// Pings the peers in a single request. If that fails, the peers are pinged one by one so that a peer that cannot
+    /// be pinged does not prevent the others from being monitored. A peer that could not be pinged has no expected
+    /// nonce and is evaluated as unresponsive.
+    async fn send_pings(&mut self, node_ids: &[NodeId], loop_count: u64) -> Vec<PeerPingPong> {
+        let new_ping_pong = |node_id: &NodeId, expected_nonce| PeerPingPong {
+            expected_nonce,
+            received_nonce: None,
+            latency: None,
+            node_id: node_id.clone(),
+        };
+        match self.liveness_handle.send_pings(node_ids.to_vec()).await {
+            Ok(nonces) => node_ids
+                .iter()
+                .zip(nonces)
+                .map(|(node_id, nonce)| new_ping_pong(node_id, Some(nonce)))
+                .collect(),
+            Err(e) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Failed to send pings to all peers ({}), pinging them individually (iter {})", e, loop_count
+                );
+                let mut peer_ping_pongs = Vec::with_capacity(node_ids.len());
+                for node_id in node_ids {
+                    let expected_nonce = match self.liveness_handle.send_pings(vec![node_id.clone()]).await {
+                        Ok(nonces) => nonces.first().copied(),
                         Err(e) => {
-                            warn!(target: LOG_TARGET, "Failed to send pings to peers ({})", e);
-                            continue;
+                            warn!(
+                                target: LOG_TARGET,
+                                "Failed to send ping to peer {} ({}) (iter {})", node_id, e, loop_count
+                            );
+                            None
                         },
                     };
+                    peer_ping_pongs.push(new_ping_pong(node_id, expected_nonce));
+                }
+                peer_ping_pongs
+            },
+        }
+    }
+}
 
-                    // Only listen for the expected pongs from the peers (ignore any other pongs)
-                    let timeout_timer = time::sleep(self.auto_ping_interval);
-                    tokio::pin!(timeout_timer);
-                    loop {
-                        tokio::select! {
-                            biased;
-                            _ = self.shutdown_signal.wait() => {
-                                break;
-                            }
+/// The liveness of the pinged peers in this round, annotated with their location if a lookup is configured
+fn round_snapshot(
+    loop_count: u64,
+    peer_ping_pongs: &[PeerPingPong],
+    addresses: &HashMap<NodeId, Multiaddr>,
+    annotation_lookup: Option<&PeerAnnotationLookup>,
+) -> MonitorPeersSnapshot {
+    let peers = peer_ping_pongs
+        .iter()
+        .filter_map(|p| {
+            let address = addresses.get(&p.node_id)?;
+            Some(PeerLivenessSnapshot {
+                node_id: p.node_id.clone(),
+                address: address.clone(),
+                responsive: p.received_nonce.is_some(),
+                latency: p.latency,
+                annotation: annotation_lookup.and_then(|lookup| lookup.lookup(address)),
+            })
+        })
+        .collect();
+    MonitorPeersSnapshot {
+        round: loop_count,
+        peers,
+    }
+}
 
-                            event = liveness_events.recv() => {
-                                let event_str = format!("{:?}", event);
-                                match event {
-                                    Ok(arc_event) => {
-                                        if let LivenessEvent::ReceivedPong(pong) = &*arc_event {
-                                            if let Some(ping_pong) = peer_ping_pongs.iter_mut().find(|p| p.expected_nonce == pong.nonce) {
-                                                ping_pong.received_nonce = Some(pong.nonce);
-                                            }
-                                            if peer_ping_pongs.iter().all(|p| p.received_nonce.is_some()) {
-                                                break;
-                                            }
-                                        }
-                                    },
-                                    Err(RecvError::Closed) => {
-                                        return;
-                                    },
-                                    Err(ref e) => {
-                                        debug!(
-                                            target: LOG_TARGET,
-                                            "Liveness event error: {:?} ({})",
-                                            event_str, e.to_string()
-                                        );
-                                    },
-                                }
-                            },
+/// The fraction of the pinged peers that responded in this round, and whether it is below `min_responsive_ratio`
+fn network_health(loop_count: u64, peer_ping_pongs: &[PeerPingPong], min_responsive_ratio: f64) -> NetworkHealth {
+    let num_responsive = peer_ping_pongs.iter().filter(|p| p.received_nonce.is_some()).count();
+    let responsive_ratio = if peer_ping_pongs.is_empty() {
+        0.0
+    } else {
+        num_responsive as f64 / peer_ping_pongs.len() as f64
+    };
+    NetworkHealth {
+        round: loop_count,
+        responsive_ratio,
+        network_unhealthy: responsive_ratio < min_responsive_ratio,
+    }
+}
+
+/// Publishes the outcome of a monitor peers round to the metrics registry
+#[cfg(feature = "metrics")]
+fn update_round_metrics(peer_ping_pongs: &[PeerPingPong], num_active: usize, num_culled: usize) {
+    let num_unresponsive = peer_ping_pongs.iter().filter(|p| p.received_nonce.is_none()).count();
+    super::metrics::active_peers().set(i64::try_from(num_active).unwrap_or(i64::MAX));
+    super::metrics::unresponsive_peers().set(i64::try_from(num_unresponsive).unwrap_or(i64::MAX));
+    super::metrics::culled_peers().inc_by(num_culled as u64);
+    let pong_rtt = super::metrics::pong_rtt();
+    for latency in peer_ping_pongs.iter().filter_map(|p| p.latency) {
+        pong_rtt.observe(latency.as_secs_f64());
+    }
+}
 
-                            _ = &mut timeout_timer => {
-                                trace!(
-                                    target: LOG_TARGET,
-                                    "Timed out waiting for pongs, received {} of {} (iter  {})",
-                                    peer_ping_pongs.iter().filter(|p| p.received_nonce.is_some()).count(),
-                                    peer_ping_pongs.len(),
-                                    loop_count
-                                );
+/// Wait for the expected pongs to arrive, up to `timeout`. If the liveness event stream lags, pongs may have been
+/// dropped, so the remaining wait is shortened to `LAGGED_PONG_GRACE_PERIOD` rather than blocking for the full
+/// timeout on pongs that will never be seen. A pong is only credited to the peer that its nonce was sent to; peers
+/// that echo another peer's nonce are added to `nonce_echoers`.
+async fn wait_for_pongs(
+    liveness_events: &mut LivenessEventReceiver,
+    peer_ping_pongs: &mut [PeerPingPong],
+    nonce_echoers: &mut Vec<NodeId>,
+    timeout: Duration,
+    shutdown_signal: &mut ShutdownSignal,
+    loop_count: u64,
+) -> PongWaitResult {
+    let mut lagged = false;
+    let timeout_timer = time::sleep(timeout);
+    tokio::pin!(timeout_timer);
+    loop {
+        tokio::select! {
+            biased;
+            _ = shutdown_signal.wait() => {
+                break;
+            }
+
+            event = liveness_events.recv() => {
+                match event {
+                    Ok(arc_event) => {
+                        if let LivenessEvent::ReceivedPong(pong) = &*arc_event {
+                            if let Some(ping_pong) = peer_ping_pongs
+                                .iter_mut()
+                                .find(|p| p.expected_nonce == Some(pong.nonce))
+                            {
+                                if ping_pong.node_id == pong.node_id {
+                                    ping_pong.received_nonce = Some(pong.nonce);
+                                    ping_pong.latency = pong.latency;
+                                } else {
+                                    warn!(
+                                        target: LOG_TARGET,
+                                        "Peer {} returned a pong with nonce {} that was sent to peer {}, ignoring it \
+                                         (iter {})",
+                                        pong.node_id,
+                                        pong.nonce,
+                                        ping_pong.node_id,
+                                        loop_count
+                                    );
+                                    if !nonce_echoers.contains(&pong.node_id) {
+                                        nonce_echoers.push(pong.node_id.clone());
+                                    }
+                                }
+                            }
+                            if !peer_ping_pongs.iter().any(|p| p.is_pending()) {
                                 break;
-                            },
+                            }
                         }
-                    }
+                    },
+                    Err(RecvError::Closed) => {
+                        return PongWaitResult::Closed;
+                    },
+                    Err(RecvError::Lagged(n)) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Liveness event stream lagged by {} events, waiting at most {:.0?} for the remaining pongs \
+                             (iter {})",
+                            n,
+                            LAGGED_PONG_GRACE_PERIOD,
+                            loop_count
+                        );
+                        lagged = true;
+                        let grace_deadline = Instant::now() + LAGGED_PONG_GRACE_PERIOD;
+                        if grace_deadline < timeout_timer.deadline() {
+                            timeout_timer.as_mut().reset(grace_deadline);
+                        }
+                    },
+                }
+            },
 
-                    // Compare nonces and close connections for peers that did not respond multiple times
-                    update_stats_and_cull_unresponsive_connections(
-                        &peer_ping_pongs,
-                        &mut active_peer_connections,
-                        &mut peer_liveness_stats,
-                        loop_count
-                    ).await;
-                },
-            }
+            _ = &mut timeout_timer => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Timed out waiting for pongs, received {} of {} (iter  {})",
+                    peer_ping_pongs.iter().filter(|p| p.received_nonce.is_some()).count(),
+                    peer_ping_pongs.len(),
+                    loop_count
+                );
+                break;
+            },
         }
     }
+
+    if lagged {
+        PongWaitResult::Lagged
+    } else {
+        PongWaitResult::Done
+    }
 }
 
+/// Updates the liveness stats from the received pongs and disconnects unresponsive outbound peers whose weighted cull
+/// score since their last response reaches that of `consecutive_failures` unresponsive rounds, or inbound peers that
+/// did not respond in `inbound_unresponsive_rounds` consecutive rounds. In `dry_run` mode those peers are
+/// only logged. A `PeerCulled` event is published before each peer is disconnected. Returns the peers that were (or
+/// would have been) culled.
 async fn update_stats_and_cull_unresponsive_connections(
     peer_ping_pongs: &[PeerPingPong],
     active_peer_connections: &mut [PeerConnection],
-    peer_liveness_stats: &mut HashMap<NodeId, PeerLiveness<Stats, 7>>,
+    peer_liveness_stats: &mut HashMap<NodeId, PeerLiveness<Stats>>,
     loop_count: u64,
-) {
+    lagged: bool,
+    config: &MonitorPeersConfig,
+    cull_policy: CullPolicy,
+    event_publisher: &MonitorPeersEventSender,
+) -> Vec<NodeId> {
+    let dry_run = config.dry_run;
+    let threshold = cull_score_threshold(config, cull_policy);
     let received_nonces_count = peer_ping_pongs.iter().filter(|p| p.received_nonce.is_some()).count();
     if received_nonces_count != peer_ping_pongs.len() {
         trace!(
@@ -263,38 +735,62 @@ async fn update_stats_and_cull_unresponsive_connections(
                 peer_liveness_stats
                     .entry(peer.peer_node_id().clone())
                     .and_modify(|item| {
-                        item.push_pop(Stats {
-                            connected: true,
-                            responsive: true,
+                        item.push_pop(
+                            Stats {
+                                connected: true,
+                                responsive: true,
+                                loop_count,
+                            },
                             loop_count,
-                        })
+                        )
                     });
+            } else if lagged {
+                // The pong may have been dropped by the lagging event stream, so this round says nothing about the
+                // peer's responsiveness
+                trace!(
+                    target: LOG_TARGET,
+                    "No pong from {} in a lagged round, not counting it as unresponsive (iter {})",
+                    peer.peer_node_id(),
+                    loop_count
+                );
             } else {
                 peer_liveness_stats
                     .entry(peer.peer_node_id().clone())
                     .and_modify(|item| {
-                        item.push_pop(Stats {
-                            connected: true,
-                            responsive: false,
+                        item.push_pop(
+                            Stats {
+                                connected: true,
+                                responsive: false,
+                                loop_count,
+                            },
                             loop_count,
-                        })
+                        )
                     });
                 if let Some(stats) = peer_liveness_stats.get(peer.peer_node_id()) {
-                    // Evaluate the last 3 entries in the stats
-                    if stats
-                        .iter()
-                        .rev()
-                        .take(3)
-                        .filter(|s| s.connected && !s.responsive)
-                        .count() >=
-                        3
-                    {
+                    let score = cull_score(stats, config);
+                    let unresponsive_rounds = consecutive_unresponsive_rounds(stats);
+                    if peer.direction() == ConnectionDirection::Inbound {
+                        if unresponsive_rounds >= config.inbound_unresponsive_rounds {
+                            disconnect_peers.push(peer.clone());
+                        } else {
+                            trace!(
+                                target: LOG_TARGET,
+                                "Inbound peer {} unresponsive for {}/{} rounds",
+                                peer.peer_node_id(),
+                                unresponsive_rounds,
+                                config.inbound_unresponsive_rounds
+                            );
+                        }
+                    } else if score >= threshold {
                         disconnect_peers.push(peer.clone());
                     } else {
                         trace!(
                             target: LOG_TARGET,
-                            "Peer {} stats - (iter, conn, resp) {:?}",
+                            "Peer {} cull score {}/{}, unresponsive for {} rounds, stats - (iter, conn, resp) {:?}",
                             peer.peer_node_id(),
+                            score,
+                            threshold,
+                            unresponsive_rounds,
                             stats.iter().map(|s|(s.loop_count, s.connected, s.responsive)).collect::<Vec<_>>(),
                         );
                     }
@@ -303,15 +799,35 @@ async fn update_stats_and_cull_unresponsive_connections(
         }
     }
 
+    let mut culled_peers = Vec::with_capacity(disconnect_peers.len());
     for peer in disconnect_peers {
         if let Some(stats) = peer_liveness_stats.get(peer.peer_node_id()) {
+            if dry_run {
+                info!(
+                    target: LOG_TARGET,
+                    "[dry run] Would disconnect {} as the peer is no longer responsive - (iter, conn, resp) {:?}",
+                    peer.peer_node_id(),
+                    stats.iter().map(|s|(s.loop_count, s.connected, s.responsive)).collect::<Vec<_>>(),
+                );
+                culled_peers.push(peer.peer_node_id().clone());
+                continue;
+            }
             debug!(
                 target: LOG_TARGET,
                 "Disconnecting {} as the peer is no longer responsive - (iter, conn, resp) {:?}",
                 peer.peer_node_id(),
                 stats.iter().map(|s|(s.loop_count, s.connected, s.responsive)).collect::<Vec<_>>(),
             );
-            if let Err(e) = peer.clone().disconnect(Minimized::No).await {
+            // Sending only fails if there are no subscribers
+            let _result = event_publisher.send(Arc::new(MonitorPeersEvent::PeerCulled {
+                node_id: peer.peer_node_id().clone(),
+                samples: stats.iter().map(Stats::to_sample).collect(),
+            }));
+            if let Err(e) = peer
+                .clone()
+                .disconnect_with_reason(Minimized::No, DisconnectReason::Unresponsive)
+                .await
+            {
                 warn!(
                     target: LOG_TARGET,
                     "Error while attempting to disconnect peer {}: {}", peer.peer_node_id(), e
@@ -319,6 +835,960 @@ async fn update_stats_and_cull_unresponsive_connections(
             }
             peer_liveness_stats.remove(peer.peer_node_id());
             trace!(target: LOG_TARGET, "Disconnected {} (iter, {})", peer.peer_node_id(), loop_count);
+            culled_peers.push(peer.peer_node_id().clone());
         }
     }
+    culled_peers
+}
+
+/// Bans the peers that echoed the nonce of a ping sent to another peer for `ban_duration`
+async fn ban_nonce_echoers(comms: &mut ConnectivityRequester, nonce_echoers: Vec<NodeId>, ban_duration: Duration) {
+    for node_id in nonce_echoers {
+        debug!(
+            target: LOG_TARGET,
+            "Banning {} for {:.0?} for echoing another peer's ping nonce", node_id, ban_duration
+        );
+        if let Err(e) = comms
+            .ban_peer_until(node_id.clone(), ban_duration, "Echoed another peer's ping nonce")
+            .await
+        {
+            warn!(target: LOG_TARGET, "Failed to ban peer {} ({})", node_id, e);
+        }
+    }
+}
+
+/// Re-dials the culled peers whose cooldown has passed at `now`. Each peer is only re-dialed once; if it has recovered
+/// it is monitored again as a new connection. A dial that is still pending is abandoned on shutdown.
+fn redial_culled_peers(
+    comms: &ConnectivityRequester,
+    shutdown_signal: &ShutdownSignal,
+    culled_peers: &mut CulledPeers,
+    now: Instant,
+) {
+    for node_id in culled_peers.take_due(now) {
+        let comms = comms.clone();
+        let mut shutdown_signal = shutdown_signal.clone();
+        task::spawn(async move {
+            tokio::select! {
+                biased;
+                _ = shutdown_signal.wait() => {
+                    debug!(target: LOG_TARGET, "Shutdown signalled, not re-dialing culled peer {}", node_id);
+                },
+                result = comms.dial_peer(node_id.clone()) => match result {
+                    Ok(_) => debug!(target: LOG_TARGET, "Re-dialed culled peer {}, it has recovered", node_id),
+                    Err(e) => debug!(
+                        target: LOG_TARGET,
+                        "Culled peer {} is still unreachable, dropping it ({})", node_id, e
+                    ),
+                },
+            }
+        });
+    }
+}
+
+/// Evicts the least recently updated peers from `peer_liveness_stats` until at most `max_tracked_peers` remain. Peers
+/// in `active_peer_node_ids` are still being monitored and are never evicted, so more peers may remain if most of them
+/// are active.
+fn evict_least_recently_updated_peers<T>(
+    peer_liveness_stats: &mut HashMap<NodeId, PeerLiveness<T>>,
+    active_peer_node_ids: &[NodeId],
+    max_tracked_peers: usize,
+) {
+    let num_to_evict = peer_liveness_stats.len().saturating_sub(max_tracked_peers);
+    if num_to_evict == 0 {
+        return;
+    }
+    let mut by_last_updated = peer_liveness_stats
+        .iter()
+        .filter(|(node_id, _)| !active_peer_node_ids.contains(node_id))
+        .map(|(node_id, stats)| (stats.last_updated, node_id.clone()))
+        .collect::<Vec<_>>();
+    by_last_updated.sort_unstable_by_key(|(last_updated, _)| *last_updated);
+    for (last_updated, node_id) in by_last_updated.into_iter().take(num_to_evict) {
+        debug!(
+            target: LOG_TARGET,
+            "Evicting liveness stats for {} (last updated in iter {}), more than {} peers are tracked",
+            node_id,
+            last_updated,
+            max_tracked_peers
+        );
+        peer_liveness_stats.remove(&node_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use rand::rngs::OsRng;
+    use tari_comms::{
+        connection_manager::PeerConnectionRequest,
+        test_utils::mocks::{
+            create_connectivity_mock,
+            create_dummy_peer_connection,
+            create_dummy_peer_connection_with_direction,
+        },
+        types::CommsPublicKey,
+    };
+    use tari_crypto::keys::PublicKey;
+    use tari_service_framework::reply_channel;
+    use tari_shutdown::Shutdown;
+    use tokio::{sync::broadcast, task};
+
+    use super::*;
+    use crate::services::{
+        liveness::{error::LivenessError, LivenessRequest, LivenessResponse, Metadata, PingPongEvent},
+        monitor_peers::PeerAnnotation,
+    };
+
+    fn pong_event(node_id: NodeId, nonce: u64) -> Arc<LivenessEvent> {
+        Arc::new(LivenessEvent::ReceivedPong(Box::new(PingPongEvent::new(
+            node_id,
+            None,
+            Metadata::new(),
+            nonce,
+        ))))
+    }
+
+    #[tokio::test]
+    async fn it_completes_the_round_promptly_when_the_event_stream_lags() {
+        let (publisher, mut liveness_events) = broadcast::channel(2);
+        let shutdown = Shutdown::new();
+        let mut shutdown_signal = shutdown.to_signal();
+        let mut peer_ping_pongs = vec![PeerPingPong {
+            expected_nonce: Some(123),
+            received_nonce: None,
+            latency: None,
+            node_id: NodeId::default(),
+        }];
+
+        // Overflow the channel so that the receiver lags and the expected pong is lost
+        for nonce in 0..5 {
+            publisher.send(pong_event(NodeId::default(), nonce)).unwrap();
+        }
+
+        let start = Instant::now();
+        let result = time::timeout(
+            Duration::from_secs(10),
+            wait_for_pongs(
+                &mut liveness_events,
+                &mut peer_ping_pongs,
+                &mut Vec::new(),
+                Duration::from_secs(60),
+                &mut shutdown_signal,
+                1,
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, PongWaitResult::Lagged);
+        // Only the grace period is waited for, not the rest of the 60s round
+        assert!(start.elapsed() < LAGGED_PONG_GRACE_PERIOD * 2);
+        assert!(peer_ping_pongs[0].received_nonce.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_completes_the_round_when_all_pongs_are_received() {
+        let (publisher, mut liveness_events) = broadcast::channel(10);
+        let shutdown = Shutdown::new();
+        let mut shutdown_signal = shutdown.to_signal();
+        let mut peer_ping_pongs = vec![PeerPingPong {
+            expected_nonce: Some(123),
+            received_nonce: None,
+            latency: None,
+            node_id: NodeId::default(),
+        }];
+
+        publisher.send(pong_event(NodeId::default(), 123)).unwrap();
+
+        let result = wait_for_pongs(
+            &mut liveness_events,
+            &mut peer_ping_pongs,
+            &mut Vec::new(),
+            Duration::from_secs(60),
+            &mut shutdown_signal,
+            1,
+        )
+        .await;
+
+        assert_eq!(result, PongWaitResult::Done);
+        assert_eq!(peer_ping_pongs[0].received_nonce, Some(123));
+    }
+
+    #[tokio::test]
+    async fn it_does_not_credit_a_pong_echoed_by_another_peer() {
+        let (publisher, mut liveness_events) = broadcast::channel(10);
+        let shutdown = Shutdown::new();
+        let mut shutdown_signal = shutdown.to_signal();
+        let honest_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let echoer_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let mut peer_ping_pongs = vec![
+            PeerPingPong {
+                expected_nonce: Some(1),
+                received_nonce: None,
+                latency: None,
+                node_id: honest_id.clone(),
+            },
+            PeerPingPong {
+                expected_nonce: Some(2),
+                received_nonce: None,
+                latency: None,
+                node_id: echoer_id.clone(),
+            },
+        ];
+
+        // The second peer echoes the first peer's nonce, and then its own
+        publisher.send(pong_event(echoer_id.clone(), 1)).unwrap();
+        publisher.send(pong_event(echoer_id.clone(), 2)).unwrap();
+
+        let mut nonce_echoers = Vec::new();
+        let result = wait_for_pongs(
+            &mut liveness_events,
+            &mut peer_ping_pongs,
+            &mut nonce_echoers,
+            Duration::from_millis(100),
+            &mut shutdown_signal,
+            1,
+        )
+        .await;
+
+        assert_eq!(result, PongWaitResult::Done);
+        assert!(peer_ping_pongs[0].received_nonce.is_none());
+        assert_eq!(peer_ping_pongs[1].received_nonce, Some(2));
+        assert_eq!(nonce_echoers, vec![echoer_id.clone()]);
+
+        let (mut comms, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        ban_nonce_echoers(&mut comms, nonce_echoers, Duration::from_secs(60)).await;
+        let banned = time::timeout(Duration::from_secs(5), async {
+            loop {
+                let banned = mock_state.take_banned_peers().await;
+                if !banned.is_empty() {
+                    break banned;
+                }
+                task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(banned.len(), 1);
+        assert_eq!(banned[0].0, echoer_id);
+        assert_eq!(banned[0].1, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn it_evicts_the_least_recently_updated_peers() {
+        let node_ids = (0..5)
+            .map(|_| NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1))
+            .collect::<Vec<_>>();
+        let mut peer_liveness_stats: HashMap<NodeId, PeerLiveness<Stats>> = HashMap::new();
+        for (loop_count, node_id) in (1u64..).zip(&node_ids) {
+            let mut stats = PeerLiveness::new(loop_count, CullPolicy::default().window);
+            stats.push_pop(
+                Stats {
+                    connected: true,
+                    responsive: false,
+                    loop_count,
+                },
+                loop_count,
+            );
+            peer_liveness_stats.insert(node_id.clone(), stats);
+        }
+        // The oldest peer is updated again, so it becomes the most recently updated
+        peer_liveness_stats.get_mut(&node_ids[0]).unwrap().push_pop(
+            Stats {
+                connected: true,
+                responsive: false,
+                loop_count: 6,
+            },
+            6,
+        );
+
+        evict_least_recently_updated_peers(&mut peer_liveness_stats, &[], 3);
+
+        assert_eq!(peer_liveness_stats.len(), 3);
+        assert!(!peer_liveness_stats.contains_key(&node_ids[1]));
+        assert!(!peer_liveness_stats.contains_key(&node_ids[2]));
+        // Retained peers keep their full history, so culling decisions are unaffected
+        let retained = peer_liveness_stats.get(&node_ids[0]).unwrap();
+        assert_eq!(retained.iter().filter(|s| s.connected && !s.responsive).count(), 2);
+        assert!(peer_liveness_stats.contains_key(&node_ids[3]));
+        assert!(peer_liveness_stats.contains_key(&node_ids[4]));
+
+        // Below the cap nothing is evicted
+        evict_least_recently_updated_peers(&mut peer_liveness_stats, &[], 3);
+        assert_eq!(peer_liveness_stats.len(), 3);
+    }
+
+    #[test]
+    fn it_does_not_evict_active_peers() {
+        let node_ids = (0..4)
+            .map(|_| NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1))
+            .collect::<Vec<_>>();
+        let mut peer_liveness_stats: HashMap<NodeId, PeerLiveness<Stats>> = HashMap::new();
+        for (loop_count, node_id) in (1u64..).zip(&node_ids) {
+            peer_liveness_stats.insert(
+                node_id.clone(),
+                PeerLiveness::new(loop_count, CullPolicy::default().window),
+            );
+        }
+
+        // The least recently updated peer is still connected, so the next oldest inactive peers are evicted instead
+        evict_least_recently_updated_peers(&mut peer_liveness_stats, &node_ids[..1], 2);
+        assert_eq!(peer_liveness_stats.len(), 2);
+        assert!(peer_liveness_stats.contains_key(&node_ids[0]));
+        assert!(peer_liveness_stats.contains_key(&node_ids[3]));
+
+        // The cap is exceeded rather than evicting peers that are being monitored
+        let active_peer_node_ids = [node_ids[0].clone(), node_ids[3].clone()];
+        evict_least_recently_updated_peers(&mut peer_liveness_stats, &active_peer_node_ids, 1);
+        assert_eq!(peer_liveness_stats.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_disconnect_unresponsive_peers_in_dry_run_mode() {
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let (conn, mut conn_requests) = create_dummy_peer_connection(node_id.clone());
+        let mut active_peer_connections = vec![conn];
+        let mut peer_liveness_stats = HashMap::new();
+        peer_liveness_stats.insert(node_id.clone(), PeerLiveness::new(0, CullPolicy::default().window));
+        let peer_ping_pongs = [PeerPingPong {
+            expected_nonce: Some(123),
+            received_nonce: None,
+            latency: None,
+            node_id: node_id.clone(),
+        }];
+
+        for loop_count in 1..=3 {
+            let culled = update_stats_and_cull_unresponsive_connections(
+                &peer_ping_pongs,
+                &mut active_peer_connections,
+                &mut peer_liveness_stats,
+                loop_count,
+                false,
+                &MonitorPeersConfig {
+                    dry_run: true,
+                    ..Default::default()
+                },
+                CullPolicy::default(),
+                &broadcast::channel(1).0,
+            )
+            .await;
+            if loop_count < 3 {
+                assert!(culled.is_empty());
+            } else {
+                assert_eq!(culled, vec![node_id.clone()]);
+            }
+        }
+
+        // No disconnect was requested and the stats are still tracked
+        assert!(conn_requests.try_recv().is_err());
+        assert!(peer_liveness_stats.contains_key(&node_id));
+        assert_eq!(active_peer_connections[0].disconnect_reason(), None);
+    }
+
+    #[tokio::test]
+    async fn it_records_the_disconnect_reason_when_culling() {
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let (conn, mut conn_requests) = create_dummy_peer_connection(node_id.clone());
+        // Acknowledge disconnect requests like the peer connection actor would
+        task::spawn(async move {
+            while let Some(request) = conn_requests.recv().await {
+                if let PeerConnectionRequest::Disconnect(_, reply_tx, ..) = request {
+                    let _result = reply_tx.send(Ok(()));
+                }
+            }
+        });
+        let mut active_peer_connections = vec![conn.clone()];
+        let mut peer_liveness_stats = HashMap::new();
+        peer_liveness_stats.insert(node_id.clone(), PeerLiveness::new(0, CullPolicy::default().window));
+        let peer_ping_pongs = [PeerPingPong {
+            expected_nonce: Some(123),
+            received_nonce: None,
+            latency: None,
+            node_id: node_id.clone(),
+        }];
+
+        let mut culled = vec![];
+        for loop_count in 1..=3 {
+            culled.extend(
+                update_stats_and_cull_unresponsive_connections(
+                    &peer_ping_pongs,
+                    &mut active_peer_connections,
+                    &mut peer_liveness_stats,
+                    loop_count,
+                    false,
+                    &MonitorPeersConfig::default(),
+                    CullPolicy::default(),
+                    &broadcast::channel(1).0,
+                )
+                .await,
+            );
+        }
+
+        assert_eq!(culled, vec![node_id.clone()]);
+        assert_eq!(conn.disconnect_reason(), Some(DisconnectReason::Unresponsive));
+        assert!(!peer_liveness_stats.contains_key(&node_id));
+    }
+
+    #[tokio::test]
+    async fn it_publishes_an_event_when_culling_a_peer() {
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let (conn, mut conn_requests) = create_dummy_peer_connection(node_id.clone());
+        task::spawn(async move {
+            while let Some(request) = conn_requests.recv().await {
+                if let PeerConnectionRequest::Disconnect(_, reply_tx, ..) = request {
+                    let _result = reply_tx.send(Ok(()));
+                }
+            }
+        });
+        let (event_publisher, mut events) = broadcast::channel(10);
+        let mut active_peer_connections = vec![conn];
+        let mut peer_liveness_stats = HashMap::new();
+        peer_liveness_stats.insert(node_id.clone(), PeerLiveness::new(0, CullPolicy::default().window));
+        let peer_ping_pongs = [PeerPingPong {
+            expected_nonce: Some(123),
+            received_nonce: None,
+            latency: None,
+            node_id: node_id.clone(),
+        }];
+
+        for loop_count in 1..=3 {
+            update_stats_and_cull_unresponsive_connections(
+                &peer_ping_pongs,
+                &mut active_peer_connections,
+                &mut peer_liveness_stats,
+                loop_count,
+                false,
+                &MonitorPeersConfig::default(),
+                CullPolicy::default(),
+                &event_publisher,
+            )
+            .await;
+        }
+
+        let event = events.try_recv().unwrap();
+        let samples = (1..=3)
+            .map(|round| LivenessSample {
+                round,
+                connected: true,
+                responsive: false,
+            })
+            .collect();
+        assert_eq!(*event, MonitorPeersEvent::PeerCulled { node_id, samples });
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn it_redials_culled_peers_once_after_the_cooldown() {
+        let (comms, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let shutdown = Shutdown::new();
+        let redial_after = Duration::from_secs(60);
+        let mut culled_peers = CulledPeers::new(redial_after);
+        let culled_at = Instant::now();
+        culled_peers.insert(node_id.clone(), culled_at);
+
+        // Still cooling down
+        redial_culled_peers(
+            &comms,
+            &shutdown.to_signal(),
+            &mut culled_peers,
+            culled_at + redial_after / 2,
+        );
+        task::yield_now().await;
+        assert!(mock_state.get_dialed_peers().await.is_empty());
+
+        redial_culled_peers(
+            &comms,
+            &shutdown.to_signal(),
+            &mut culled_peers,
+            culled_at + redial_after + Duration::from_secs(1),
+        );
+        mock_state.await_call_count(1).await;
+        assert_eq!(mock_state.get_dialed_peers().await, vec![node_id]);
+
+        // The peer is only re-dialed once
+        assert!(culled_peers.take_due(culled_at + redial_after * 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_does_not_redial_culled_peers_after_shutdown() {
+        let (comms, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let mut shutdown = Shutdown::new();
+        let redial_after = Duration::from_secs(60);
+        let mut culled_peers = CulledPeers::new(redial_after);
+        let culled_at = Instant::now();
+        culled_peers.insert(node_id, culled_at);
+
+        shutdown.trigger();
+        redial_culled_peers(
+            &comms,
+            &shutdown.to_signal(),
+            &mut culled_peers,
+            culled_at + redial_after + Duration::from_secs(1),
+        );
+        task::yield_now().await;
+        assert!(mock_state.get_dialed_peers().await.is_empty());
+    }
+
+    /// Runs rounds in which the peer is not connected in the first `disconnected_rounds` rounds and connected but
+    /// unresponsive afterwards, returning the round in which it is first culled
+    async fn first_culled_round(
+        config: &MonitorPeersConfig,
+        cull_policy: CullPolicy,
+        disconnected_rounds: u64,
+    ) -> Option<u64> {
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let (conn, _conn_requests) = create_dummy_peer_connection(node_id.clone());
+        let config = MonitorPeersConfig {
+            dry_run: true,
+            ..config.clone()
+        };
+        let mut peer_liveness_stats = HashMap::new();
+        peer_liveness_stats.insert(node_id.clone(), PeerLiveness::new(0, cull_policy.window));
+
+        for loop_count in 1..=10 {
+            if loop_count <= disconnected_rounds {
+                peer_liveness_stats.get_mut(&node_id).unwrap().push_pop(
+                    Stats {
+                        connected: false,
+                        responsive: false,
+                        loop_count,
+                    },
+                    loop_count,
+                );
+                continue;
+            }
+            let mut active_peer_connections = vec![conn.clone()];
+            let peer_ping_pongs = [PeerPingPong {
+                expected_nonce: Some(loop_count),
+                received_nonce: None,
+                latency: None,
+                node_id: node_id.clone(),
+            }];
+            let culled = update_stats_and_cull_unresponsive_connections(
+                &peer_ping_pongs,
+                &mut active_peer_connections,
+                &mut peer_liveness_stats,
+                loop_count,
+                false,
+                &config,
+                cull_policy,
+                &broadcast::channel(1).0,
+            )
+            .await;
+            if culled.contains(&node_id) {
+                return Some(loop_count);
+            }
+        }
+        None
+    }
+
+    #[tokio::test]
+    async fn it_counts_disconnected_rounds_towards_culling_with_a_lower_weight() {
+        let config = MonitorPeersConfig::default();
+        let cull_policy = CullPolicy::default();
+
+        // Three unresponsive rounds reach the cull score of the policy
+        assert_eq!(first_culled_round(&config, cull_policy, 0).await, Some(3));
+        // Three dropped connections weigh as much as one unresponsive round, so the peer is culled one round sooner
+        assert_eq!(first_culled_round(&config, cull_policy, 3).await, Some(5));
+        // Without a weight, dropped connections do not count at all
+        let unweighted = MonitorPeersConfig {
+            disconnected_weight: 0,
+            ..Default::default()
+        };
+        assert_eq!(first_culled_round(&unweighted, cull_policy, 3).await, Some(6));
+    }
+
+    #[tokio::test]
+    async fn it_requires_more_consecutive_misses_to_cull_inbound_peers() {
+        let inbound_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let outbound_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let (inbound_conn, _inbound_requests) =
+            create_dummy_peer_connection_with_direction(inbound_id.clone(), ConnectionDirection::Inbound);
+        let (outbound_conn, _outbound_requests) =
+            create_dummy_peer_connection_with_direction(outbound_id.clone(), ConnectionDirection::Outbound);
+        let config = MonitorPeersConfig {
+            dry_run: true,
+            monitor_inbound: true,
+            ..Default::default()
+        };
+        let mut peer_liveness_stats = HashMap::new();
+        peer_liveness_stats.insert(inbound_id.clone(), PeerLiveness::new(0, CullPolicy::default().window));
+        peer_liveness_stats.insert(outbound_id.clone(), PeerLiveness::new(0, CullPolicy::default().window));
+
+        let mut first_culled = HashMap::new();
+        for loop_count in 1..=7 {
+            let mut active_peer_connections = vec![inbound_conn.clone(), outbound_conn.clone()];
+            let peer_ping_pongs = active_peer_connections
+                .iter()
+                .map(|conn| PeerPingPong {
+                    expected_nonce: Some(loop_count),
+                    // The inbound peer responds in the second round, resetting its consecutive misses
+                    received_nonce: (loop_count == 2 && conn.peer_node_id() == &inbound_id).then_some(loop_count),
+                    latency: None,
+                    node_id: conn.peer_node_id().clone(),
+                })
+                .collect::<Vec<_>>();
+
+            let culled = update_stats_and_cull_unresponsive_connections(
+                &peer_ping_pongs,
+                &mut active_peer_connections,
+                &mut peer_liveness_stats,
+                loop_count,
+                false,
+                &config,
+                CullPolicy::default(),
+                &broadcast::channel(1).0,
+            )
+            .await;
+            for node_id in culled {
+                first_culled.entry(node_id).or_insert(loop_count);
+            }
+        }
+
+        // The outbound peer reaches the cull score after three misses, whereas the inbound peer is only culled after
+        // five consecutive misses following its response
+        assert_eq!(first_culled.get(&outbound_id), Some(&3));
+        assert_eq!(first_culled.get(&inbound_id), Some(&7));
+    }
+
+    #[tokio::test]
+    async fn it_culls_outbound_peers_after_the_configured_consecutive_failures() {
+        let config = MonitorPeersConfig::default();
+        for consecutive_failures in [1, 2, 5] {
+            let cull_policy = CullPolicy {
+                window: 5,
+                consecutive_failures,
+            };
+            assert_eq!(
+                first_culled_round(&config, cull_policy, 0).await,
+                Some(consecutive_failures as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn it_only_keeps_the_window_of_liveness_stats() {
+        let mut stats = PeerLiveness::new(0, 5);
+        for loop_count in 1..=7 {
+            stats.push_pop(
+                Stats {
+                    connected: true,
+                    responsive: false,
+                    loop_count,
+                },
+                loop_count,
+            );
+        }
+        assert_eq!(stats.iter().map(|s| s.loop_count).collect::<Vec<_>>(), vec![3, 4, 5, 6, 7]);
+    }
+
+    #[tokio::test]
+    async fn it_times_out_a_stalled_round_and_continues() {
+        let (comms, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        mock_state.set_hang_active_connections(true).await;
+        let (liveness_requester, _liveness_requests) = reply_channel::unbounded();
+        let (event_publisher, _) = broadcast::channel(10);
+        let mut shutdown = Shutdown::new();
+        let service = MonitorPeersService::new(
+            comms,
+            LivenessHandle::new(liveness_requester, event_publisher),
+            shutdown.to_signal(),
+            Duration::from_millis(20),
+            MonitorPeersConfig::default(),
+        );
+        let service_task = task::spawn(service.run());
+
+        // Rounds start every 200ms and the stalled request is abandoned after 100ms, so several rounds are attempted
+        time::sleep(Duration::from_millis(700)).await;
+        assert!(mock_state.count_calls_containing("GetActiveConnections").await >= 3);
+
+        shutdown.trigger();
+        time::timeout(Duration::from_secs(1), service_task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_disconnects_the_monitored_peers_on_shutdown() {
+        let (comms, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        let node_id = NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let (conn, mut conn_requests) =
+            create_dummy_peer_connection_with_direction(node_id.clone(), ConnectionDirection::Outbound);
+        mock_state.add_active_connection(conn.clone()).await;
+        // Acknowledge disconnect requests like the peer connection actor would
+        task::spawn(async move {
+            while let Some(request) = conn_requests.recv().await {
+                if let PeerConnectionRequest::Disconnect(_, reply_tx, ..) = request {
+                    let _result = reply_tx.send(Ok(()));
+                }
+            }
+        });
+        // A liveness backend that sends the pings, but the peer never responds
+        let (liveness_requester, mut liveness_requests) = reply_channel::unbounded();
+        task::spawn(async move {
+            while let Some(request_context) = liveness_requests.next().await {
+                let (request, reply_tx) = request_context.split();
+                if let LivenessRequest::SendPings(node_ids) = request {
+                    let nonces = (0u64..).take(node_ids.len()).collect();
+                    let _result = reply_tx.send(Ok(LivenessResponse::Ok(Some(nonces))));
+                }
+            }
+        });
+        let (event_publisher, _) = broadcast::channel(10);
+        let (snapshot_tx, mut snapshot_rx) = watch::channel(MonitorPeersSnapshot::default());
+        let mut shutdown = Shutdown::new();
+        let service = MonitorPeersService::new(
+            comms,
+            LivenessHandle::new(liveness_requester, event_publisher),
+            shutdown.to_signal(),
+            Duration::from_millis(20),
+            MonitorPeersConfig {
+                disconnect_monitored_on_shutdown: true,
+                ..Default::default()
+            },
+        )
+        .with_snapshots(snapshot_tx);
+        let service_task = task::spawn(service.run());
+
+        // Wait for the peer to be monitored in the first round
+        time::timeout(Duration::from_secs(5), snapshot_rx.wait_for(|s| s.round >= 1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(conn.disconnect_reason(), None);
+
+        shutdown.trigger();
+        time::timeout(Duration::from_secs(5), service_task)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(conn.disconnect_reason(), Some(DisconnectReason::Shutdown));
+    }
+
+    #[tokio::test]
+    async fn it_monitors_the_remaining_peers_when_a_ping_cannot_be_sent() {
+        let (comms, mock) = create_connectivity_mock();
+        let mock_state = mock.spawn();
+        let node_ids = (0..3)
+            .map(|_| NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1))
+            .collect::<Vec<_>>();
+        let mut conn_requests = Vec::new();
+        for node_id in &node_ids {
+            let (conn, rx) =
+                create_dummy_peer_connection_with_direction(node_id.clone(), ConnectionDirection::Outbound);
+            mock_state.add_active_connection(conn).await;
+            conn_requests.push(rx);
+        }
+        // A liveness backend that fails to send any request that includes the unreachable peer, and otherwise sends
+        // the pings and receives the pongs
+        let unreachable = node_ids[0].clone();
+        let (event_publisher, _) = broadcast::channel(10);
+        let (liveness_requester, mut liveness_requests) = reply_channel::unbounded();
+        let publisher = event_publisher.clone();
+        task::spawn(async move {
+            let mut next_nonce = 0u64;
+            while let Some(request_context) = liveness_requests.next().await {
+                let (request, reply_tx) = request_context.split();
+                if let LivenessRequest::SendPings(node_ids) = request {
+                    if node_ids.contains(&unreachable) {
+                        let _result = reply_tx.send(Err(LivenessError::SendPingFailed));
+                        continue;
+                    }
+                    let nonces = (next_nonce..).take(node_ids.len()).collect::<Vec<_>>();
+                    next_nonce += nonces.len() as u64;
+                    let _result = reply_tx.send(Ok(LivenessResponse::Ok(Some(nonces.clone()))));
+                    for (node_id, nonce) in node_ids.into_iter().zip(nonces) {
+                        let _result = publisher.send(pong_event(node_id, nonce));
+                    }
+                }
+            }
+        });
+        let (snapshot_tx, mut snapshot_rx) = watch::channel(MonitorPeersSnapshot::default());
+        let mut shutdown = Shutdown::new();
+        let service = MonitorPeersService::new(
+            comms,
+            LivenessHandle::new(liveness_requester, event_publisher),
+            shutdown.to_signal(),
+            Duration::from_millis(20),
+            MonitorPeersConfig::default(),
+        )
+        .with_snapshots(snapshot_tx);
+        let service_task = task::spawn(service.run());
+
+        let snapshot = time::timeout(Duration::from_secs(5), snapshot_rx.wait_for(|s| s.round >= 1))
+            .await
+            .unwrap()
+            .unwrap()
+            .clone();
+        assert_eq!(snapshot.peers.len(), 3);
+        for peer in &snapshot.peers {
+            assert_eq!(peer.responsive, peer.node_id != node_ids[0], "{:?}", peer);
+        }
+
+        shutdown.trigger();
+        time::timeout(Duration::from_secs(5), service_task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_reports_an_unhealthy_network_when_there_are_no_peers_to_monitor() {
+        let (comms, mock) = create_connectivity_mock();
+        let _mock_state = mock.spawn();
+        let (liveness_requester, _liveness_requests) = reply_channel::unbounded();
+        let (network_health_tx, mut network_health_rx) = watch::channel(NetworkHealth::default());
+        let mut shutdown = Shutdown::new();
+        let service = MonitorPeersService::new(
+            comms,
+            LivenessHandle::new(liveness_requester, broadcast::channel(1).0),
+            shutdown.to_signal(),
+            Duration::from_millis(20),
+            MonitorPeersConfig::default(),
+        )
+        .with_network_health(network_health_tx);
+        let service_task = task::spawn(service.run());
+
+        let health = *time::timeout(Duration::from_secs(5), network_health_rx.wait_for(|h| h.round >= 1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(health.network_unhealthy);
+        assert!(health.responsive_ratio.abs() < f64::EPSILON);
+
+        shutdown.trigger();
+        time::timeout(Duration::from_secs(5), service_task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn it_annotates_the_peers_in_the_round_snapshot() {
+        let ping_pong = |nonce, latency: Option<u64>| PeerPingPong {
+            expected_nonce: Some(nonce),
+            received_nonce: latency.map(|_| nonce),
+            latency: latency.map(Duration::from_millis),
+            node_id: NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1),
+        };
+        let peer_ping_pongs = [ping_pong(1, Some(100)), ping_pong(2, None)];
+        let addresses = HashMap::from([
+            (
+                peer_ping_pongs[0].node_id.clone(),
+                "/ip4/10.0.0.1/tcp/18189".parse::<Multiaddr>().unwrap(),
+            ),
+            (
+                peer_ping_pongs[1].node_id.clone(),
+                "/ip4/192.168.0.1/tcp/18189".parse::<Multiaddr>().unwrap(),
+            ),
+        ]);
+        let lookup = PeerAnnotationLookup::new(|address| {
+            address.to_string().starts_with("/ip4/10.").then(|| PeerAnnotation {
+                region: Some("EU".to_string()),
+                asn: Some(64512),
+            })
+        });
+
+        let snapshot = round_snapshot(3, &peer_ping_pongs, &addresses, Some(&lookup));
+        assert_eq!(snapshot.round, 3);
+        assert_eq!(snapshot.peers.len(), 2);
+        assert_eq!(
+            snapshot.peers[0].annotation,
+            Some(PeerAnnotation {
+                region: Some("EU".to_string()),
+                asn: Some(64512),
+            })
+        );
+        assert!(snapshot.peers[0].responsive);
+        assert_eq!(snapshot.peers[0].latency, Some(Duration::from_millis(100)));
+        // The lookup did not recognise this address
+        assert_eq!(snapshot.peers[1].annotation, None);
+        assert!(!snapshot.peers[1].responsive);
+
+        // Without a lookup, there are no annotations
+        let snapshot = round_snapshot(3, &peer_ping_pongs, &addresses, None);
+        assert!(snapshot.peers.iter().all(|p| p.annotation.is_none()));
+    }
+
+    #[test]
+    fn it_flips_network_unhealthy_when_the_responsive_ratio_drops_below_the_minimum() {
+        let ping_pongs = |num_responsive: usize, num_peers: usize| {
+            (0..num_peers)
+                .map(|i| PeerPingPong {
+                    expected_nonce: Some(i as u64),
+                    received_nonce: (i < num_responsive).then_some(i as u64),
+                    latency: None,
+                    node_id: NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1),
+                })
+                .collect::<Vec<_>>()
+        };
+        let min_responsive_ratio = MonitorPeersConfig::default().min_responsive_ratio;
+        let (network_health_tx, network_health_rx) = watch::channel(NetworkHealth::default());
+
+        network_health_tx.send_replace(network_health(1, &ping_pongs(3, 4), min_responsive_ratio));
+        let health = *network_health_rx.borrow();
+        assert_eq!(health.round, 1);
+        assert!((health.responsive_ratio - 0.75).abs() < f64::EPSILON);
+        assert!(!health.network_unhealthy);
+
+        network_health_tx.send_replace(network_health(2, &ping_pongs(1, 4), min_responsive_ratio));
+        let health = *network_health_rx.borrow();
+        assert!((health.responsive_ratio - 0.25).abs() < f64::EPSILON);
+        assert!(health.network_unhealthy);
+
+        // Exactly at the minimum is healthy
+        network_health_tx.send_replace(network_health(3, &ping_pongs(2, 4), min_responsive_ratio));
+        assert!(!network_health_rx.borrow().network_unhealthy);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn it_publishes_the_round_outcome_as_metrics() {
+        let metric = |name: &str| {
+            tari_metrics::get_default_registry()
+                .gather()
+                .into_iter()
+                .find(|f| f.get_name() == name)
+                .unwrap_or_else(|| panic!("{} was not registered", name))
+                .get_metric()[0]
+                .clone()
+        };
+        let ping_pong = |nonce, latency: Option<u64>| PeerPingPong {
+            expected_nonce: Some(nonce),
+            received_nonce: latency.map(|_| nonce),
+            latency: latency.map(Duration::from_millis),
+            node_id: NodeId::from_public_key(&CommsPublicKey::random_keypair(&mut OsRng).1),
+        };
+        let peer_ping_pongs = [ping_pong(1, Some(100)), ping_pong(2, Some(300)), ping_pong(3, None)];
+
+        // The meters are registered once in the default registry and are shared with the other tests, so the
+        // counter and histogram are compared against their values before the round
+        update_round_metrics(&[], 0, 0);
+        let culled_before = metric("monitor_peers_culled_total").get_counter().get_value();
+        let pong_rtt_before = metric("monitor_peers_pong_rtt_seconds").get_histogram().clone();
+
+        update_round_metrics(&peer_ping_pongs, 3, 1);
+
+        assert_eq!(metric("monitor_peers_active").get_gauge().get_value(), 3.0);
+        assert_eq!(metric("monitor_peers_unresponsive").get_gauge().get_value(), 1.0);
+        assert_eq!(
+            metric("monitor_peers_culled_total").get_counter().get_value() - culled_before,
+            1.0
+        );
+        let pong_rtt = metric("monitor_peers_pong_rtt_seconds").get_histogram().clone();
+        assert_eq!(pong_rtt.get_sample_count() - pong_rtt_before.get_sample_count(), 2);
+        assert!((pong_rtt.get_sample_sum() - pong_rtt_before.get_sample_sum() - 0.4).abs() < 1e-9);
+    }
 }
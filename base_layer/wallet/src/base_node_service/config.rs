@@ -22,6 +22,7 @@
 
 use std::time::Duration;
 
+use log::Level;
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::serializers;
 
@@ -35,6 +36,37 @@ pub struct BaseNodeServiceConfig {
     pub base_node_rpc_pool_size: usize,
     /// This is the size of the event channel used to communicate base node events to the wallet
     pub event_channel_size: usize,
+    /// How often the base node connection and RPC pools are checked, at least 1s
+    #[serde(with = "serializers::seconds")]
+    pub connection_check_interval: Duration,
+    /// Fall back to the seed peers when none of the configured base nodes can be connected to. The preferred base node
+    /// is reconnected to once it is reachable again.
+    pub allow_seed_fallback: bool,
+    /// The number of failed connection attempts after which the seed peers are tried
+    pub seed_fallback_threshold: usize,
+    /// The number of consecutive RPC pool errors after which the base node connection is torn down, at least 1
+    pub rpc_error_disconnect_threshold: usize,
+    /// The log level of the one-line summary logged for each base node connection attempt
+    #[serde(with = "log_level")]
+    pub connection_attempt_log_level: Level,
+    /// The order in which the base node peers are tried at the start of each connection cycle. A base node set by the
+    /// user is still tried first.
+    pub base_node_selection: BaseNodeSelection,
+    /// Reject base nodes that report a different network than the wallet's after connecting. A rejected base node is
+    /// skipped for a while as long as other base node peers are available.
+    pub check_base_node_network: bool,
+    /// How long to wait before retrying the connection when there is only one base node peer
+    #[serde(with = "serializers::seconds")]
+    pub connectivity_retry_wait: Duration,
+    /// The retry wait is randomly varied by up to this fraction of it either way (0.0 to 1.0), so that many wallets
+    /// retrying a sole base node do not do so in lockstep
+    pub connectivity_retry_jitter: f64,
+    /// The maximum number of wallet RPC client requests that wait for a base node connection. Further requests are
+    /// dropped once canceled requests have been evicted.
+    pub max_pending_wallet_rpc_requests: usize,
+    /// The maximum number of sync RPC client requests that wait for a base node connection, independent of the
+    /// wallet RPC requests
+    pub max_pending_sync_rpc_requests: usize,
 }
 
 impl Default for BaseNodeServiceConfig {
@@ -43,6 +75,47 @@ impl Default for BaseNodeServiceConfig {
             base_node_monitor_max_refresh_interval: Duration::from_secs(30),
             base_node_rpc_pool_size: 10,
             event_channel_size: 250,
+            connection_check_interval: Duration::from_secs(5),
+            allow_seed_fallback: false,
+            seed_fallback_threshold: 10,
+            rpc_error_disconnect_threshold: 3,
+            connection_attempt_log_level: Level::Info,
+            base_node_selection: BaseNodeSelection::Ordered,
+            check_base_node_network: true,
+            connectivity_retry_wait: Duration::from_secs(5),
+            connectivity_retry_jitter: 0.2,
+            max_pending_wallet_rpc_requests: 100,
+            max_pending_sync_rpc_requests: 100,
         }
     }
 }
+
+/// The order in which the base node peers are tried when connecting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BaseNodeSelection {
+    /// Start at the current peer and try the peers in list order
+    #[default]
+    Ordered,
+    /// Shuffle the peers for every connection cycle, so that many wallets do not all connect to the first peer
+    Random,
+    /// Try the peers with the lowest connection latency first; peers that have not been connected to come last
+    LatencySorted,
+}
+
+mod log_level {
+    use std::str::FromStr;
+
+    use log::Level;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Level, D::Error>
+    where D: Deserializer<'de> {
+        let level = String::deserialize(deserializer)?;
+        Level::from_str(&level).map_err(|_| D::Error::custom(format!("Invalid log level '{}'", level)))
+    }
+
+    pub fn serialize<S>(level: &Level, s: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        s.serialize_str(level.as_str())
+    }
+}
@@ -24,7 +24,7 @@ use std::{fmt, fmt::Formatter, sync::Arc, time::Duration};
 
 use tari_common_types::{chain_metadata::ChainMetadata, types::BlockHash};
 use tari_service_framework::reply_channel::SenderService;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 use tower::Service;
 
 use super::{error::BaseNodeServiceError, service::BaseNodeState};
@@ -68,16 +68,20 @@ impl fmt::Display for BaseNodeEvent {
 pub struct BaseNodeServiceHandle {
     handle: SenderService<BaseNodeServiceRequest, Result<BaseNodeServiceResponse, BaseNodeServiceError>>,
     event_stream_sender: BaseNodeEventSender,
+    tip_height_watch: watch::Receiver<Option<u64>>,
 }
 
 impl BaseNodeServiceHandle {
+    /// `tip_height_watch` receives the tip height of the connected base node, as published by the base node service
     pub fn new(
         handle: SenderService<BaseNodeServiceRequest, Result<BaseNodeServiceResponse, BaseNodeServiceError>>,
         event_stream_sender: BaseNodeEventSender,
+        tip_height_watch: watch::Receiver<Option<u64>>,
     ) -> Self {
         Self {
             handle,
             event_stream_sender,
+            tip_height_watch,
         }
     }
 
@@ -85,6 +89,12 @@ impl BaseNodeServiceHandle {
         self.event_stream_sender.subscribe()
     }
 
+    /// Returns a watch of the latest tip height of the connected base node, refreshed by the periodic metadata query.
+    /// The value is `None` until the first query completes and while no base node is connected.
+    pub fn get_tip_height_watcher(&self) -> watch::Receiver<Option<u64>> {
+        self.tip_height_watch.clone()
+    }
+
     pub async fn get_chain_metadata(&mut self) -> Result<Option<ChainMetadata>, BaseNodeServiceError> {
         match self.handle.call(BaseNodeServiceRequest::GetChainMetadata).await?? {
             BaseNodeServiceResponse::ChainMetadata(metadata) => Ok(metadata),
@@ -35,7 +35,7 @@ use tari_service_framework::{
     ServiceInitializer,
     ServiceInitializerContext,
 };
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 
 use crate::{
     base_node_service::{config::BaseNodeServiceConfig, handle::BaseNodeServiceHandle, service::BaseNodeService},
@@ -71,7 +71,9 @@ where T: WalletBackend + 'static
 
         let (event_publisher, _) = broadcast::channel(self.config.event_channel_size);
 
-        let basenode_service_handle = BaseNodeServiceHandle::new(sender, event_publisher.clone());
+        let (tip_height_publisher, tip_height_watch) = watch::channel(None);
+
+        let basenode_service_handle = BaseNodeServiceHandle::new(sender, event_publisher.clone(), tip_height_watch);
 
         // Register handle before waiting for handles to be ready
         context.register_handle(basenode_service_handle);
@@ -89,6 +91,7 @@ where T: WalletBackend + 'static
                 event_publisher,
                 handles.get_shutdown_signal(),
                 db,
+                tip_height_publisher,
             )
             .start()
             .await;
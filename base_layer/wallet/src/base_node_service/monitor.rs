@@ -36,7 +36,10 @@ use tari_comms::{
     backoff::{Backoff, ExponentialBackoff},
     protocol::rpc::RpcError,
 };
-use tokio::{sync::RwLock, time};
+use tokio::{
+    sync::{watch, RwLock},
+    time,
+};
 
 use crate::{
     base_node_service::{
@@ -58,6 +61,7 @@ pub struct BaseNodeMonitor<TBackend, TWalletConnectivity> {
     db: WalletDatabase<TBackend>,
     wallet_connectivity: TWalletConnectivity,
     event_publisher: BaseNodeEventSender,
+    tip_height_publisher: watch::Sender<Option<u64>>,
 }
 
 impl<TBackend, TWalletConnectivity> BaseNodeMonitor<TBackend, TWalletConnectivity>
@@ -71,6 +75,7 @@ where
         db: WalletDatabase<TBackend>,
         wallet_connectivity: TWalletConnectivity,
         event_publisher: BaseNodeEventSender,
+        tip_height_publisher: watch::Sender<Option<u64>>,
     ) -> Self {
         Self {
             max_interval,
@@ -80,6 +85,7 @@ where
             db,
             wallet_connectivity,
             event_publisher,
+            tip_height_publisher,
         }
     }
 
@@ -224,6 +230,13 @@ where
             self.publish_event(BaseNodeEvent::NewBlockDetected(hash, height));
         }
 
+        let tip_height = new_state.chain_metadata.as_ref().map(|m| m.best_block_height());
+        self.tip_height_publisher.send_if_modified(|current| {
+            let modified = *current != tip_height;
+            *current = tip_height;
+            modified
+        });
+
         *lock = new_state.clone();
 
         self.publish_event(BaseNodeEvent::BaseNodeStateChanged(new_state));
@@ -259,3 +272,89 @@ where
         Either::Right((v, _)) => Some(v),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use tari_common_types::types::FixedHash;
+    use tari_comms::{
+        peer_manager::PeerFeatures,
+        protocol::rpc::mock::{MockRpcImpl, MockRpcServer},
+        test_utils::node_identity::build_node_identity,
+    };
+    use tari_core::{base_node::rpc::BaseNodeWalletRpcClient, proto::base_node::TipInfoResponse};
+    use tari_service_framework::reply_channel;
+    use tari_utilities::SafePassword;
+    use tokio::{sync::broadcast, task, time::timeout};
+
+    use super::*;
+    use crate::{
+        base_node_service::handle::BaseNodeServiceHandle,
+        connectivity_service::{create_wallet_connectivity_mock, BaseNodePeerManager},
+        storage::sqlite_db::wallet::WalletSqliteDatabase,
+        test_utils::make_wallet_database_memory_connection,
+    };
+
+    // The 'get_tip_info' method of the base node wallet RPC service
+    const GET_TIP_INFO_METHOD: u32 = 5;
+
+    fn tip_info(height: u64) -> TipInfoResponse {
+        TipInfoResponse {
+            metadata: Some(
+                ChainMetadata::new(height, FixedHash::zero(), 0, 0, 1.into(), 0)
+                    .unwrap()
+                    .into(),
+            ),
+            is_synced: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_publishes_the_tip_height_of_the_base_node() {
+        let mock_svc = MockRpcImpl::new();
+        mock_svc.set_response(GET_TIP_INFO_METHOD, tip_info(10)).await;
+        let server_identity = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+        let mut mock_server = MockRpcServer::new(mock_svc.clone(), server_identity);
+        mock_server.serve();
+        let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+        let mut conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+        let client = conn.connect_rpc::<BaseNodeWalletRpcClient>().await.unwrap();
+        let wallet_connectivity = create_wallet_connectivity_mock();
+        wallet_connectivity.notify_base_node_set(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+        wallet_connectivity.set_base_node_wallet_rpc_client(client);
+
+        let connection = make_wallet_database_memory_connection();
+        let db = WalletDatabase::new(WalletSqliteDatabase::new(connection, SafePassword::from("password")).unwrap());
+        let (event_publisher, _) = broadcast::channel(10);
+        let (tip_height_publisher, tip_height_watch) = watch::channel(None);
+        let (sender, _) = reply_channel::unbounded();
+        let handle = BaseNodeServiceHandle::new(sender, event_publisher.clone(), tip_height_watch);
+        let monitor = BaseNodeMonitor::new(
+            Duration::from_secs(1),
+            Default::default(),
+            db,
+            wallet_connectivity.clone(),
+            event_publisher,
+            tip_height_publisher,
+        );
+        let mut tip_height_watch = handle.get_tip_height_watcher();
+        assert_eq!(*tip_height_watch.borrow(), None);
+        task::spawn(monitor.run());
+
+        for height in [10, 11] {
+            mock_svc.set_response(GET_TIP_INFO_METHOD, tip_info(height)).await;
+            timeout(Duration::from_secs(10), tip_height_watch.wait_for(|h| *h == Some(height)))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        // The tip height is cleared when the base node changes, and stays cleared until the new one is connected
+        wallet_connectivity.send_shutdown();
+        let other_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+        wallet_connectivity.notify_base_node_set(BaseNodePeerManager::new(0, vec![other_peer.to_peer()]).unwrap());
+        timeout(Duration::from_secs(10), tip_height_watch.wait_for(|h| h.is_none()))
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
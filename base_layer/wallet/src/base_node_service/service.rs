@@ -29,7 +29,7 @@ use tari_common_types::chain_metadata::ChainMetadata;
 use tari_comms::peer_manager::NodeId;
 use tari_service_framework::reply_channel::Receiver;
 use tari_shutdown::ShutdownSignal;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 
 use super::{
     config::BaseNodeServiceConfig,
@@ -65,6 +65,7 @@ where T: WalletBackend + 'static
     shutdown_signal: ShutdownSignal,
     state: Arc<RwLock<BaseNodeState>>,
     db: WalletDatabase<T>,
+    tip_height_publisher: watch::Sender<Option<u64>>,
 }
 
 impl<T> BaseNodeService<T>
@@ -77,6 +78,7 @@ where T: WalletBackend + 'static
         event_publisher: BaseNodeEventSender,
         shutdown_signal: ShutdownSignal,
         db: WalletDatabase<T>,
+        tip_height_publisher: watch::Sender<Option<u64>>,
     ) -> Self {
         Self {
             config,
@@ -86,6 +88,7 @@ where T: WalletBackend + 'static
             shutdown_signal,
             state: Default::default(),
             db,
+            tip_height_publisher,
         }
     }
 
@@ -131,6 +134,7 @@ where T: WalletBackend + 'static
             self.db.clone(),
             self.wallet_connectivity.clone(),
             self.event_publisher.clone(),
+            self.tip_height_publisher.clone(),
         );
 
         let shutdown_signal = self.shutdown_signal.clone();
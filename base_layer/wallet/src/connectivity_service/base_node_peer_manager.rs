@@ -37,6 +37,10 @@ pub struct BaseNodePeerManager {
     current_peer_index: usize,
     // The other base nodes that the wallet can connect to if the selected peer is not available
     peer_list: Vec<Peer>,
+    // The order in which the indexes of the peer list are tried
+    dial_order: Vec<usize>,
+    // The peer that is kept first in the dial order, if any
+    preferred_peer_index: Option<usize>,
     last_connection_attempt: Option<LastConnectionAttempt>,
 }
 
@@ -58,11 +62,54 @@ impl BaseNodePeerManager {
         }
         Ok(Self {
             current_peer_index: preferred_peer_index,
+            dial_order: (0..peer_list.len()).collect(),
+            preferred_peer_index: Some(preferred_peer_index),
             peer_list,
             last_connection_attempt: None,
         })
     }
 
+    /// Create a BaseNodePeerManager from a seed list, e.g. fetched from DNS, ignoring duplicate peers. The first seed
+    /// is the current peer, but none of the seeds is preferred over the others.
+    pub fn from_seeds(seeds: Vec<Peer>) -> Result<Self, WalletConnectivityError> {
+        let mut peer_list = Vec::with_capacity(seeds.len());
+        for seed in seeds {
+            if !peer_list.iter().any(|p: &Peer| p.node_id == seed.node_id) {
+                peer_list.push(seed);
+            }
+        }
+        if peer_list.is_empty() {
+            return Err(WalletConnectivityError::NoBaseNodePeers);
+        }
+        let mut peer_manager = Self::new(0, peer_list)?;
+        peer_manager.preferred_peer_index = None;
+        Ok(peer_manager)
+    }
+
+    /// Append the seeds that are not in the peer list yet, returning the number of peers added. The current peer and
+    /// connection attempt state are preserved, so a periodic seed refresh does not disrupt the connection.
+    pub fn merge_seeds(&mut self, seeds: Vec<Peer>) -> usize {
+        let num_peers = self.peer_list.len();
+        for seed in seeds {
+            if !self.peer_list.iter().any(|p| p.node_id == seed.node_id) {
+                self.dial_order.push(self.peer_list.len());
+                self.peer_list.push(seed);
+            }
+        }
+        self.peer_list.len() - num_peers
+    }
+
+    /// Returns true if this has the same current peer as `other` and a peer list that starts with `other`'s peer
+    /// list, i.e. it is `other` with seeds merged in
+    pub fn extends(&self, other: &BaseNodePeerManager) -> bool {
+        self.current_peer_index == other.current_peer_index &&
+            self.peer_list.len() >= other.peer_list.len() &&
+            self.peer_list
+                .iter()
+                .zip(&other.peer_list)
+                .all(|(a, b)| a.node_id == b.node_id)
+    }
+
     /// Get the current peer
     pub fn get_current_peer(&self) -> Peer {
         self.peer_list
@@ -71,12 +118,57 @@ impl BaseNodePeerManager {
             .unwrap_or(self.peer_list[0].clone())
     }
 
-    /// Get the next peer in the list
+    /// Get the preferred peer, if any
+    pub fn get_preferred_peer(&self) -> Option<Peer> {
+        self.preferred_peer_index.map(|i| self.peer_list[i].clone())
+    }
+
+    /// Make the preferred peer, if any, the current peer and reset the connection attempt state, so that it is dialed
+    /// straight away
+    pub fn select_preferred_peer(&mut self) {
+        if let Some(preferred) = self.preferred_peer_index {
+            self.current_peer_index = preferred;
+            self.last_connection_attempt = None;
+        }
+    }
+
+    /// Get the next peer in the dial order
     pub fn get_next_peer(&mut self) -> Peer {
-        self.current_peer_index = (self.current_peer_index + 1) % self.peer_list.len();
+        let position = self
+            .dial_order
+            .iter()
+            .position(|&i| i == self.current_peer_index)
+            .unwrap_or_default();
+        self.current_peer_index = self.dial_order[(position + 1) % self.dial_order.len()];
         self.peer_list[self.current_peer_index].clone()
     }
 
+    /// Returns true if the current peer is the last one in the dial order, i.e. the next peer starts a new cycle
+    pub fn is_last_in_dial_order(&self) -> bool {
+        self.dial_order.last() == Some(&self.current_peer_index)
+    }
+
+    /// Set the order in which the peers are tried, given as a permutation of the peer list indexes, and make the first
+    /// peer in that order the current peer. The preferred peer, if any, is moved to the front of the order.
+    pub fn set_dial_order(&mut self, mut dial_order: Vec<usize>) -> Result<(), WalletConnectivityError> {
+        let mut sorted = dial_order.clone();
+        sorted.sort_unstable();
+        if sorted.into_iter().ne(0..self.peer_list.len()) {
+            return Err(WalletConnectivityError::PeerIndexOutOfBounds(format!(
+                "Dial order {:?} is not a permutation of {} peers",
+                dial_order,
+                self.peer_list.len()
+            )));
+        }
+        if let Some(preferred) = self.preferred_peer_index {
+            dial_order.retain(|&i| i != preferred);
+            dial_order.insert(0, preferred);
+        }
+        self.current_peer_index = dial_order[0];
+        self.dial_order = dial_order;
+        Ok(())
+    }
+
     /// Get the base node peer manager state
     pub fn get_state(&self) -> (usize, Vec<Peer>) {
         (self.current_peer_index, self.peer_list.clone())
@@ -90,6 +182,11 @@ impl BaseNodePeerManager {
         })
     }
 
+    /// Reset the connection attempt state, so that the current peer is tried without waiting
+    pub fn reset_attempt_state(&mut self) {
+        self.last_connection_attempt = None;
+    }
+
     /// Get the last connection attempt stats
     pub fn time_since_last_connection_attempt(&self) -> Option<Duration> {
         if let Some(stats) = self.last_connection_attempt.clone() {
@@ -122,3 +219,75 @@ impl Display for BaseNodePeerManager {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use tari_comms::{peer_manager::PeerFeatures, test_utils::node_identity::build_node_identity};
+
+    use super::*;
+
+    #[test]
+    fn it_resets_the_connection_attempt_state() {
+        let peer_list = (0..2)
+            .map(|_| build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer())
+            .collect::<Vec<_>>();
+        let mut peer_manager = BaseNodePeerManager::new(0, peer_list).unwrap();
+        assert!(peer_manager.time_since_last_connection_attempt().is_none());
+
+        peer_manager.set_last_connection_attempt();
+        assert!(peer_manager.time_since_last_connection_attempt().is_some());
+
+        peer_manager.reset_attempt_state();
+        assert!(peer_manager.time_since_last_connection_attempt().is_none());
+    }
+
+    #[test]
+    fn it_merges_overlapping_seed_sets_without_duplicates() {
+        let seeds = (0..5)
+            .map(|_| build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer())
+            .collect::<Vec<_>>();
+        let node_ids = |peer_manager: &BaseNodePeerManager| {
+            peer_manager
+                .get_state()
+                .1
+                .into_iter()
+                .map(|p| p.node_id)
+                .collect::<Vec<_>>()
+        };
+
+        let mut peer_manager = BaseNodePeerManager::from_seeds(vec![
+            seeds[0].clone(),
+            seeds[1].clone(),
+            seeds[0].clone(),
+            seeds[2].clone(),
+        ])
+        .unwrap();
+        assert_eq!(node_ids(&peer_manager), vec![
+            seeds[0].node_id.clone(),
+            seeds[1].node_id.clone(),
+            seeds[2].node_id.clone()
+        ]);
+        let current = peer_manager.get_next_peer();
+        peer_manager.set_last_connection_attempt();
+        let before_merge = peer_manager.clone();
+
+        assert_eq!(peer_manager.merge_seeds(seeds[1..].to_vec()), 2);
+        assert_eq!(
+            node_ids(&peer_manager),
+            seeds.iter().map(|p| p.node_id.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(peer_manager.get_current_peer().node_id, current.node_id);
+        assert!(peer_manager.time_since_last_connection_attempt().is_some());
+        assert!(peer_manager.extends(&before_merge));
+        assert!(!before_merge.extends(&peer_manager));
+
+        // Merging known seeds changes nothing
+        assert_eq!(peer_manager.merge_seeds(seeds.clone()), 0);
+        assert_eq!(peer_manager.get_state().1.len(), seeds.len());
+
+        assert!(matches!(
+            BaseNodePeerManager::from_seeds(vec![]),
+            Err(WalletConnectivityError::NoBaseNodePeers)
+        ));
+    }
+}
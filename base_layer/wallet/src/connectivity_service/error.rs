@@ -21,6 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use futures::channel::{mpsc, oneshot};
+use tari_common::configuration::Network;
 use tari_comms::connectivity::ConnectivityError;
 
 #[derive(Debug, thiserror::Error)]
@@ -33,6 +34,10 @@ pub enum WalletConnectivityError {
     ServiceTerminated,
     #[error("Preferred peer index is out of bounds: {0}")]
     PeerIndexOutOfBounds(String),
+    #[error("No base node peers were provided")]
+    NoBaseNodePeers,
+    #[error("Base node is on network '{reported}', but the wallet is on network '{expected}'")]
+    BaseNodeNetworkMismatch { expected: Network, reported: String },
 }
 
 impl From<mpsc::SendError> for WalletConnectivityError {
@@ -20,6 +20,8 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::time::Duration;
+
 use tari_comms::{
     peer_manager::{NodeId, Peer},
     protocol::rpc::RpcClientLease,
@@ -28,7 +30,7 @@ use tari_comms::{
 use tari_core::base_node::{rpc::BaseNodeWalletRpcClient, sync::rpc::BaseNodeSyncRpcClient};
 use tokio::sync::{mpsc, oneshot, watch};
 
-use super::service::OnlineStatus;
+use super::service::{BaseNodeDetails, OnlineStatus};
 use crate::{
     connectivity_service::{BaseNodePeerManager, WalletConnectivityInterface},
     util::watch::Watch,
@@ -38,6 +40,8 @@ pub enum WalletConnectivityRequest {
     ObtainBaseNodeWalletRpcClient(oneshot::Sender<RpcClientLease<BaseNodeWalletRpcClient>>),
     ObtainBaseNodeSyncRpcClient(oneshot::Sender<RpcClientLease<BaseNodeSyncRpcClient>>),
     DisconnectBaseNode(NodeId),
+    ForceReconnect,
+    GetCurrentBaseNode(oneshot::Sender<Option<BaseNodeDetails>>),
 }
 
 #[derive(Clone)]
@@ -45,6 +49,8 @@ pub struct WalletConnectivityHandle {
     sender: mpsc::Sender<WalletConnectivityRequest>,
     base_node_watch: Watch<Option<BaseNodePeerManager>>,
     online_status_rx: watch::Receiver<OnlineStatus>,
+    ever_connected_rx: watch::Receiver<bool>,
+    time_to_first_online_rx: watch::Receiver<Option<Duration>>,
 }
 
 impl WalletConnectivityHandle {
@@ -52,20 +58,35 @@ impl WalletConnectivityHandle {
         sender: mpsc::Sender<WalletConnectivityRequest>,
         base_node_watch: Watch<Option<BaseNodePeerManager>>,
         online_status_rx: watch::Receiver<OnlineStatus>,
+        ever_connected_rx: watch::Receiver<bool>,
+        time_to_first_online_rx: watch::Receiver<Option<Duration>>,
     ) -> Self {
         Self {
             sender,
             base_node_watch,
             online_status_rx,
+            ever_connected_rx,
+            time_to_first_online_rx,
         }
     }
+
+    /// Subscribes to changes of the selected base node, e.g. so that a UI can react to base node switches. The
+    /// receiver sees the current base node, which is `None` until one is set, and is notified of every later change.
+    pub fn subscribe_base_node_changes(&self) -> watch::Receiver<Option<BaseNodePeerManager>> {
+        self.base_node_watch.subscribe()
+    }
 }
 
 #[async_trait::async_trait]
 impl WalletConnectivityInterface for WalletConnectivityHandle {
     fn set_base_node(&mut self, base_node_peer_manager: BaseNodePeerManager) {
         if let Some(selected_peer) = self.base_node_watch.borrow().as_ref() {
-            if selected_peer.get_current_peer().public_key == base_node_peer_manager.get_current_peer().public_key {
+            // Seeds merged into the peer list of the selected peer are still published
+            let adds_peers = base_node_peer_manager.extends(selected_peer) &&
+                base_node_peer_manager.get_state().1.len() > selected_peer.get_state().1.len();
+            if selected_peer.get_current_peer().public_key == base_node_peer_manager.get_current_peer().public_key &&
+                !adds_peers
+            {
                 return;
             }
         }
@@ -126,6 +147,20 @@ impl WalletConnectivityInterface for WalletConnectivityHandle {
             .await;
     }
 
+    async fn force_reconnect(&mut self) {
+        let _unused = self.sender.send(WalletConnectivityRequest::ForceReconnect).await;
+    }
+
+    async fn get_current_base_node_details(&mut self) -> Option<BaseNodeDetails> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(WalletConnectivityRequest::GetCurrentBaseNode(reply_tx))
+            .await
+            .ok()?;
+
+        reply_rx.await.ok().flatten()
+    }
+
     fn get_connectivity_status(&mut self) -> OnlineStatus {
         *self.online_status_rx.borrow()
     }
@@ -134,6 +169,14 @@ impl WalletConnectivityInterface for WalletConnectivityHandle {
         self.online_status_rx.clone()
     }
 
+    fn has_ever_connected(&self) -> bool {
+        *self.ever_connected_rx.borrow()
+    }
+
+    fn get_time_to_first_online_watch(&self) -> watch::Receiver<Option<Duration>> {
+        self.time_to_first_online_rx.clone()
+    }
+
     fn get_current_base_node_peer(&self) -> Option<Peer> {
         self.base_node_watch
             .borrow()
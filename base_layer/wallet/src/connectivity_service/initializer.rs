@@ -28,6 +28,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::sync::Arc;
+
+use tari_common::configuration::Network;
+use tari_comms::PeerManager;
 use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
 use tokio::sync::mpsc;
 
@@ -40,11 +44,18 @@ use crate::{
 
 pub struct WalletConnectivityInitializer {
     config: BaseNodeServiceConfig,
+    network: Option<Network>,
 }
 
 impl WalletConnectivityInitializer {
     pub fn new(config: BaseNodeServiceConfig) -> Self {
-        Self { config }
+        Self { config, network: None }
+    }
+
+    /// The wallet's network, which connected base nodes must be on if `check_base_node_network` is enabled
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
     }
 }
 
@@ -54,18 +65,37 @@ impl ServiceInitializer for WalletConnectivityInitializer {
         let (sender, receiver) = mpsc::channel(5);
         let base_node_watch = Watch::new(None);
         let online_status_watch = Watch::new(OnlineStatus::Offline);
+        let ever_connected_watch = Watch::new(false);
+        let time_to_first_online_watch = Watch::new(None);
         context.register_handle(WalletConnectivityHandle::new(
             sender,
             base_node_watch.clone(),
             online_status_watch.get_receiver(),
+            ever_connected_watch.get_receiver(),
+            time_to_first_online_watch.get_receiver(),
         ));
 
         let config = self.config.clone();
+        let network = self.network;
 
         context.spawn_until_shutdown(move |handles| {
             let connectivity = handles.expect_handle();
-            let service =
-                WalletConnectivityService::new(config, receiver, base_node_watch, online_status_watch, connectivity);
+            let comms_peer_manager = handles.get_handle::<Arc<PeerManager>>();
+            let mut service = WalletConnectivityService::new(
+                config,
+                receiver,
+                base_node_watch,
+                online_status_watch,
+                ever_connected_watch,
+                time_to_first_online_watch,
+                connectivity,
+            );
+            if let Some(comms_peer_manager) = comms_peer_manager {
+                service = service.with_comms_peer_manager(comms_peer_manager);
+            }
+            if let Some(network) = network {
+                service = service.with_network(network);
+            }
             service.start()
         });
 
@@ -30,7 +30,7 @@ use tari_comms::{
 use tari_core::base_node::{rpc::BaseNodeWalletRpcClient, sync::rpc::BaseNodeSyncRpcClient};
 use tokio::sync::watch;
 
-use crate::connectivity_service::{BaseNodePeerManager, OnlineStatus};
+use crate::connectivity_service::{BaseNodeDetails, BaseNodePeerManager, OnlineStatus};
 
 #[async_trait::async_trait]
 pub trait WalletConnectivityInterface: Clone + Send + Sync + 'static {
@@ -66,10 +66,24 @@ pub trait WalletConnectivityInterface: Clone + Send + Sync + 'static {
 
     async fn disconnect_base_node(&mut self, node_id: NodeId);
 
+    /// Drops and re-establishes the connection to the current base node, e.g. to clear a wedged RPC state. Unlike
+    /// selecting a different base node, the wallet reconnects to the same peer.
+    async fn force_reconnect(&mut self);
+
+    /// Returns the details of the base node the wallet is currently connected to, or None if not connected
+    async fn get_current_base_node_details(&mut self) -> Option<BaseNodeDetails>;
+
     fn get_connectivity_status(&mut self) -> OnlineStatus;
 
     fn get_connectivity_status_watch(&self) -> watch::Receiver<OnlineStatus>;
 
+    /// Returns true if the wallet has been online with a base node at least once since startup. Together with
+    /// `get_connectivity_status` this distinguishes "connecting for the first time" from "connection dropped".
+    fn has_ever_connected(&self) -> bool;
+
+    /// How long after startup the wallet first came online with a base node, or None if it has not been online yet
+    fn get_time_to_first_online_watch(&self) -> watch::Receiver<Option<Duration>>;
+
     fn get_current_base_node_peer(&self) -> Option<Peer>;
 
     fn get_current_base_node_peer_public_key(&self) -> Option<CommsPublicKey>;
@@ -20,6 +20,8 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::time::Duration;
+
 use tari_comms::{
     peer_manager::{NodeId, Peer},
     protocol::rpc::RpcClientLease,
@@ -29,7 +31,7 @@ use tari_core::base_node::{rpc::BaseNodeWalletRpcClient, sync::rpc::BaseNodeSync
 use tokio::sync::watch::Receiver;
 
 use crate::{
-    connectivity_service::{BaseNodePeerManager, OnlineStatus, WalletConnectivityInterface},
+    connectivity_service::{BaseNodeDetails, BaseNodePeerManager, OnlineStatus, WalletConnectivityInterface},
     util::watch::Watch,
 };
 
@@ -43,6 +45,7 @@ pub struct WalletConnectivityMock {
     base_node_watch: Watch<Option<BaseNodePeerManager>>,
     base_node_wallet_rpc_client: Watch<Option<RpcClientLease<BaseNodeWalletRpcClient>>>,
     base_node_sync_rpc_client: Watch<Option<RpcClientLease<BaseNodeSyncRpcClient>>>,
+    time_to_first_online_watch: Watch<Option<Duration>>,
 }
 
 impl WalletConnectivityMock {
@@ -52,6 +55,7 @@ impl WalletConnectivityMock {
             base_node_watch: Watch::new(None),
             base_node_wallet_rpc_client: Watch::new(None),
             base_node_sync_rpc_client: Watch::new(None),
+            time_to_first_online_watch: Watch::new(None),
         }
     }
 }
@@ -120,6 +124,23 @@ impl WalletConnectivityInterface for WalletConnectivityMock {
         self.send_shutdown();
     }
 
+    async fn force_reconnect(&mut self) {}
+
+    async fn get_current_base_node_details(&mut self) -> Option<BaseNodeDetails> {
+        if *self.online_status_watch.borrow() != OnlineStatus::Online {
+            return None;
+        }
+        let peer = self.get_current_base_node_peer()?;
+        let addresses = peer.addresses.address_iter().cloned().collect::<Vec<_>>();
+        Some(BaseNodeDetails {
+            node_id: peer.node_id,
+            public_key: peer.public_key,
+            address: addresses.first()?.clone(),
+            addresses,
+            connection_age: Duration::ZERO,
+        })
+    }
+
     fn get_connectivity_status(&mut self) -> OnlineStatus {
         *self.online_status_watch.borrow()
     }
@@ -128,6 +149,14 @@ impl WalletConnectivityInterface for WalletConnectivityMock {
         self.online_status_watch.get_receiver()
     }
 
+    fn has_ever_connected(&self) -> bool {
+        false
+    }
+
+    fn get_time_to_first_online_watch(&self) -> Receiver<Option<Duration>> {
+        self.time_to_first_online_watch.get_receiver()
+    }
+
     fn get_current_base_node_peer(&self) -> Option<Peer> {
         self.base_node_watch
             .borrow()
@@ -30,8 +30,11 @@ pub use handle::WalletConnectivityHandle;
 mod initializer;
 pub use initializer::WalletConnectivityInitializer;
 
+#[cfg(feature = "metrics")]
+mod metrics;
+
 mod service;
-pub use service::OnlineStatus;
+pub use service::{BaseNodeDetails, OnlineStatus};
 
 #[cfg(test)]
 mod test;
@@ -22,16 +22,21 @@
 
 use std::{
     cmp::{max, min},
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     mem,
+    sync::Arc,
     time::Duration,
 };
 
 use log::*;
+use rand::{rngs::OsRng, seq::SliceRandom, Rng};
+use tari_common::configuration::Network;
 use tari_comms::{
     connectivity::{ConnectivityError, ConnectivityRequester},
-    peer_manager::NodeId,
+    multiaddr::Multiaddr,
+    peer_manager::{NodeId, Peer, PeerManager, PeerQuery},
     protocol::rpc::{RpcClientLease, RpcClientPool},
+    types::CommsPublicKey,
     Minimized,
     PeerConnection,
 };
@@ -39,17 +44,26 @@ use tari_core::base_node::{rpc::BaseNodeWalletRpcClient, sync::rpc::BaseNodeSync
 use tokio::{
     sync::{mpsc, oneshot, watch},
     time,
-    time::{timeout, Duration as TokioDuration, MissedTickBehavior},
+    time::{timeout, Duration as TokioDuration, Instant, MissedTickBehavior},
 };
 
 use crate::{
-    base_node_service::config::BaseNodeServiceConfig,
+    base_node_service::config::{BaseNodeSelection, BaseNodeServiceConfig},
     connectivity_service::{error::WalletConnectivityError, handle::WalletConnectivityRequest, BaseNodePeerManager},
     util::watch::Watch,
 };
 
 const LOG_TARGET: &str = "wallet::connectivity";
+/// The default `connectivity_retry_wait` in seconds, used to bound waits in tests
+#[cfg(test)]
 pub(crate) const CONNECTIVITY_WAIT: u64 = 5;
+const MIN_CONNECTION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Connection attempt summaries for the same peer and kind of outcome are logged at most once per interval
+const ATTEMPT_SUMMARY_INTERVAL: Duration = Duration::from_secs(60);
+/// The wait before retrying a failed RPC pool request, giving a transient error time to clear
+const RPC_POOL_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// How long a base node on a different network is passed over in favour of the other base node peers
+const NETWORK_MISMATCH_EXCLUSION: Duration = Duration::from_secs(10 * 60);
 
 /// Connection status of the Base Node
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -59,6 +73,19 @@ pub enum OnlineStatus {
     Offline,
 }
 
+/// Details of the base node the wallet is currently connected to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseNodeDetails {
+    pub node_id: NodeId,
+    pub public_key: CommsPublicKey,
+    /// The address of the active connection
+    pub address: Multiaddr,
+    /// All known addresses of the base node
+    pub addresses: Vec<Multiaddr>,
+    /// How long the connection has been established
+    pub connection_age: Duration,
+}
+
 pub struct WalletConnectivityService {
     config: BaseNodeServiceConfig,
     request_receiver: mpsc::Receiver<WalletConnectivityRequest>,
@@ -67,12 +94,209 @@ pub struct WalletConnectivityService {
     base_node_watch: Watch<Option<BaseNodePeerManager>>,
     pools: HashMap<NodeId, ClientPoolContainer>,
     online_status_watch: Watch<OnlineStatus>,
-    pending_requests: Vec<ReplyOneshot>,
+    ever_connected_watch: Watch<bool>,
+    time_to_first_online_watch: Watch<Option<Duration>>,
+    started_at: Instant,
+    pending_wallet_requests: PendingRequests<RpcClientLease<BaseNodeWalletRpcClient>>,
+    pending_sync_requests: PendingRequests<RpcClientLease<BaseNodeSyncRpcClient>>,
+    last_peer_list: Vec<NodeId>,
+    comms_peer_manager: Option<Arc<PeerManager>>,
+    attempt_summaries: AttemptSummaryLimiter,
+    connection_latencies: HashMap<NodeId, Duration>,
+    network: Option<Network>,
+    /// Set once the seed peers have been merged into the peer list, until the preferred base node is connected again
+    fell_back_to_seeds: bool,
+    network_mismatches: NetworkMismatchExclusions,
 }
 
 struct ClientPoolContainer {
     pub base_node_wallet_rpc_client: RpcClientPool<BaseNodeWalletRpcClient>,
     pub base_node_sync_rpc_client: RpcClientPool<BaseNodeSyncRpcClient>,
+    pub wallet_rpc_errors: ConsecutiveErrorCounter,
+    pub sync_rpc_errors: ConsecutiveErrorCounter,
+}
+
+/// Counts consecutive RPC pool errors so that a single transient error does not tear down the base node connection
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ConsecutiveErrorCounter {
+    count: usize,
+    threshold: usize,
+}
+
+impl ConsecutiveErrorCounter {
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            count: 0,
+            threshold: max(threshold, 1),
+        }
+    }
+
+    /// Records an error, returning true once the threshold of consecutive errors is reached
+    pub fn record_error(&mut self) -> bool {
+        self.count += 1;
+        self.count >= self.threshold
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+/// A bounded FIFO queue of RPC client requests waiting for a base node connection
+pub(super) struct PendingRequests<T> {
+    queue: VecDeque<oneshot::Sender<T>>,
+    capacity: usize,
+}
+
+impl<T> PendingRequests<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: VecDeque::new(),
+            capacity: max(capacity, 1),
+        }
+    }
+
+    /// Queues the request, first evicting canceled requests if the queue is full. The request is returned if the
+    /// queue is still full.
+    pub fn push(&mut self, reply: oneshot::Sender<T>) -> Result<(), oneshot::Sender<T>> {
+        if self.queue.len() >= self.capacity {
+            self.evict_canceled();
+        }
+        if self.queue.len() >= self.capacity {
+            return Err(reply);
+        }
+        self.queue.push_back(reply);
+        Ok(())
+    }
+
+    /// Removes the requests whose callers have gone away, keeping the remaining requests in order
+    pub fn evict_canceled(&mut self) -> usize {
+        let len = self.queue.len();
+        self.queue.retain(|reply| !reply.is_closed());
+        len - self.queue.len()
+    }
+
+    pub fn take(&mut self) -> VecDeque<oneshot::Sender<T>> {
+        mem::take(&mut self.queue)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+/// The kind of result of a connection attempt, leaving out details such as the dial timeout that grow between
+/// otherwise identical attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AttemptOutcome {
+    Connected,
+    NotConnected,
+    Failed(mem::Discriminant<WalletConnectivityError>, Option<mem::Discriminant<ConnectivityError>>),
+}
+
+impl AttemptOutcome {
+    pub fn from_result(result: &Result<bool, WalletConnectivityError>) -> Self {
+        match result {
+            Ok(true) => AttemptOutcome::Connected,
+            Ok(false) => AttemptOutcome::NotConnected,
+            Err(e) => {
+                let connectivity_error = match e {
+                    WalletConnectivityError::ConnectivityError(e) => Some(mem::discriminant(e)),
+                    _ => None,
+                };
+                AttemptOutcome::Failed(mem::discriminant(e), connectivity_error)
+            },
+        }
+    }
+}
+
+/// Base nodes that were rejected for reporting a different network, which are skipped while they are excluded and
+/// other base node peers are available
+#[derive(Debug, Clone)]
+pub(super) struct NetworkMismatchExclusions {
+    duration: Duration,
+    excluded_until: HashMap<NodeId, Instant>,
+}
+
+impl NetworkMismatchExclusions {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            excluded_until: HashMap::new(),
+        }
+    }
+
+    pub fn exclude(&mut self, node_id: NodeId, now: Instant) {
+        self.excluded_until.insert(node_id, now + self.duration);
+    }
+
+    /// Returns true if the peer is still excluded at `now`. Expired exclusions are removed.
+    pub fn is_excluded(&mut self, node_id: &NodeId, now: Instant) -> bool {
+        self.excluded_until.retain(|_, excluded_until| *excluded_until > now);
+        self.excluded_until.contains_key(node_id)
+    }
+
+    /// Returns true if `node_id` should be skipped because it is excluded and at least one of `peers` is not
+    pub fn should_skip(&mut self, node_id: &NodeId, peers: &[Peer], now: Instant) -> bool {
+        self.is_excluded(node_id, now) && peers.iter().any(|p| !self.excluded_until.contains_key(&p.node_id))
+    }
+}
+
+/// Rate limits connection attempt summaries for the same peer and kind of outcome, e.g. when rapidly retrying a single
+/// base node peer
+#[derive(Debug, Clone)]
+pub(super) struct AttemptSummaryLimiter {
+    interval: Duration,
+    last: Option<((NodeId, AttemptOutcome), Instant)>,
+    suppressed: usize,
+}
+
+impl AttemptSummaryLimiter {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+            suppressed: 0,
+        }
+    }
+
+    /// Returns the summary to log, or None if a summary for the same peer and kind of outcome was already logged within
+    /// the interval. A summary that is logged again after being suppressed includes the number of suppressed repeats.
+    pub fn check(&mut self, key: (NodeId, AttemptOutcome), summary: String, now: Instant) -> Option<String> {
+        match &self.last {
+            Some((last, logged_at)) if *last == key => {
+                if now.saturating_duration_since(*logged_at) < self.interval {
+                    self.suppressed += 1;
+                    return None;
+                }
+            },
+            _ => self.suppressed = 0,
+        }
+        let message = if self.suppressed > 0 {
+            format!("{} ({} similar attempts suppressed)", summary, self.suppressed)
+        } else {
+            summary
+        };
+        self.last = Some((key, now));
+        self.suppressed = 0;
+        Some(message)
+    }
 }
 
 impl WalletConnectivityService {
@@ -81,8 +305,12 @@ impl WalletConnectivityService {
         request_receiver: mpsc::Receiver<WalletConnectivityRequest>,
         base_node_watch: Watch<Option<BaseNodePeerManager>>,
         online_status_watch: Watch<OnlineStatus>,
+        ever_connected_watch: Watch<bool>,
+        time_to_first_online_watch: Watch<Option<Duration>>,
         connectivity: ConnectivityRequester,
     ) -> Self {
+        let pending_wallet_requests = PendingRequests::new(config.max_pending_wallet_rpc_requests);
+        let pending_sync_requests = PendingRequests::new(config.max_pending_sync_rpc_requests);
         Self {
             config,
             request_receiver,
@@ -90,17 +318,38 @@ impl WalletConnectivityService {
             base_node_watch_receiver: base_node_watch.get_receiver(),
             base_node_watch,
             pools: HashMap::new(),
-            pending_requests: Vec::new(),
+            pending_wallet_requests,
+            pending_sync_requests,
             online_status_watch,
+            ever_connected_watch,
+            time_to_first_online_watch,
+            started_at: Instant::now(),
+            last_peer_list: Vec::new(),
+            comms_peer_manager: None,
+            attempt_summaries: AttemptSummaryLimiter::new(ATTEMPT_SUMMARY_INTERVAL),
+            connection_latencies: HashMap::new(),
+            network: None,
+            fell_back_to_seeds: false,
+            network_mismatches: NetworkMismatchExclusions::new(NETWORK_MISMATCH_EXCLUSION),
         }
     }
 
+    /// Check that connected base nodes are on the wallet's network, when `check_base_node_network` is enabled
+    pub(super) fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// Use the seed peers known to the comms peer manager as a fallback when `allow_seed_fallback` is enabled
+    pub(super) fn with_comms_peer_manager(mut self, peer_manager: Arc<PeerManager>) -> Self {
+        self.comms_peer_manager = Some(peer_manager);
+        self
+    }
+
     pub async fn start(mut self) {
         debug!(target: LOG_TARGET, "Wallet connectivity service has started.");
-        let mut check_connection =
-            time::interval_at(time::Instant::now() + Duration::from_secs(5), Duration::from_secs(5));
+        let mut check_connection = connection_check_timer(&self.config);
         self.set_online_status(OnlineStatus::Offline);
-        check_connection.set_missed_tick_behavior(MissedTickBehavior::Delay);
         loop {
             tokio::select! {
                 // BIASED: select branches are in order of priority
@@ -108,6 +357,7 @@ impl WalletConnectivityService {
 
                 Ok(_) = self.base_node_watch_receiver.changed() => {
                     if self.base_node_watch_receiver.borrow().is_some() {
+                        self.reset_attempt_state_on_peer_list_change();
                         // This will block the rest until the connection is established. This is what we want.
                         trace!(target: LOG_TARGET, "start: base_node_watch_receiver.changed");
                         self.check_connection().await;
@@ -139,6 +389,9 @@ impl WalletConnectivityService {
                         if pool.base_node_wallet_rpc_client.is_connected().await {
                             trace!(target: LOG_TARGET, "check_connection: rpc pool is already connected");
                             self.set_online_status(OnlineStatus::Online);
+                            if self.fell_back_to_seeds {
+                                self.return_to_preferred_peer(peer_manager).await;
+                            }
                             return;
                         }
                         debug!(
@@ -167,27 +420,82 @@ impl WalletConnectivityService {
     async fn handle_request(&mut self, request: WalletConnectivityRequest) {
         use WalletConnectivityRequest::{
             DisconnectBaseNode,
+            ForceReconnect,
+            GetCurrentBaseNode,
             ObtainBaseNodeSyncRpcClient,
             ObtainBaseNodeWalletRpcClient,
         };
         match request {
             ObtainBaseNodeWalletRpcClient(reply) => {
-                self.handle_pool_request(reply.into()).await;
+                self.handle_get_base_node_wallet_rpc_client(reply).await;
             },
             ObtainBaseNodeSyncRpcClient(reply) => {
-                self.handle_pool_request(reply.into()).await;
+                self.handle_get_base_node_sync_rpc_client(reply).await;
             },
             DisconnectBaseNode(node_id) => {
                 self.disconnect_base_node(node_id).await;
             },
+            ForceReconnect => {
+                self.force_reconnect().await;
+            },
+            GetCurrentBaseNode(reply) => {
+                let _result = reply.send(self.current_base_node_details().await);
+            },
         }
     }
 
-    async fn handle_pool_request(&mut self, reply: ReplyOneshot) {
-        use ReplyOneshot::{SyncRpc, WalletRpc};
-        match reply {
-            WalletRpc(tx) => self.handle_get_base_node_wallet_rpc_client(tx).await,
-            SyncRpc(tx) => self.handle_get_base_node_sync_rpc_client(tx).await,
+    async fn current_base_node_details(&mut self) -> Option<BaseNodeDetails> {
+        let peer = self
+            .base_node_watch_receiver
+            .borrow()
+            .as_ref()
+            .map(|p| p.get_current_peer().clone())?;
+        let connection = match self.connectivity.get_connection(peer.node_id.clone()).await {
+            Ok(Some(connection)) if connection.is_connected() => connection,
+            Ok(_) => return None,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to get base node connection: {}", e);
+                return None;
+            },
+        };
+        Some(BaseNodeDetails {
+            node_id: peer.node_id,
+            public_key: peer.public_key,
+            address: connection.address().clone(),
+            addresses: peer.addresses.address_iter().cloned().collect(),
+            connection_age: connection.age(),
+        })
+    }
+
+    fn queue_wallet_request(&mut self, reply: oneshot::Sender<RpcClientLease<BaseNodeWalletRpcClient>>) {
+        if self.pending_wallet_requests.push(reply).is_err() {
+            warn!(
+                target: LOG_TARGET,
+                "{} wallet requests already waiting for connection, dropping request",
+                self.pending_wallet_requests.capacity()
+            );
+        }
+        self.update_pending_request_metrics();
+    }
+
+    fn queue_sync_request(&mut self, reply: oneshot::Sender<RpcClientLease<BaseNodeSyncRpcClient>>) {
+        if self.pending_sync_requests.push(reply).is_err() {
+            warn!(
+                target: LOG_TARGET,
+                "{} sync requests already waiting for connection, dropping request",
+                self.pending_sync_requests.capacity()
+            );
+        }
+        self.update_pending_request_metrics();
+    }
+
+    fn update_pending_request_metrics(&self) {
+        #[cfg(feature = "metrics")]
+        {
+            super::metrics::pending_requests("wallet")
+                .set(i64::try_from(self.pending_wallet_requests.len()).unwrap_or(i64::MAX));
+            super::metrics::pending_requests("sync")
+                .set(i64::try_from(self.pending_sync_requests.len()).unwrap_or(i64::MAX));
         }
     }
 
@@ -198,35 +506,51 @@ impl WalletConnectivityService {
         let node_id = if let Some(val) = self.current_base_node() {
             val
         } else {
-            self.pending_requests.push(reply.into());
-            warn!(target: LOG_TARGET, "{} wallet requests waiting for connection", self.pending_requests.len());
+            self.queue_wallet_request(reply);
+            warn!(target: LOG_TARGET, "{} wallet requests waiting for connection", self.pending_wallet_requests.len());
             return;
         };
 
-        match self.pools.get(&node_id) {
-            Some(pools) => match pools.base_node_wallet_rpc_client.get().await {
-                Ok(client) => {
-                    debug!(target: LOG_TARGET, "Obtained pool RPC 'wallet' connection to base node '{}'", node_id);
-                    let _result = reply.send(client);
-                },
-                Err(e) => {
-                    warn!(
-                        target: LOG_TARGET,
-                        "Base node '{}' pool RPC 'wallet' connection failed ({}). Reconnecting...",
-                        node_id,
-                        e
-                    );
-                    self.disconnect_base_node(node_id).await;
-                    self.pending_requests.push(reply.into());
-                },
+        match self.pools.get_mut(&node_id) {
+            Some(pools) => loop {
+                match pools.base_node_wallet_rpc_client.get().await {
+                    Ok(client) => {
+                        debug!(target: LOG_TARGET, "Obtained pool RPC 'wallet' connection to base node '{}'", node_id);
+                        pools.wallet_rpc_errors.reset();
+                        let _result = reply.send(client);
+                        break;
+                    },
+                    Err(e) => {
+                        if pools.wallet_rpc_errors.record_error() {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Base node '{}' pool RPC 'wallet' connection failed ({}). Reconnecting...",
+                                node_id,
+                                e
+                            );
+                            self.disconnect_base_node(node_id).await;
+                            self.queue_wallet_request(reply);
+                            break;
+                        }
+                        warn!(
+                            target: LOG_TARGET,
+                            "Base node '{}' pool RPC 'wallet' connection failed ({}), retrying ({} of {})",
+                            node_id,
+                            e,
+                            pools.wallet_rpc_errors.count(),
+                            pools.wallet_rpc_errors.threshold()
+                        );
+                        time::sleep(RPC_POOL_RETRY_DELAY).await;
+                    },
+                }
             },
             None => {
-                self.pending_requests.push(reply.into());
+                self.queue_wallet_request(reply);
                 warn!(
                     target: LOG_TARGET,
                     "Wallet RPC pool for base node `{}` not found, {} requests waiting",
                     node_id,
-                    self.pending_requests.len()
+                    self.pending_wallet_requests.len()
                 );
             },
         }
@@ -239,35 +563,51 @@ impl WalletConnectivityService {
         let node_id = if let Some(val) = self.current_base_node() {
             val
         } else {
-            self.pending_requests.push(reply.into());
-            warn!(target: LOG_TARGET, "{} sync requests waiting for connection", self.pending_requests.len());
+            self.queue_sync_request(reply);
+            warn!(target: LOG_TARGET, "{} sync requests waiting for connection", self.pending_sync_requests.len());
             return;
         };
 
-        match self.pools.get(&node_id) {
-            Some(pools) => match pools.base_node_sync_rpc_client.get().await {
-                Ok(client) => {
-                    debug!(target: LOG_TARGET, "Obtained pool RPC 'sync' connection to base node '{}'", node_id);
-                    let _result = reply.send(client);
-                },
-                Err(e) => {
-                    warn!(
-                        target: LOG_TARGET,
-                        "Base node '{}' pool RPC 'sync' connection failed ({}). Reconnecting...",
-                        node_id,
-                        e
-                    );
-                    self.disconnect_base_node(node_id).await;
-                    self.pending_requests.push(reply.into());
-                },
+        match self.pools.get_mut(&node_id) {
+            Some(pools) => loop {
+                match pools.base_node_sync_rpc_client.get().await {
+                    Ok(client) => {
+                        debug!(target: LOG_TARGET, "Obtained pool RPC 'sync' connection to base node '{}'", node_id);
+                        pools.sync_rpc_errors.reset();
+                        let _result = reply.send(client);
+                        break;
+                    },
+                    Err(e) => {
+                        if pools.sync_rpc_errors.record_error() {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Base node '{}' pool RPC 'sync' connection failed ({}). Reconnecting...",
+                                node_id,
+                                e
+                            );
+                            self.disconnect_base_node(node_id).await;
+                            self.queue_sync_request(reply);
+                            break;
+                        }
+                        warn!(
+                            target: LOG_TARGET,
+                            "Base node '{}' pool RPC 'sync' connection failed ({}), retrying ({} of {})",
+                            node_id,
+                            e,
+                            pools.sync_rpc_errors.count(),
+                            pools.sync_rpc_errors.threshold()
+                        );
+                        time::sleep(RPC_POOL_RETRY_DELAY).await;
+                    },
+                }
             },
             None => {
-                self.pending_requests.push(reply.into());
+                self.queue_sync_request(reply);
                 warn!(
                     target: LOG_TARGET,
                     "Sync RPC pool for base node `{}` not found, {} requests waiting",
                     node_id,
-                    self.pending_requests.len()
+                    self.pending_sync_requests.len()
                 );
             },
         }
@@ -280,6 +620,29 @@ impl WalletConnectivityService {
             .map(|p| p.get_current_peer().node_id.clone())
     }
 
+    /// Reset the connection attempt state when the peer list changes, so the new list is tried without backoff
+    fn reset_attempt_state_on_peer_list_change(&mut self) {
+        let Some(mut peer_manager) = self.get_base_node_peer_manager() else {
+            return;
+        };
+        let peer_list = peer_manager
+            .get_state()
+            .1
+            .into_iter()
+            .map(|p| p.node_id)
+            .collect::<Vec<_>>();
+        if peer_list == self.last_peer_list {
+            return;
+        }
+        debug!(target: LOG_TARGET, "Base node peer list changed, resetting connection attempt state");
+        peer_manager.reset_attempt_state();
+        self.last_peer_list = peer_list;
+        self.fell_back_to_seeds = false;
+        self.base_node_watch.send(Some(peer_manager));
+        // Do not treat our own update as another change
+        self.base_node_watch_receiver.borrow_and_update();
+    }
+
     fn get_base_node_peer_manager(&self) -> Option<BaseNodePeerManager> {
         self.base_node_watch_receiver.borrow().as_ref().map(|p| p.clone())
     }
@@ -296,24 +659,51 @@ impl WalletConnectivityService {
         };
     }
 
+    /// Tears down the connection to the current base node and immediately reconnects to the same peer
+    async fn force_reconnect(&mut self) {
+        let Some(mut peer_manager) = self.get_base_node_peer_manager() else {
+            debug!(target: LOG_TARGET, "Base node peer manager has not been set, cannot reconnect");
+            return;
+        };
+        let node_id = peer_manager.get_current_peer().node_id;
+        info!(target: LOG_TARGET, "Forcing a reconnect to base node '{}'", node_id);
+        self.disconnect_base_node(node_id).await;
+        // Without a previous attempt on record the current peer is dialed straight away instead of the next one
+        peer_manager.reset_attempt_state();
+        self.base_node_watch.send(Some(peer_manager));
+        // Do not treat our own update as a peer list change
+        self.base_node_watch_receiver.borrow_and_update();
+        self.set_online_status(OnlineStatus::Connecting);
+        self.setup_base_node_connection().await;
+    }
+
     async fn setup_base_node_connection(&mut self) {
         let mut peer_manager = if let Some(val) = self.get_base_node_peer_manager() {
             val
         } else {
             return;
         };
+        self.apply_base_node_selection(&mut peer_manager);
         let mut loop_count = 0;
-        let number_of_seeds = peer_manager.get_state().1.len();
+        let mut number_of_seeds = peer_manager.get_state().1.len();
+        let mut failed_attempts = 0;
+        let mut tried_seed_fallback = self.fell_back_to_seeds;
         loop {
             loop_count += 1;
             let node_id = if let Some(_time) = peer_manager.time_since_last_connection_attempt() {
+                let cycle_completed = peer_manager.is_last_in_dial_order();
                 if peer_manager.get_current_peer().node_id == peer_manager.get_next_peer().node_id {
                     // If we only have one peer in the list, wait a bit before retrying
-                    debug!(target: LOG_TARGET,
-                        "Retrying after {}s ...",
-                        Duration::from_secs(CONNECTIVITY_WAIT).as_secs()
+                    let wait = jittered_wait(
+                        self.config.connectivity_retry_wait,
+                        self.config.connectivity_retry_jitter,
                     );
-                    time::sleep(Duration::from_secs(CONNECTIVITY_WAIT)).await;
+                    debug!(target: LOG_TARGET, "Retrying after {:.2?} ...", wait);
+                    time::sleep(wait).await;
+                }
+                // Every connection cycle starts with a fresh order, e.g. reshuffled or sorted by the latest latencies
+                if cycle_completed {
+                    self.apply_base_node_selection(&mut peer_manager);
                 }
                 // If 'peer_manager.get_next_peer()' is called, 'current_peer' is advanced to the next peer
                 peer_manager.get_current_peer().node_id
@@ -321,6 +711,16 @@ impl WalletConnectivityService {
                 peer_manager.get_current_peer().node_id
             };
             peer_manager.set_last_connection_attempt();
+            if self
+                .network_mismatches
+                .should_skip(&node_id, &peer_manager.get_state().1, Instant::now())
+            {
+                debug!(
+                    target: LOG_TARGET,
+                    "Skipping base node peer '{}', it recently reported a different network", node_id
+                );
+                continue;
+            }
 
             debug!(
                 target: LOG_TARGET,
@@ -329,10 +729,18 @@ impl WalletConnectivityService {
                 peer_manager.time_since_last_connection_attempt()
             );
             self.pools.remove(&node_id);
-            match self
-                .try_setup_rpc_pool(node_id.clone(), loop_count / number_of_seeds + 1)
-                .await
-            {
+            let dial_cycle = loop_count / number_of_seeds + 1;
+            let dial_started = Instant::now();
+            let result = match self.try_setup_rpc_pool(node_id.clone(), dial_cycle).await {
+                Ok(true) => self.check_base_node_network(&node_id).await.map(|_| true),
+                result => result,
+            };
+            if let Ok(true) = result {
+                self.connection_latencies
+                    .insert(node_id.clone(), dial_started.elapsed());
+            }
+            self.log_attempt_summary(&node_id, dial_timeout(dial_cycle), &result);
+            match result {
                 Ok(true) => {
                     if self.peer_list_change_detected(&peer_manager) {
                         debug!(
@@ -342,6 +750,12 @@ impl WalletConnectivityService {
                         self.set_online_status(OnlineStatus::Offline);
                         break;
                     }
+                    // Keep any seeds that were merged into the peer list while connecting
+                    if let Some(current) = self.get_base_node_peer_manager() {
+                        if current.extends(&peer_manager) {
+                            peer_manager.merge_seeds(current.get_state().1);
+                        }
+                    }
                     self.base_node_watch.send(Some(peer_manager.clone()));
                     if let Ok(true) = self.notify_pending_requests().await {
                         self.set_online_status(OnlineStatus::Online);
@@ -359,6 +773,15 @@ impl WalletConnectivityService {
                     );
                     self.disconnect_base_node(node_id).await;
                 },
+                Err(e @ WalletConnectivityError::BaseNodeNetworkMismatch { .. }) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Rejecting base node '{}' for {:.0?}: {}", node_id, NETWORK_MISMATCH_EXCLUSION, e
+                    );
+                    self.network_mismatches.exclude(node_id.clone(), Instant::now());
+                    self.set_online_status(OnlineStatus::Offline);
+                    self.disconnect_base_node(node_id).await;
+                },
                 Err(WalletConnectivityError::ConnectivityError(ConnectivityError::DialCancelled)) => {
                     debug!(target: LOG_TARGET, "Dial was cancelled.");
                     self.disconnect_base_node(node_id).await;
@@ -368,6 +791,7 @@ impl WalletConnectivityService {
                     self.disconnect_base_node(node_id).await;
                 },
             }
+            failed_attempts += 1;
             if self.peer_list_change_detected(&peer_manager) {
                 debug!(
                     target: LOG_TARGET,
@@ -376,11 +800,148 @@ impl WalletConnectivityService {
                 self.set_online_status(OnlineStatus::Offline);
                 break;
             }
+            if self.config.allow_seed_fallback &&
+                !tried_seed_fallback &&
+                failed_attempts >= self.config.seed_fallback_threshold
+            {
+                tried_seed_fallback = true;
+                if let Some(seeds) = self.get_seed_peers().await {
+                    // The configured base nodes stay in the list, ahead of the seeds, so that the preferred one can
+                    // be returned to once it is reachable again
+                    let num_added = peer_manager.merge_seeds(seeds);
+                    warn!(
+                        target: LOG_TARGET,
+                        "Could not connect to any configured base node after {} attempts, falling back to {} seed \
+                         peers",
+                        failed_attempts,
+                        num_added
+                    );
+                    self.fell_back_to_seeds = true;
+                    self.apply_base_node_selection(&mut peer_manager);
+                    number_of_seeds = peer_manager.get_state().1.len();
+                    loop_count = 0;
+                    self.last_peer_list = peer_manager.get_state().1.into_iter().map(|p| p.node_id).collect();
+                    self.base_node_watch.send(Some(peer_manager.clone()));
+                    // Do not treat our own update as a peer list change
+                    self.base_node_watch_receiver.borrow_and_update();
+                }
+            }
         }
     }
 
+    /// Ask the base node which network it is on and reject it if that is not the wallet's network. Base nodes that
+    /// cannot report their network, e.g. older versions, are accepted.
+    async fn check_base_node_network(&mut self, node_id: &NodeId) -> Result<(), WalletConnectivityError> {
+        let expected = match self.network {
+            Some(network) if self.config.check_base_node_network => network,
+            _ => return Ok(()),
+        };
+        let Some(pools) = self.pools.get(node_id) else {
+            return Ok(());
+        };
+        let reported = match pools.base_node_wallet_rpc_client.get().await {
+            Ok(mut client) => client.get_network().await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match reported {
+            Ok(byte) => {
+                let reported = u8::try_from(byte).ok().and_then(|b| Network::try_from(b).ok());
+                if reported == Some(expected) {
+                    Ok(())
+                } else {
+                    Err(WalletConnectivityError::BaseNodeNetworkMismatch {
+                        expected,
+                        reported: reported.map_or_else(|| format!("unknown ({})", byte), |n| n.to_string()),
+                    })
+                }
+            },
+            Err(e) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Could not verify the network of base node '{}', accepting it ({})", node_id, e
+                );
+                Ok(())
+            },
+        }
+    }
+
+    /// Order the candidate peers for a connection cycle according to the configured base node selection
+    fn apply_base_node_selection(&self, peer_manager: &mut BaseNodePeerManager) {
+        if self.config.base_node_selection == BaseNodeSelection::Ordered {
+            return;
+        }
+        let dial_order = candidate_order(
+            self.config.base_node_selection,
+            &peer_manager.get_state().1,
+            &self.connection_latencies,
+        );
+        if let Err(e) = peer_manager.set_dial_order(dial_order) {
+            warn!(target: LOG_TARGET, "Could not order the base node peers: {}", e);
+        }
+    }
+
+    /// The seed peers known to comms, if there are any
+    async fn get_seed_peers(&self) -> Option<Vec<Peer>> {
+        let comms_peer_manager = self.comms_peer_manager.as_ref()?;
+        let seeds = match comms_peer_manager
+            .perform_query(PeerQuery::new().select_where(|p| p.is_seed()))
+            .await
+        {
+            Ok(seeds) => seeds,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Could not get seed peers from the peer manager: {}", e);
+                return None;
+            },
+        };
+        if seeds.is_empty() {
+            warn!(target: LOG_TARGET, "No seed peers available to fall back to");
+            return None;
+        }
+        Some(seeds)
+    }
+
+    /// While connected to a seed peer after falling back, switch back to the preferred base node, e.g. the one set by
+    /// the user, as soon as it can be dialed again
+    async fn return_to_preferred_peer(&mut self, mut peer_manager: BaseNodePeerManager) {
+        let Some(preferred) = peer_manager.get_preferred_peer() else {
+            self.fell_back_to_seeds = false;
+            return;
+        };
+        let current = peer_manager.get_current_peer().node_id;
+        if preferred.node_id == current {
+            self.fell_back_to_seeds = false;
+            return;
+        }
+        match timeout(dial_timeout(1), self.connectivity.dial_peer(preferred.node_id.clone())).await {
+            Ok(Ok(_)) => {
+                info!(
+                    target: LOG_TARGET,
+                    "Preferred base node '{}' is reachable again, switching back from seed peer '{}'",
+                    preferred.node_id,
+                    current
+                );
+                self.fell_back_to_seeds = false;
+                peer_manager.select_preferred_peer();
+                self.base_node_watch.send(Some(peer_manager));
+                // Do not treat our own update as a peer list change
+                self.base_node_watch_receiver.borrow_and_update();
+                self.disconnect_base_node(current).await;
+                self.set_online_status(OnlineStatus::Connecting);
+                self.setup_base_node_connection().await;
+            },
+            _ => {
+                trace!(target: LOG_TARGET, "Preferred base node '{}' is still unreachable", preferred.node_id);
+            },
+        }
+    }
+
+    /// Returns true if the peer list was replaced while connecting. Seeds merged into the list are not a change, as
+    /// they do not affect the current peer.
     fn peer_list_change_detected(&self, peer_manager: &BaseNodePeerManager) -> bool {
         if let Some(current) = self.get_base_node_peer_manager() {
+            if current.extends(peer_manager) {
+                return false;
+            }
             current
                 .get_state()
                 .1
@@ -399,16 +960,49 @@ impl WalletConnectivityService {
     }
 
     fn set_online_status(&self, status: OnlineStatus) {
+        if status == OnlineStatus::Online && !*self.ever_connected_watch.borrow() {
+            let time_to_first_online = self.started_at.elapsed();
+            debug!(
+                target: LOG_TARGET,
+                "Wallet connected to a base node for the first time, {:.2?} after startup", time_to_first_online
+            );
+            self.ever_connected_watch.send(true);
+            self.time_to_first_online_watch.send(Some(time_to_first_online));
+        }
         self.online_status_watch.send(status);
     }
 
+    /// Logs a concise summary of a connection attempt at the configured level, independent of the verbose logs
+    fn log_attempt_summary(
+        &mut self,
+        node_id: &NodeId,
+        dial_timeout: TokioDuration,
+        result: &Result<bool, WalletConnectivityError>,
+    ) {
+        let outcome = AttemptOutcome::from_result(result);
+        let result = match result {
+            Ok(true) => "connected".to_string(),
+            Ok(false) => "not connected".to_string(),
+            Err(e) => format!("failed ({})", e),
+        };
+        let summary = format!(
+            "Base node connection attempt: peer '{}', dial timeout {:?}, result {}",
+            node_id, dial_timeout, result
+        );
+        if let Some(message) = self
+            .attempt_summaries
+            .check((node_id.clone(), outcome), summary, Instant::now())
+        {
+            log!(target: LOG_TARGET, self.config.connection_attempt_log_level, "{}", message);
+        }
+    }
+
     async fn try_setup_rpc_pool(
         &mut self,
         peer_node_id: NodeId,
         dial_cycle: usize,
     ) -> Result<bool, WalletConnectivityError> {
-        // dial_timeout: 1 = 1s, 2 = 10s, 3 = 20s, 4 = 30s, 5 = 40s, 6 = 50s, 7 = 60s, 8 = 70s, 9 = 80s, 10 = 90s
-        let dial_timeout = TokioDuration::from_secs(min((max(1, 10 * (dial_cycle.saturating_sub(1)))) as u64, 90));
+        let dial_timeout = dial_timeout(dial_cycle);
         trace!(target: LOG_TARGET, "Attempt dial with client timeout {:?}", dial_timeout);
         let conn = match timeout(dial_timeout, self.try_dial_peer(peer_node_id.clone())).await {
             Ok(Ok(Some(c))) => c,
@@ -431,10 +1025,23 @@ impl WalletConnectivityService {
             "Established peer connection to base node '{}'",
             conn.peer_node_id()
         );
+        // Peers that do not advertise their protocols may still support them, so only skip peers that advertise
+        // protocols without the ones we need
+        if !conn.supported_rpc_protocols().is_empty() &&
+            !(conn.supports_rpc::<BaseNodeSyncRpcClient>() && conn.supports_rpc::<BaseNodeWalletRpcClient>())
+        {
+            warn!(
+                target: LOG_TARGET,
+                "Base node '{}' does not support the wallet and sync RPC protocols", peer_node_id
+            );
+            return Ok(false);
+        }
         self.pools.insert(peer_node_id.clone(), ClientPoolContainer {
             base_node_sync_rpc_client: conn.create_rpc_client_pool(1, Default::default()),
             base_node_wallet_rpc_client: conn
                 .create_rpc_client_pool(self.config.base_node_rpc_pool_size, Default::default()),
+            wallet_rpc_errors: ConsecutiveErrorCounter::new(self.config.rpc_error_disconnect_threshold),
+            sync_rpc_errors: ConsecutiveErrorCounter::new(self.config.rpc_error_disconnect_threshold),
         });
         trace!(target: LOG_TARGET, "Created RPC pools for '{}'", peer_node_id);
         Ok(true)
@@ -454,18 +1061,28 @@ impl WalletConnectivityService {
     }
 
     async fn notify_pending_requests(&mut self) -> Result<bool, WalletConnectivityError> {
-        let current_pending = mem::take(&mut self.pending_requests);
+        let pending_wallet_requests = self.pending_wallet_requests.take();
+        let pending_sync_requests = self.pending_sync_requests.take();
         let mut count = 0;
-        let current_pending_len = current_pending.len();
-        for reply in current_pending {
-            if reply.is_canceled() {
+        let current_pending_len = pending_wallet_requests.len() + pending_sync_requests.len();
+        for reply in pending_wallet_requests {
+            if reply.is_closed() {
                 continue;
             }
             count += 1;
             trace!(target: LOG_TARGET, "Handle {} of {} pending RPC pool requests", count, current_pending_len);
-            self.handle_pool_request(reply).await;
+            self.handle_get_base_node_wallet_rpc_client(reply).await;
         }
-        if self.pending_requests.is_empty() {
+        for reply in pending_sync_requests {
+            if reply.is_closed() {
+                continue;
+            }
+            count += 1;
+            trace!(target: LOG_TARGET, "Handle {} of {} pending RPC pool requests", count, current_pending_len);
+            self.handle_get_base_node_sync_rpc_client(reply).await;
+        }
+        self.update_pending_request_metrics();
+        if self.pending_wallet_requests.is_empty() && self.pending_sync_requests.is_empty() {
             Ok(true)
         } else {
             warn!(target: LOG_TARGET, "{} of {} pending RPC pool requests not handled", count, current_pending_len);
@@ -474,28 +1091,57 @@ impl WalletConnectivityService {
     }
 }
 
-enum ReplyOneshot {
-    WalletRpc(oneshot::Sender<RpcClientLease<BaseNodeWalletRpcClient>>),
-    SyncRpc(oneshot::Sender<RpcClientLease<BaseNodeSyncRpcClient>>),
+/// The dial timeout for a connection attempt, growing with the number of cycles through the peer list:
+/// 1 = 1s, 2 = 10s, 3 = 20s, 4 = 30s, 5 = 40s, 6 = 50s, 7 = 60s, 8 = 70s, 9 = 80s, 10 = 90s
+fn dial_timeout(dial_cycle: usize) -> TokioDuration {
+    TokioDuration::from_secs(min((max(1, 10 * (dial_cycle.saturating_sub(1)))) as u64, 90))
 }
 
-impl ReplyOneshot {
-    pub fn is_canceled(&self) -> bool {
-        use ReplyOneshot::{SyncRpc, WalletRpc};
-        match self {
-            WalletRpc(tx) => tx.is_closed(),
-            SyncRpc(tx) => tx.is_closed(),
-        }
+/// The order in which the peers are tried for a connection cycle, as indexes into `peers`
+pub(super) fn candidate_order(
+    selection: BaseNodeSelection,
+    peers: &[Peer],
+    connection_latencies: &HashMap<NodeId, Duration>,
+) -> Vec<usize> {
+    let mut order = (0..peers.len()).collect::<Vec<_>>();
+    match selection {
+        BaseNodeSelection::Ordered => {},
+        BaseNodeSelection::Random => order.shuffle(&mut OsRng),
+        BaseNodeSelection::LatencySorted => {
+            // Peers without a known latency keep their list order after the others
+            order.sort_by_key(|&i| {
+                connection_latencies
+                    .get(&peers[i].node_id)
+                    .map_or((1, Duration::ZERO), |latency| (0, *latency))
+            });
+        },
     }
+    order
 }
 
-impl From<oneshot::Sender<RpcClientLease<BaseNodeWalletRpcClient>>> for ReplyOneshot {
-    fn from(tx: oneshot::Sender<RpcClientLease<BaseNodeWalletRpcClient>>) -> Self {
-        ReplyOneshot::WalletRpc(tx)
+/// Varies `base` randomly by up to `jitter` (clamped to 0.0..=1.0) of it either way
+pub(super) fn jittered_wait(base: Duration, jitter: f64) -> Duration {
+    let jitter = if jitter.is_nan() { 0.0 } else { jitter.clamp(0.0, 1.0) };
+    if jitter == 0.0 {
+        return base;
     }
+    base.mul_f64(OsRng.gen_range(1.0 - jitter..=1.0 + jitter))
 }
-impl From<oneshot::Sender<RpcClientLease<BaseNodeSyncRpcClient>>> for ReplyOneshot {
-    fn from(tx: oneshot::Sender<RpcClientLease<BaseNodeSyncRpcClient>>) -> Self {
-        ReplyOneshot::SyncRpc(tx)
-    }
+
+/// Creates the timer for the periodic connection check, which also serves as a health check for the RPC pools
+pub(super) fn connection_check_timer(config: &BaseNodeServiceConfig) -> time::Interval {
+    let period = if config.connection_check_interval < MIN_CONNECTION_CHECK_INTERVAL {
+        warn!(
+            target: LOG_TARGET,
+            "Connection check interval {:.0?} is too short, using {:.0?}",
+            config.connection_check_interval,
+            MIN_CONNECTION_CHECK_INTERVAL
+        );
+        MIN_CONNECTION_CHECK_INTERVAL
+    } else {
+        config.connection_check_interval
+    };
+    let mut check_connection = time::interval_at(time::Instant::now() + period, period);
+    check_connection.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    check_connection
 }
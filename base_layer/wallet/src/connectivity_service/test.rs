@@ -21,11 +21,19 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use core::convert;
-use std::{iter, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use futures::future;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use tari_common::configuration::Network;
 use tari_comms::{
-    peer_manager::{NodeId, PeerFeatures},
+    connectivity::ConnectivityError,
+    peer_manager::{NodeId, PeerFeatures, PeerFlags},
     protocol::rpc::{
         mock::{MockRpcImpl, MockRpcServer},
         RpcPoolClient,
@@ -33,20 +41,40 @@ use tari_comms::{
     test_utils::{
         mocks::{create_connectivity_mock, ConnectivityManagerMockState},
         node_identity::build_node_identity,
+        peer_manager::build_peer_manager,
     },
     Minimized,
+    PeerManager,
 };
 use tari_shutdown::Shutdown;
 use tari_test_utils::runtime::spawn_until_shutdown;
 use tokio::{
-    sync::{mpsc, Barrier},
+    sync::{mpsc, oneshot, Barrier},
     task,
-    time::{sleep, timeout},
+    time::{sleep, timeout, Instant},
 };
 
-use super::service::{WalletConnectivityService, CONNECTIVITY_WAIT};
+use super::service::{
+    candidate_order,
+    connection_check_timer,
+    jittered_wait,
+    AttemptOutcome,
+    AttemptSummaryLimiter,
+    ConsecutiveErrorCounter,
+    NetworkMismatchExclusions,
+    PendingRequests,
+    WalletConnectivityService,
+    CONNECTIVITY_WAIT,
+};
 use crate::{
-    connectivity_service::{BaseNodePeerManager, OnlineStatus, WalletConnectivityHandle, WalletConnectivityInterface},
+    base_node_service::config::{BaseNodeSelection, BaseNodeServiceConfig},
+    connectivity_service::{
+        BaseNodePeerManager,
+        OnlineStatus,
+        WalletConnectivityError,
+        WalletConnectivityHandle,
+        WalletConnectivityInterface,
+    },
     util::watch::Watch,
 };
 
@@ -55,24 +83,53 @@ async fn setup() -> (
     MockRpcServer<MockRpcImpl>,
     ConnectivityManagerMockState,
     Shutdown,
+) {
+    setup_with_config(Default::default(), None, None, MockRpcImpl::new()).await
+}
+
+async fn setup_with_config(
+    config: BaseNodeServiceConfig,
+    comms_peer_manager: Option<Arc<PeerManager>>,
+    network: Option<Network>,
+    mock_svc: MockRpcImpl,
+) -> (
+    WalletConnectivityHandle,
+    MockRpcServer<MockRpcImpl>,
+    ConnectivityManagerMockState,
+    Shutdown,
 ) {
     let (tx, rx) = mpsc::channel(1);
     let base_node_watch = Watch::new(None);
     let online_status_watch = Watch::new(OnlineStatus::Offline);
-    let handle = WalletConnectivityHandle::new(tx, base_node_watch.clone(), online_status_watch.get_receiver());
+    let ever_connected_watch = Watch::new(false);
+    let time_to_first_online_watch = Watch::new(None);
+    let handle = WalletConnectivityHandle::new(
+        tx,
+        base_node_watch.clone(),
+        online_status_watch.get_receiver(),
+        ever_connected_watch.get_receiver(),
+        time_to_first_online_watch.get_receiver(),
+    );
     let (connectivity, mock) = create_connectivity_mock();
     let mock_state = mock.spawn();
     // let peer_manager = create_peer_manager(tempdir().unwrap());
-    let service = WalletConnectivityService::new(
-        Default::default(),
+    let mut service = WalletConnectivityService::new(
+        config,
         rx,
         base_node_watch,
         online_status_watch,
+        ever_connected_watch,
+        time_to_first_online_watch,
         connectivity,
     );
+    if let Some(comms_peer_manager) = comms_peer_manager {
+        service = service.with_comms_peer_manager(comms_peer_manager);
+    }
+    if let Some(network) = network {
+        service = service.with_network(network);
+    }
     let shutdown = spawn_until_shutdown(service.start());
 
-    let mock_svc = MockRpcImpl::new();
     let mut mock_server = MockRpcServer::new(mock_svc, build_node_identity(PeerFeatures::COMMUNICATION_NODE));
     mock_server.serve();
 
@@ -300,3 +357,676 @@ async fn it_gracefully_handles_multiple_connection_failures() {
     // Still able to get a base node rpc client
     pending_request.await.unwrap();
 }
+
+#[tokio::test]
+async fn it_remembers_that_it_has_been_online_after_a_connection_drops() {
+    let (mut handle, mock_server, mock_state, _shutdown) = setup().await;
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let mut conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+    assert!(!handle.has_ever_connected());
+
+    mock_state.add_active_connection(conn.clone()).await;
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+
+    let rpc_client = handle.obtain_base_node_wallet_rpc_client().await.unwrap();
+    assert!(rpc_client.is_connected());
+    let mut status_watch = handle.get_connectivity_status_watch();
+    timeout(
+        Duration::from_secs(2 * CONNECTIVITY_WAIT),
+        status_watch.wait_for(|status| *status == OnlineStatus::Online),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(handle.has_ever_connected());
+
+    // Drop the connection and wait for the service to notice
+    conn.disconnect(Minimized::No).await.unwrap();
+    drop(rpc_client);
+    timeout(
+        Duration::from_secs(2 * CONNECTIVITY_WAIT),
+        status_watch.wait_for(|status| *status != OnlineStatus::Online),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(handle.has_ever_connected());
+}
+
+#[tokio::test]
+async fn it_records_the_time_to_first_online() {
+    const CONNECT_DELAY: Duration = Duration::from_millis(500);
+    let (mut handle, mock_server, mock_state, _shutdown) = setup().await;
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+    let mut time_to_first_online = handle.get_time_to_first_online_watch();
+    assert!(time_to_first_online.borrow().is_none());
+
+    // Delay the connection to the base node
+    mock_state.set_pending_connection(base_node_peer.node_id()).await;
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+    mock_state.await_call_count(1).await;
+    sleep(CONNECT_DELAY).await;
+    mock_state.add_active_connection(conn).await;
+
+    let elapsed = timeout(
+        Duration::from_secs(2 * CONNECTIVITY_WAIT),
+        time_to_first_online.wait_for(Option::is_some),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .unwrap();
+    assert!(elapsed >= CONNECT_DELAY, "{:?}", elapsed);
+    assert!(
+        elapsed < CONNECT_DELAY + Duration::from_secs(CONNECTIVITY_WAIT),
+        "{:?}",
+        elapsed
+    );
+}
+
+#[tokio::test]
+async fn it_falls_back_to_seed_peers_when_the_configured_base_node_is_unreachable() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let comms_peer_manager = build_peer_manager(tempdir.path());
+    let seed_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let mut peer = seed_peer.to_peer();
+    peer.add_flags(PeerFlags::SEED);
+    comms_peer_manager.add_peer(peer).await.unwrap();
+
+    let config = BaseNodeServiceConfig {
+        allow_seed_fallback: true,
+        seed_fallback_threshold: 1,
+        connection_check_interval: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let (mut handle, mock_server, mock_state, _shutdown) =
+        setup_with_config(config, Some(comms_peer_manager), None, MockRpcImpl::new()).await;
+    // Only the seed peer is reachable
+    let conn = mock_server.create_mockimpl_connection(seed_peer.to_peer()).await;
+    mock_state.add_active_connection(conn.clone()).await;
+
+    let unreachable_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![unreachable_peer.to_peer()]).unwrap());
+
+    let rpc_client = timeout(
+        Duration::from_secs(2 * CONNECTIVITY_WAIT),
+        handle.obtain_base_node_wallet_rpc_client(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert!(rpc_client.is_connected());
+    // The configured base node may be redialed since, while the wallet checks if it is reachable again
+    assert_eq!(mock_state.get_dialed_peers().await[..2], [
+        unreachable_peer.node_id().clone(),
+        seed_peer.node_id().clone()
+    ]);
+    assert_eq!(
+        handle.get_current_base_node_peer_node_id().as_ref(),
+        Some(seed_peer.node_id())
+    );
+    // The configured base node is kept in the peer list, ahead of the seeds
+    let (_, peer_list) = handle.get_base_node_peer_manager_state().unwrap();
+    assert_eq!(
+        peer_list.iter().map(|p| &p.node_id).collect::<Vec<_>>(),
+        vec![unreachable_peer.node_id(), seed_peer.node_id()]
+    );
+    drop(rpc_client);
+
+    // Once the configured base node is reachable again, the wallet switches back to it
+    let conn = mock_server.create_mockimpl_connection(unreachable_peer.to_peer()).await;
+    mock_state.add_active_connection(conn).await;
+    timeout(Duration::from_secs(10), async {
+        while handle.get_current_base_node_peer_node_id().as_ref() != Some(unreachable_peer.node_id()) {
+            sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+    let rpc_client = handle.obtain_base_node_wallet_rpc_client().await.unwrap();
+    assert!(rpc_client.is_connected());
+    assert_eq!(
+        handle.get_current_base_node_peer_node_id().as_ref(),
+        Some(unreachable_peer.node_id())
+    );
+}
+
+#[tokio::test]
+async fn it_uses_the_configured_connection_check_interval() {
+    let config = BaseNodeServiceConfig {
+        connection_check_interval: Duration::from_secs(12),
+        ..Default::default()
+    };
+    assert_eq!(connection_check_timer(&config).period(), Duration::from_secs(12));
+
+    // Intervals below the minimum are clamped
+    let config = BaseNodeServiceConfig {
+        connection_check_interval: Duration::from_millis(100),
+        ..Default::default()
+    };
+    assert_eq!(connection_check_timer(&config).period(), Duration::from_secs(1));
+}
+
+#[test]
+fn it_does_not_disconnect_on_a_transient_rpc_error() {
+    let mut errors = ConsecutiveErrorCounter::new(BaseNodeServiceConfig::default().rpc_error_disconnect_threshold);
+    // A single error followed by a successful `get()` does not reach the threshold
+    assert!(!errors.record_error());
+    errors.reset();
+    assert_eq!(errors.count(), 0);
+    assert!(!errors.record_error());
+
+    // Consecutive errors up to the threshold trigger a disconnect
+    let mut errors = ConsecutiveErrorCounter::new(2);
+    assert!(!errors.record_error());
+    assert!(errors.record_error());
+
+    // The threshold is at least 1, i.e. disconnect on the first error
+    let mut errors = ConsecutiveErrorCounter::new(0);
+    assert!(errors.record_error());
+}
+
+#[test]
+fn it_rate_limits_connection_attempt_summaries_by_peer_and_outcome() {
+    let mut summaries = AttemptSummaryLimiter::new(Duration::from_secs(60));
+    let start = Instant::now();
+    let peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE).node_id().clone();
+    let failed = || {
+        let result = Err(WalletConnectivityError::ConnectivityError(ConnectivityError::DialCancelled));
+        (peer.clone(), AttemptOutcome::from_result(&result))
+    };
+    let attempt = |dial_timeout: &str, result: &str| {
+        format!(
+            "Base node connection attempt: peer 'a', dial timeout {}, result {}",
+            dial_timeout, result
+        )
+    };
+
+    // Retrying the same peer with the same kind of result is only logged once per interval, even though the dial
+    // timeout grows between attempts
+    assert_eq!(
+        summaries.check(failed(), attempt("1s", "failed"), start),
+        Some(attempt("1s", "failed"))
+    );
+    assert_eq!(
+        summaries.check(failed(), attempt("10s", "failed"), start + Duration::from_secs(5)),
+        None
+    );
+    assert_eq!(
+        summaries.check(failed(), attempt("20s", "failed"), start + Duration::from_secs(10)),
+        None
+    );
+    assert_eq!(
+        summaries.check(failed(), attempt("30s", "failed"), start + Duration::from_secs(61)),
+        Some(format!("{} (2 similar attempts suppressed)", attempt("30s", "failed")))
+    );
+
+    // A different kind of result is logged straight away
+    assert_eq!(
+        summaries.check(
+            (peer.clone(), AttemptOutcome::from_result(&Ok(true))),
+            attempt("30s", "connected"),
+            start + Duration::from_secs(62)
+        ),
+        Some(attempt("30s", "connected"))
+    );
+}
+
+/// Records the connection attempt summaries logged by any test in this module
+struct AttemptSummaryCapture(Mutex<Vec<String>>);
+
+impl Log for AttemptSummaryCapture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        let message = record.args().to_string();
+        if message.starts_with("Base node connection attempt") {
+            self.0.lock().unwrap().push(message);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static ATTEMPT_SUMMARIES: AttemptSummaryCapture = AttemptSummaryCapture(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn it_logs_one_summary_for_repeated_failures_with_growing_dial_timeouts() {
+    log::set_logger(&ATTEMPT_SUMMARIES).expect("no other logger is set in these tests");
+    log::set_max_level(LevelFilter::Info);
+    let config = BaseNodeServiceConfig {
+        connectivity_retry_wait: Duration::from_millis(10),
+        connectivity_retry_jitter: 0.0,
+        ..Default::default()
+    };
+    let (mut handle, _mock_server, mock_state, _shutdown) =
+        setup_with_config(config, None, None, MockRpcImpl::new()).await;
+    // There is no connection to the base node, so every dial fails straight away
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+
+    timeout(Duration::from_secs(5), async {
+        loop {
+            let dials = mock_state
+                .get_dialed_peers()
+                .await
+                .iter()
+                .filter(|node_id| *node_id == base_node_peer.node_id())
+                .count();
+            if dials >= 4 {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let peer = format!("peer '{}'", base_node_peer.node_id());
+    let summaries = ATTEMPT_SUMMARIES
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|summary| summary.contains(&peer))
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(summaries.len(), 1, "{:?}", summaries);
+    assert!(summaries[0].contains("dial timeout 1s"));
+}
+
+#[test]
+fn it_varies_the_first_dial_target_with_random_base_node_selection() {
+    let peers = (0..5)
+        .map(|_| build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer())
+        .collect::<Vec<_>>();
+    let mut peer_manager = BaseNodePeerManager::from_seeds(peers.clone()).unwrap();
+
+    let mut first_dial_targets = HashSet::new();
+    for _ in 0..50 {
+        peer_manager
+            .set_dial_order(candidate_order(BaseNodeSelection::Random, &peers, &HashMap::new()))
+            .unwrap();
+        first_dial_targets.insert(peer_manager.get_current_peer().node_id);
+        // Every peer is still tried once per cycle, and the cycle ends with the last peer in the order
+        let mut dialed = HashSet::from([peer_manager.get_current_peer().node_id]);
+        while !peer_manager.is_last_in_dial_order() {
+            dialed.insert(peer_manager.get_next_peer().node_id);
+        }
+        assert_eq!(dialed.len(), peers.len());
+    }
+    assert!(first_dial_targets.len() > 1);
+
+    // A preferred peer, e.g. the base node set by the user, stays first in every cycle
+    let mut peer_manager = BaseNodePeerManager::new(2, peers.clone()).unwrap();
+    for _ in 0..10 {
+        peer_manager
+            .set_dial_order(candidate_order(BaseNodeSelection::Random, &peers, &HashMap::new()))
+            .unwrap();
+        assert_eq!(peer_manager.get_current_peer().node_id, peers[2].node_id);
+    }
+
+    assert_eq!(
+        candidate_order(BaseNodeSelection::Ordered, &peers, &HashMap::new()),
+        vec![0, 1, 2, 3, 4]
+    );
+    let latencies = HashMap::from([
+        (peers[3].node_id.clone(), Duration::from_millis(50)),
+        (peers[1].node_id.clone(), Duration::from_millis(200)),
+    ]);
+    assert_eq!(
+        candidate_order(BaseNodeSelection::LatencySorted, &peers, &latencies),
+        vec![3, 1, 0, 2, 4]
+    );
+    assert!(peer_manager.set_dial_order(vec![0, 1, 2]).is_err());
+}
+
+#[tokio::test]
+async fn it_reorders_the_base_node_peers_for_every_connection_cycle() {
+    let config = BaseNodeServiceConfig {
+        base_node_selection: BaseNodeSelection::Random,
+        ..Default::default()
+    };
+    let (mut handle, _mock_server, mock_state, _shutdown) =
+        setup_with_config(config, None, None, MockRpcImpl::new()).await;
+    // There are no connections to the base nodes, so every dial fails straight away
+    let peers = (0..5)
+        .map(|_| build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer())
+        .collect::<Vec<_>>();
+    handle.set_base_node(BaseNodePeerManager::from_seeds(peers.clone()).unwrap());
+
+    let dialed = timeout(Duration::from_secs(10), async {
+        loop {
+            let dialed = mock_state.get_dialed_peers().await;
+            if dialed.len() >= 4 * peers.len() {
+                break dialed;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    let cycles = dialed.chunks(peers.len()).take(4).collect::<Vec<_>>();
+    // Every cycle tries each peer once, in a new order
+    for cycle in &cycles {
+        assert_eq!(cycle.iter().collect::<HashSet<_>>().len(), peers.len());
+    }
+    assert!(cycles.iter().any(|cycle| *cycle != cycles[0]));
+}
+
+#[test]
+fn it_jitters_the_connectivity_retry_wait() {
+    let base = Duration::from_secs(CONNECTIVITY_WAIT);
+    let waits = (0..50).map(|_| jittered_wait(base, 0.2)).collect::<HashSet<_>>();
+    assert!(waits.len() > 1);
+    for wait in waits {
+        assert!(wait >= base.mul_f64(0.8) && wait <= base.mul_f64(1.2), "{:?}", wait);
+    }
+
+    assert_eq!(jittered_wait(base, 0.0), base);
+    assert_eq!(jittered_wait(base, f64::NAN), base);
+    assert!(jittered_wait(base, 5.0) <= base * 2);
+}
+
+#[tokio::test]
+async fn it_bounds_the_pending_requests_of_each_rpc_type_independently() {
+    let config = BaseNodeServiceConfig {
+        max_pending_wallet_rpc_requests: 2,
+        max_pending_sync_rpc_requests: 2,
+        ..Default::default()
+    };
+    // No base node is set, so all requests wait for a connection
+    let (handle, _mock_server, _mock_state, _shutdown) =
+        setup_with_config(config, None, None, MockRpcImpl::new()).await;
+
+    // Requests beyond the cap are dropped, which the caller sees as None
+    let wallet_requests = (0..3).map(|_| {
+        let mut handle = handle.clone();
+        task::spawn(
+            async move { timeout(Duration::from_millis(500), handle.obtain_base_node_wallet_rpc_client()).await },
+        )
+    });
+    let results = future::join_all(wallet_requests).await;
+    assert_eq!(results.iter().filter(|r| matches!(r, Ok(Ok(None)))).count(), 1);
+    assert_eq!(results.iter().filter(|r| matches!(r, Ok(Err(_)))).count(), 2);
+
+    // The wallet requests have since been canceled, and the full wallet queue does not affect sync requests
+    let sync_requests = (0..2).map(|_| {
+        let mut handle = handle.clone();
+        task::spawn(async move { timeout(Duration::from_millis(500), handle.obtain_base_node_sync_rpc_client()).await })
+    });
+    let results = future::join_all(sync_requests).await;
+    assert!(results.iter().all(|r| matches!(r, Ok(Err(_)))));
+}
+
+#[test]
+fn it_evicts_canceled_pending_requests_first_in_first_out() {
+    let mut pending = PendingRequests::<u32>::new(2);
+    let (tx1, rx1) = oneshot::channel();
+    let (tx2, _rx2) = oneshot::channel();
+    let (tx3, _rx3) = oneshot::channel();
+    let (tx4, _rx4) = oneshot::channel();
+    pending.push(tx1).unwrap();
+    pending.push(tx2).unwrap();
+    assert!(pending.push(tx3).is_err());
+
+    // Once the first caller goes away, there is room for another request, queued after the others
+    drop(rx1);
+    pending.push(tx4).unwrap();
+    assert_eq!(pending.len(), 2);
+    let queued = pending.take();
+    assert!(queued.iter().all(|reply| !reply.is_closed()));
+    assert!(pending.is_empty());
+}
+
+#[tokio::test]
+async fn it_rejects_a_base_node_on_a_different_network() {
+    // The 'get_network' method of the base node wallet RPC service
+    const GET_NETWORK_METHOD: u32 = 13;
+    let mock_svc = MockRpcImpl::new();
+    mock_svc
+        .set_response(GET_NETWORK_METHOD, u32::from(Network::Esmeralda.as_byte()))
+        .await;
+    let (mut handle, mock_server, mock_state, _shutdown) =
+        setup_with_config(Default::default(), None, Some(Network::MainNet), mock_svc).await;
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+    mock_state.add_active_connection(conn.clone()).await;
+
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+
+    // The connection is dropped once the base node reports the wrong network, and the wallet never comes online
+    let mut attempts = 0;
+    while conn.is_connected() {
+        attempts += 1;
+        assert!(attempts <= 50, "the base node connection was not rejected");
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert_ne!(handle.get_connectivity_status(), OnlineStatus::Online);
+    assert!(
+        timeout(Duration::from_millis(500), handle.obtain_base_node_wallet_rpc_client())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn it_moves_on_from_a_base_node_on_a_different_network() {
+    // The 'get_network' method of the base node wallet RPC service
+    const GET_NETWORK_METHOD: u32 = 13;
+    let mock_svc = MockRpcImpl::new();
+    mock_svc
+        .set_response(GET_NETWORK_METHOD, u32::from(Network::Esmeralda.as_byte()))
+        .await;
+    let (mut handle, mock_server, mock_state, _shutdown) =
+        setup_with_config(Default::default(), None, Some(Network::MainNet), mock_svc).await;
+    let wrong_network_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let wrong_network_conn = mock_server
+        .create_mockimpl_connection(wrong_network_peer.to_peer())
+        .await;
+    mock_state.add_active_connection(wrong_network_conn.clone()).await;
+
+    let mainnet_svc = MockRpcImpl::new();
+    mainnet_svc
+        .set_response(GET_NETWORK_METHOD, u32::from(Network::MainNet.as_byte()))
+        .await;
+    let mut mainnet_server = MockRpcServer::new(mainnet_svc, build_node_identity(PeerFeatures::COMMUNICATION_NODE));
+    mainnet_server.serve();
+    let mainnet_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    // Hold back the connection to the next peer so that the status after the rejection can be observed
+    mock_state.set_pending_connection(mainnet_peer.node_id()).await;
+
+    let status_watch = handle.get_connectivity_status_watch();
+    handle.set_base_node(
+        BaseNodePeerManager::new(0, vec![wrong_network_peer.to_peer(), mainnet_peer.to_peer()]).unwrap(),
+    );
+
+    // The wallet reports that it is offline once the base node on the wrong network is rejected, before the next peer
+    // is connected
+    wait_for_peers_to_be_dialed(&mock_state, &[mainnet_peer.node_id()], Duration::from_secs(10)).await;
+    assert_eq!(*status_watch.borrow(), OnlineStatus::Offline);
+    assert!(!wrong_network_conn.is_connected());
+
+    // The next peer is used instead
+    let mainnet_conn = mainnet_server.create_mockimpl_connection(mainnet_peer.to_peer()).await;
+    mock_state.add_active_connection(mainnet_conn).await;
+    let rpc_client = timeout(Duration::from_secs(10), handle.obtain_base_node_wallet_rpc_client())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(rpc_client.is_connected());
+    assert_eq!(
+        handle.get_current_base_node_peer_node_id().as_ref(),
+        Some(mainnet_peer.node_id())
+    );
+    assert!(!wrong_network_conn.is_connected());
+}
+
+#[test]
+fn it_skips_excluded_peers_while_others_are_available() {
+    let peers = (0..2)
+        .map(|_| build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer())
+        .collect::<Vec<_>>();
+    let mut exclusions = NetworkMismatchExclusions::new(Duration::from_secs(60));
+    let now = Instant::now();
+    exclusions.exclude(peers[0].node_id.clone(), now);
+
+    assert!(exclusions.should_skip(&peers[0].node_id, &peers, now));
+    assert!(!exclusions.should_skip(&peers[1].node_id, &peers, now));
+    // A peer is still tried if every peer is excluded
+    exclusions.exclude(peers[1].node_id.clone(), now);
+    assert!(!exclusions.should_skip(&peers[0].node_id, &peers, now));
+
+    // Exclusions expire
+    let later = now + Duration::from_secs(61);
+    assert!(!exclusions.is_excluded(&peers[0].node_id, later));
+    assert!(!exclusions.is_excluded(&peers[1].node_id, later));
+}
+
+#[tokio::test]
+async fn it_force_reconnects_to_the_current_base_node() {
+    let (mut handle, mock_server, mock_state, _shutdown) = setup().await;
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+    mock_state.add_active_connection(conn.clone()).await;
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+    let rpc_client = handle.obtain_base_node_wallet_rpc_client().await.unwrap();
+    assert!(rpc_client.is_connected());
+    drop(rpc_client);
+
+    // Hold back the new connection so that the intermediate state can be observed
+    mock_state.clear_dialed_peers().await;
+    mock_state.set_pending_connection(base_node_peer.node_id()).await;
+    handle.force_reconnect().await;
+
+    let mut status_watch = handle.get_connectivity_status_watch();
+    timeout(
+        Duration::from_secs(5),
+        status_watch.wait_for(|status| *status == OnlineStatus::Connecting),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    wait_for_peers_to_be_dialed(&mock_state, &[base_node_peer.node_id()], Duration::from_secs(5)).await;
+    // The existing connection was torn down and only the same base node was dialed again
+    assert!(!conn.is_connected());
+    assert!(mock_state
+        .get_dialed_peers()
+        .await
+        .iter()
+        .all(|node_id| node_id == base_node_peer.node_id()));
+
+    let conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+    mock_state.add_active_connection(conn).await;
+    timeout(
+        Duration::from_secs(5),
+        status_watch.wait_for(|status| *status == OnlineStatus::Online),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let rpc_client = handle.obtain_base_node_wallet_rpc_client().await.unwrap();
+    assert!(rpc_client.is_connected());
+    assert_eq!(
+        handle.get_current_base_node_peer_node_id().as_ref(),
+        Some(base_node_peer.node_id())
+    );
+}
+
+#[tokio::test]
+async fn it_returns_the_current_base_node_details() {
+    let (mut handle, mock_server, mock_state, _shutdown) = setup().await;
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+
+    // No base node has been set yet
+    assert!(handle.get_current_base_node_details().await.is_none());
+
+    mock_state.add_active_connection(conn.clone()).await;
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+    let _rpc_client = handle.obtain_base_node_wallet_rpc_client().await.unwrap();
+
+    let details = handle.get_current_base_node_details().await.unwrap();
+    assert_eq!(&details.node_id, base_node_peer.node_id());
+    assert_eq!(&details.public_key, base_node_peer.public_key());
+    assert_eq!(&details.address, conn.address());
+    assert_eq!(
+        details.addresses,
+        base_node_peer
+            .to_peer()
+            .addresses
+            .address_iter()
+            .cloned()
+            .collect::<Vec<_>>()
+    );
+    assert!(details.connection_age <= conn.age());
+}
+
+#[tokio::test]
+async fn it_notifies_subscribers_of_base_node_changes() {
+    let (mut handle, _mock_server, _mock_state, _shutdown) = setup().await;
+    let mut subscriber = handle.subscribe_base_node_changes();
+    assert!(subscriber.borrow().is_none());
+
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+    subscriber.changed().await.unwrap();
+    assert_eq!(
+        subscriber.borrow_and_update().as_ref().map(|p| p.get_current_peer().node_id.clone()),
+        Some(base_node_peer.node_id().clone())
+    );
+
+    // A late subscriber sees the current base node without being notified of it again
+    let mut late_subscriber = handle.subscribe_base_node_changes();
+    assert!(!late_subscriber.has_changed().unwrap());
+    assert_eq!(
+        late_subscriber.borrow().as_ref().map(|p| p.get_current_peer().node_id.clone()),
+        Some(base_node_peer.node_id().clone())
+    );
+
+    let next_base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![next_base_node_peer.to_peer()]).unwrap());
+    for receiver in [&mut subscriber, &mut late_subscriber] {
+        receiver.changed().await.unwrap();
+        assert_eq!(
+            receiver.borrow().as_ref().map(|p| p.get_current_peer().node_id.clone()),
+            Some(next_base_node_peer.node_id().clone())
+        );
+    }
+}
+
+#[tokio::test]
+async fn it_retries_a_transient_rpc_pool_error_without_disconnecting() {
+    let config = BaseNodeServiceConfig {
+        base_node_rpc_pool_size: 1,
+        ..Default::default()
+    };
+    let (mut handle, mock_server, mock_state, _shutdown) =
+        setup_with_config(config, None, None, MockRpcImpl::new()).await;
+    let base_node_peer = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let conn = mock_server.create_mockimpl_connection(base_node_peer.to_peer()).await;
+    mock_state.add_active_connection(conn.clone()).await;
+    handle.set_base_node(BaseNodePeerManager::new(0, vec![base_node_peer.to_peer()]).unwrap());
+
+    // Close the only session in the pool, so that the next request fails once before a new session is opened
+    let mut rpc_client = handle.obtain_base_node_wallet_rpc_client().await.unwrap();
+    rpc_client.close().await;
+    let mut attempts = 0;
+    while rpc_client.is_connected() {
+        attempts += 1;
+        assert!(attempts <= 50, "the RPC session was not closed");
+        sleep(Duration::from_millis(100)).await;
+    }
+    drop(rpc_client);
+
+    let rpc_client = timeout(Duration::from_secs(5), handle.obtain_base_node_wallet_rpc_client())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(rpc_client.is_connected());
+    assert!(conn.is_connected());
+    assert_eq!(handle.get_connectivity_status(), OnlineStatus::Online);
+}
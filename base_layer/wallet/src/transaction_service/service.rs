@@ -1687,7 +1687,7 @@ where
     ) -> Result<TxId, TransactionServiceError> {
         let tx_id = TxId::new_random();
         let payment_id = match payment_id.clone() {
-            PaymentId::Open { .. } | PaymentId::Empty => PaymentId::add_sender_address(
+            PaymentId::Open { .. } | PaymentId::TypeOnly(_) | PaymentId::Empty => PaymentId::add_sender_address(
                 payment_id,
                 self.resources.interactive_tari_address.clone(),
                 if dest_address == self.resources.one_sided_tari_address ||
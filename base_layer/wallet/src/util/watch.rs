@@ -58,4 +58,9 @@ impl<T> Watch<T> {
     pub fn get_receiver(&self) -> watch::Receiver<T> {
         self.receiver().clone()
     }
+
+    /// Returns a new receiver that sees the current value and is only notified of subsequent changes
+    pub fn subscribe(&self) -> watch::Receiver<T> {
+        self.sender().subscribe()
+    }
 }
@@ -242,7 +242,9 @@ where
                 config.base_node_service_config.clone(),
                 wallet_database.clone(),
             ))
-            .add_initializer(WalletConnectivityInitializer::new(config.base_node_service_config))
+            .add_initializer(
+                WalletConnectivityInitializer::new(config.base_node_service_config).with_network(config.network),
+            )
             .add_initializer(UtxoScannerServiceInitializer::<T, TKeyManagerInterface>::new(
                 wallet_database.clone(),
                 factories.clone(),
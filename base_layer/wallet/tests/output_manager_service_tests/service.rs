@@ -77,7 +77,7 @@ use tari_script::{inputs, script, TariScript};
 use tari_service_framework::reply_channel;
 use tari_shutdown::Shutdown;
 use tokio::{
-    sync::{broadcast, broadcast::channel},
+    sync::{broadcast, broadcast::channel, watch},
     task,
     time::sleep,
 };
@@ -127,7 +127,8 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
 
     let (sender, receiver_bns) = reply_channel::unbounded();
     let (event_publisher_bns, _) = broadcast::channel(100);
-    let basenode_service_handle = BaseNodeServiceHandle::new(sender, event_publisher_bns.clone());
+    let (_, tip_height_watch) = watch::channel(None);
+    let basenode_service_handle = BaseNodeServiceHandle::new(sender, event_publisher_bns.clone(), tip_height_watch);
     let mut mock_base_node_service = MockBaseNodeService::new(receiver_bns, shutdown.to_signal());
     mock_base_node_service.set_default_base_node_state();
     task::spawn(mock_base_node_service.run());
@@ -216,7 +217,8 @@ pub async fn setup_oms_with_bn_state<T: OutputManagerBackend + 'static>(
     let (sender, receiver_bns) = reply_channel::unbounded();
     let (event_publisher_bns, _) = broadcast::channel(100);
 
-    let base_node_service_handle = BaseNodeServiceHandle::new(sender, event_publisher_bns.clone());
+    let (_, tip_height_watch) = watch::channel(None);
+    let base_node_service_handle = BaseNodeServiceHandle::new(sender, event_publisher_bns.clone(), tip_height_watch);
     let mut mock_base_node_service = MockBaseNodeService::new(receiver_bns, shutdown.to_signal());
     mock_base_node_service.set_base_node_state(height);
     task::spawn(mock_base_node_service.run());
@@ -31,6 +31,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use tari_common::configuration::Network;
 use tari_common_types::types::{FixedHash, HashOutput, Signature};
 use tari_comms::{
     protocol::rpc::{NamedProtocolService, Request, Response, RpcClient, RpcStatus, Streaming},
@@ -850,6 +851,12 @@ impl BaseNodeWalletService for BaseNodeWalletRpcMockService {
             acquire_lock!(self.state.get_mempool_fee_per_gram_stats).clone(),
         ))
     }
+
+    async fn get_network(&self, _request: Request<()>) -> Result<Response<u32>, RpcStatus> {
+        Ok(Response::new(u32::from(
+            Network::get_current_or_user_setting_or_default().as_byte(),
+        )))
+    }
 }
 
 #[derive(Clone, Debug)]
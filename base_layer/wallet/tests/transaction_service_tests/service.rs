@@ -166,7 +166,7 @@ use tari_test_utils::{comms_and_services::get_next_memory_address, random};
 use tari_utilities::{ByteArray, SafePassword};
 use tempfile::tempdir;
 use tokio::{
-    sync::{broadcast, broadcast::channel},
+    sync::{broadcast, broadcast::channel, watch},
     task,
     time::sleep,
 };
@@ -373,7 +373,9 @@ async fn setup_transaction_service_no_comms(
     let (sender, receiver_bns) = reply_channel::unbounded();
     let (base_node_service_event_publisher, _) = broadcast::channel(100);
 
-    let base_node_service_handle = BaseNodeServiceHandle::new(sender, base_node_service_event_publisher);
+    let (_, tip_height_watch) = watch::channel(None);
+    let base_node_service_handle =
+        BaseNodeServiceHandle::new(sender, base_node_service_event_publisher, tip_height_watch);
     let mut mock_base_node_service = MockBaseNodeService::new(receiver_bns, shutdown.to_signal());
     mock_base_node_service.set_default_base_node_state();
     task::spawn(mock_base_node_service.run());
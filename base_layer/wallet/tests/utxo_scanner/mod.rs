@@ -74,7 +74,7 @@ use tari_test_utils::random;
 use tari_utilities::{epoch_time::EpochTime, ByteArray, SafePassword};
 use tempfile::{tempdir, TempDir};
 use tokio::{
-    sync::{broadcast, mpsc},
+    sync::{broadcast, mpsc, watch},
     task,
     time,
 };
@@ -114,7 +114,8 @@ async fn setup(
     // Base Node Service Mock
     let (sender, receiver_bns) = reply_channel::unbounded();
     let (event_publisher_bns, _) = broadcast::channel(100);
-    let base_node_service_handle = BaseNodeServiceHandle::new(sender, event_publisher_bns.clone());
+    let (_, tip_height_watch) = watch::channel(None);
+    let base_node_service_handle = BaseNodeServiceHandle::new(sender, event_publisher_bns.clone(), tip_height_watch);
     let mut mock_base_node_service = MockBaseNodeService::new(receiver_bns, shutdown.to_signal());
     mock_base_node_service.set_default_base_node_state();
     task::spawn(mock_base_node_service.run());
@@ -2658,7 +2658,8 @@ pub unsafe extern "C" fn transaction_type_from_encrypted_data(
                         .extract_payment_id_from_encrypted_data(&(*encrypted_data), &commitment, None),
                 ) {
                     Ok(payment_id) => {
-                        if let PaymentId::Open { tx_type, .. } |
+                        if let PaymentId::TypeOnly(tx_type) |
+                        PaymentId::Open { tx_type, .. } |
                         PaymentId::AddressAndData { tx_type, .. } |
                         PaymentId::TransactionInfo { tx_type, .. } = payment_id
                         {
@@ -37,7 +37,7 @@ static CURRENT_NETWORK: OnceLock<Network> = OnceLock::new();
 /// Represents the available Tari p2p networks. Only nodes with matching byte values will be able to connect, so these
 /// should never be changed once released.
 #[repr(u8)]
-#[derive(Clone, Debug, PartialEq, Eq, Copy, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Copy, Serialize, Deserialize)]
 #[serde(try_from = "String", into = "String")]
 pub enum Network {
     MainNet = 0x00,
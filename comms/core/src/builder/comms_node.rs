@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{iter, sync::Arc};
+use std::{iter, sync::Arc, time::Duration};
 
 use log::*;
 use tari_shutdown::ShutdownSignal;
@@ -125,6 +125,11 @@ impl UnspawnedCommsNode {
         self
     }
 
+    /// The configured TCP keepalive probe interval, to be applied to the transport used to spawn this node
+    pub fn keepalive_probe_interval(&self) -> Option<Duration> {
+        self.builder.connectivity_config.keepalive_probe_interval
+    }
+
     /// Spawn a new node using the specified [Transport](crate::transports::Transport).
     #[allow(clippy::too_many_lines)]
     pub async fn spawn_with_transport<TTransport>(self, transport: TTransport) -> Result<CommsNode, CommsBuilderError>
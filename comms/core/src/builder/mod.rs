@@ -43,7 +43,12 @@ use tokio::sync::{broadcast, mpsc};
 use crate::{
     backoff::{Backoff, BoxedBackoff, ConstantBackoff},
     connection_manager::{ConnectionManagerConfig, ConnectionManagerRequester},
-    connectivity::{ConnectivityConfig, ConnectivityRequester},
+    connectivity::{
+        ConnectionPruningPreference,
+        ConnectionTieBreakStrategy,
+        ConnectivityConfig,
+        ConnectivityRequester,
+    },
     multiaddr::Multiaddr,
     net_address::MultiaddrRange,
     peer_manager::{NodeIdentity, PeerManager},
@@ -273,6 +278,25 @@ impl CommsBuilder {
         self
     }
 
+    /// Sets the strategy used to select which connection to keep when two connections to the same peer exist.
+    pub fn with_connection_tie_break_strategy(mut self, strategy: ConnectionTieBreakStrategy) -> Self {
+        self.connectivity_config.tie_break_strategy = strategy;
+        self
+    }
+
+    /// Send TCP keepalive probes on connections that have been idle for the given interval, or None to disable
+    /// (default). This only applies when the node is spawned with a TCP transport.
+    pub fn with_keepalive_probe_interval(mut self, interval: Option<Duration>) -> Self {
+        self.connectivity_config.keepalive_probe_interval = interval;
+        self
+    }
+
+    /// Sets the maximum number of dials that are in flight at once, further dials are queued
+    pub fn with_max_concurrent_dials(mut self, max_concurrent_dials: usize) -> Self {
+        self.connectivity_config.max_concurrent_dials = max_concurrent_dials;
+        self
+    }
+
     /// Call to disable connection reaping. Usually you would want to have this enabled, however there are some test
     /// cases where disabling this is desirable.
     pub fn disable_connection_reaping(mut self) -> Self {
@@ -301,6 +325,12 @@ impl CommsBuilder {
         self
     }
 
+    /// How equidistant connections are ordered when pruning to the closest number of peer connections
+    pub fn with_connection_pruning_preference(mut self, preference: ConnectionPruningPreference) -> Self {
+        self.connectivity_config.pruning_preference = preference;
+        self
+    }
+
     /// The closest number of peer connections to maintain; connections above the threshold will be removed
     pub fn with_minimize_connections(mut self, connections: Option<usize>) -> Self {
         self.maintain_n_closest_connections_only = connections;
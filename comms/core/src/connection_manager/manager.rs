@@ -38,7 +38,7 @@ use super::{
     dialer::{Dialer, DialerRequest},
     error::ConnectionManagerError,
     listener::PeerListener,
-    peer_connection::PeerConnection,
+    peer_connection::{DisconnectReason, PeerConnection},
     requester::ConnectionManagerRequest,
 };
 #[cfg(feature = "metrics")]
@@ -69,7 +69,8 @@ const DIALER_REQUEST_CHANNEL_SIZE: usize = 32;
 pub enum ConnectionManagerEvent {
     // Peer connection
     PeerConnected(Box<PeerConnection>),
-    PeerDisconnected(ConnectionId, NodeId, Minimized),
+    /// The reason is `None` if the peer closed the connection
+    PeerDisconnected(ConnectionId, NodeId, Minimized, Option<DisconnectReason>),
     PeerConnectFailed(NodeId, ConnectionManagerError),
     PeerInboundConnectFailed(ConnectionManagerError),
 
@@ -86,8 +87,15 @@ impl fmt::Display for ConnectionManagerEvent {
         use ConnectionManagerEvent::*;
         match self {
             PeerConnected(conn) => write!(f, "PeerConnected({})", conn),
-            PeerDisconnected(id, node_id, minimized) => {
-                write!(f, "PeerDisconnected({}, {}, {:?})", id, node_id.short_str(), minimized)
+            PeerDisconnected(id, node_id, minimized, reason) => {
+                write!(
+                    f,
+                    "PeerDisconnected({}, {}, {:?}, {:?})",
+                    id,
+                    node_id.short_str(),
+                    minimized,
+                    reason
+                )
             },
             PeerConnectFailed(node_id, err) => write!(f, "PeerConnectFailed({}, {:?})", node_id.short_str(), err),
             PeerInboundConnectFailed(err) => write!(f, "PeerInboundConnectFailed({:?})", err),
@@ -26,6 +26,7 @@ use std::{
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
+        OnceLock,
     },
     time::{Duration, Instant},
 };
@@ -95,6 +96,7 @@ pub fn create(
         peer_addr,
         direction,
         substream_counter,
+        their_supported_protocols.clone(),
     );
     let peer_actor = PeerConnectionActor::new(
         id,
@@ -119,13 +121,40 @@ pub enum PeerConnectionRequest {
         protocol_id: ProtocolId,
         reply_tx: oneshot::Sender<Result<NegotiatedSubstream<Substream>, PeerConnectionError>>,
     },
-    /// Disconnect all substreams and close the transport connection
-    Disconnect(bool, oneshot::Sender<Result<(), PeerConnectionError>>, Minimized),
+    /// Disconnect all substreams and close the transport connection, optionally recording the reason in the
+    /// `PeerDisconnected` event
+    Disconnect(
+        bool,
+        oneshot::Sender<Result<(), PeerConnectionError>>,
+        Minimized,
+        Option<DisconnectReason>,
+    ),
 }
 
 /// ID type for peer connections
 pub type ConnectionId = usize;
 
+/// The reason a peer connection was disconnected locally
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The peer did not respond to liveness checks
+    Unresponsive,
+    /// The disconnect was requested without a more specific reason
+    Requested,
+    /// The local node is shutting down
+    Shutdown,
+}
+
+impl fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisconnectReason::Unresponsive => write!(f, "peer is unresponsive"),
+            DisconnectReason::Requested => write!(f, "disconnect requested"),
+            DisconnectReason::Shutdown => write!(f, "node is shutting down"),
+        }
+    }
+}
+
 /// Request handle for an active peer connection
 #[derive(Debug, Clone)]
 pub struct PeerConnection {
@@ -141,6 +170,8 @@ pub struct PeerConnection {
     drop_notifier: OneshotTrigger<NodeId>,
     number_of_rpc_clients: Arc<AtomicUsize>,
     force_disconnect_rpc_clients_when_clone_drops: Arc<AtomicBool>,
+    supported_protocols: Arc<Vec<ProtocolId>>,
+    disconnect_reason: Arc<OnceLock<DisconnectReason>>,
 }
 
 impl PeerConnection {
@@ -152,6 +183,7 @@ impl PeerConnection {
         address: Multiaddr,
         direction: ConnectionDirection,
         substream_counter: AtomicRefCounter,
+        supported_protocols: Vec<ProtocolId>,
     ) -> Self {
         Self {
             id,
@@ -166,6 +198,8 @@ impl PeerConnection {
             drop_notifier: OneshotTrigger::<NodeId>::new(),
             number_of_rpc_clients: Arc::new(AtomicUsize::new(0)),
             force_disconnect_rpc_clients_when_clone_drops: Arc::new(Default::default()),
+            supported_protocols: Arc::new(supported_protocols),
+            disconnect_reason: Arc::new(OnceLock::new()),
         }
     }
 
@@ -219,6 +253,23 @@ impl PeerConnection {
         Arc::strong_count(&self.handle_counter)
     }
 
+    /// The protocols, including RPC services, that the peer advertised during the identity exchange. This is empty if
+    /// the peer did not advertise any protocols.
+    pub fn supported_rpc_protocols(&self) -> &[ProtocolId] {
+        &self.supported_protocols
+    }
+
+    /// Returns true if the peer advertised support for the given protocol
+    pub fn supports_protocol(&self, protocol_id: &ProtocolId) -> bool {
+        self.supported_protocols.contains(protocol_id)
+    }
+
+    /// Returns true if the peer advertised support for the RPC service `T`
+    #[cfg(feature = "rpc")]
+    pub fn supports_rpc<T: NamedProtocolService>(&self) -> bool {
+        self.supports_protocol(&ProtocolId::from_static(T::PROTOCOL_NAME))
+    }
+
     pub async fn open_substream(
         &mut self,
         protocol_id: &ProtocolId,
@@ -290,19 +341,44 @@ impl PeerConnection {
     /// Immediately disconnects the peer connection. This can only fail if the peer connection worker
     /// is shut down (and the peer is already disconnected)
     pub async fn disconnect(&mut self, minimized: Minimized) -> Result<(), PeerConnectionError> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-        self.request_tx
-            .send(PeerConnectionRequest::Disconnect(false, reply_tx, minimized))
-            .await?;
-        reply_rx
-            .await
-            .map_err(|_| PeerConnectionError::InternalReplyCancelled)?
+        self.disconnect_with_reason(minimized, DisconnectReason::Requested).await
+    }
+
+    /// Immediately disconnects the peer connection, recording the reason. The first recorded reason is available from
+    /// all clones of this handle via `disconnect_reason` and is published in the `PeerDisconnected` event.
+    pub async fn disconnect_with_reason(
+        &mut self,
+        minimized: Minimized,
+        reason: DisconnectReason,
+    ) -> Result<(), PeerConnectionError> {
+        let _result = self.disconnect_reason.set(reason);
+        debug!(
+            target: LOG_TARGET,
+            "Disconnecting peer '{}' ({})",
+            self.peer_node_id.short_str(),
+            reason
+        );
+        self.send_disconnect(false, minimized, self.disconnect_reason()).await
+    }
+
+    /// The reason this connection was disconnected locally, if it was disconnected using `disconnect_with_reason`
+    pub fn disconnect_reason(&self) -> Option<DisconnectReason> {
+        self.disconnect_reason.get().copied()
     }
 
     pub(crate) async fn disconnect_silent(&mut self, minimized: Minimized) -> Result<(), PeerConnectionError> {
+        self.send_disconnect(true, minimized, None).await
+    }
+
+    async fn send_disconnect(
+        &mut self,
+        silent: bool,
+        minimized: Minimized,
+        reason: Option<DisconnectReason>,
+    ) -> Result<(), PeerConnectionError> {
         let (reply_tx, reply_rx) = oneshot::channel();
         self.request_tx
-            .send(PeerConnectionRequest::Disconnect(true, reply_tx, minimized))
+            .send(PeerConnectionRequest::Disconnect(silent, reply_tx, minimized, reason))
             .await?;
         reply_rx
             .await
@@ -434,7 +510,8 @@ impl PeerConnectionActor {
             }
         }
 
-        if let Err(err) = self.disconnect(false, Minimized::No).await {
+        // The connection closed without a local disconnect request
+        if let Err(err) = self.disconnect(false, Minimized::No, None).await {
             warn!(
                 target: LOG_TARGET,
                 "[{}] Failed to politely close connection to peer '{}' because '{}'",
@@ -459,7 +536,7 @@ impl PeerConnectionActor {
                     "Reply oneshot closed when sending reply",
                 );
             },
-            Disconnect(silent, reply_tx, minimized) => {
+            Disconnect(silent, reply_tx, minimized, reason) => {
                 debug!(
                     target: LOG_TARGET,
                     "[{}] Disconnect{}requested for {} connection to peer '{}'",
@@ -468,7 +545,7 @@ impl PeerConnectionActor {
                     self.direction,
                     self.peer_node_id.short_str()
                 );
-                let _result = reply_tx.send(self.disconnect(silent, minimized).await);
+                let _result = reply_tx.send(self.disconnect(silent, minimized, reason).await);
             },
         }
     }
@@ -564,7 +641,13 @@ impl PeerConnectionActor {
     /// # Arguments
     ///
     /// silent - true to suppress the PeerDisconnected event, false to publish the event
-    async fn disconnect(&mut self, silent: bool, minimized: Minimized) -> Result<(), PeerConnectionError> {
+    /// reason - the reason published in the PeerDisconnected event, `None` if the peer closed the connection
+    async fn disconnect(
+        &mut self,
+        silent: bool,
+        minimized: Minimized,
+        reason: Option<DisconnectReason>,
+    ) -> Result<(), PeerConnectionError> {
         self.request_rx.close();
 
         // Only emit closed event once
@@ -587,6 +670,7 @@ impl PeerConnectionActor {
                 self.id,
                 self.peer_node_id.clone(),
                 minimized,
+                reason,
             ))
             .await;
         }
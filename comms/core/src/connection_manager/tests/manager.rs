@@ -24,7 +24,7 @@ use std::time::Duration;
 
 use futures::future;
 use tari_shutdown::Shutdown;
-use tari_test_utils::{collect_try_recv, unpack_enum};
+use tari_test_utils::{collect_try_recv, streams, unpack_enum};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     runtime::Handle,
@@ -38,6 +38,7 @@ use crate::{
         ConnectionManagerError,
         ConnectionManagerEvent,
         ConnectionManagerRequester,
+        DisconnectReason,
     },
     net_address::{MultiaddressesWithStats, PeerAddressSource},
     peer_manager::{NodeId, Peer, PeerFeatures, PeerFlags, PeerManagerError},
@@ -49,6 +50,7 @@ use crate::{
         test_node::{build_connection_manager, TestNodeConfig},
     },
     transports::{MemoryTransport, TcpTransport},
+    Minimized,
     PeerConnectionError,
 };
 
@@ -439,3 +441,88 @@ async fn dial_cancelled() {
     assert_eq!(node_id, node_identity2.node_id());
     unpack_enum!(ConnectionManagerError::DialCancelled = err);
 }
+
+#[tokio::test]
+async fn disconnect_reason_is_published() {
+    let shutdown = Shutdown::new();
+
+    let node_identity1 = build_node_identity(PeerFeatures::empty());
+    let node_identity2 = build_node_identity(PeerFeatures::empty());
+
+    let peer_manager1 = build_peer_manager();
+    let mut conn_man1 = build_connection_manager(
+        TestNodeConfig {
+            node_identity: node_identity1.clone(),
+            ..Default::default()
+        },
+        MemoryTransport,
+        peer_manager1.clone(),
+        Default::default(),
+        shutdown.to_signal(),
+    );
+    conn_man1.wait_until_listening().await.unwrap();
+    let mut subscription1 = conn_man1.get_event_subscription();
+
+    let mut conn_man2 = build_connection_manager(
+        TestNodeConfig {
+            node_identity: node_identity2.clone(),
+            ..Default::default()
+        },
+        MemoryTransport,
+        build_peer_manager(),
+        Default::default(),
+        shutdown.to_signal(),
+    );
+    let mut subscription2 = conn_man2.get_event_subscription();
+    let listener_info = conn_man2.wait_until_listening().await.unwrap();
+
+    peer_manager1
+        .add_peer(Peer::new(
+            node_identity2.public_key().clone(),
+            node_identity2.node_id().clone(),
+            MultiaddressesWithStats::from_addresses_with_source(
+                vec![listener_info.bind_address().clone()],
+                &PeerAddressSource::Config,
+            ),
+            PeerFlags::empty(),
+            PeerFeatures::COMMUNICATION_CLIENT,
+            Default::default(),
+            Default::default(),
+        ))
+        .await
+        .unwrap();
+
+    let mut conn_out = conn_man1.dial_peer(node_identity2.node_id().clone()).await.unwrap();
+    conn_out
+        .disconnect_with_reason(Minimized::No, DisconnectReason::Unresponsive)
+        .await
+        .unwrap();
+    assert_eq!(conn_out.disconnect_reason(), Some(DisconnectReason::Unresponsive));
+
+    let reason = streams::assert_in_broadcast(
+        &mut subscription1,
+        |event| match &*event {
+            ConnectionManagerEvent::PeerDisconnected(_, node_id, _, reason) if node_id == node_identity2.node_id() => {
+                Some(*reason)
+            },
+            _ => None,
+        },
+        Duration::from_secs(5),
+    )
+    .await;
+    assert_eq!(reason, Some(DisconnectReason::Unresponsive));
+
+    // The remote peer did not request the disconnect, so no reason is recorded on its side
+    let reason = streams::assert_in_broadcast(
+        &mut subscription2,
+        |event| match &*event {
+            ConnectionManagerEvent::PeerDisconnected(_, node_id, _, reason) if node_id == node_identity1.node_id() => {
+                Some(*reason)
+            },
+            _ => None,
+        },
+        Duration::from_secs(5),
+    )
+    .await;
+    assert_eq!(reason, None);
+}
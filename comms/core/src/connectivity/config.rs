@@ -45,6 +45,9 @@ pub struct ConnectivityConfig {
     /// The length of time to wait before disconnecting a connection that failed tie breaking.
     /// Default: 1s
     pub connection_tie_break_linger: Duration,
+    /// The strategy used to select which connection survives when two connections to the same peer exist.
+    /// Default: PreferLowerNodeId
+    pub tie_break_strategy: ConnectionTieBreakStrategy,
     /// If the peer has not been seen within this interval, it will be removed from the peer list on the
     /// next connection attempt.
     /// Default: 24 hours
@@ -52,6 +55,19 @@ pub struct ConnectivityConfig {
     /// The closest number of peer connections to maintain; connections above the threshold will be removed
     /// (default: disabled)
     pub maintain_n_closest_connections_only: Option<usize>,
+    /// How equidistant connections are ordered when pruning to `maintain_n_closest_connections_only`.
+    /// Default: Closest
+    pub pruning_preference: ConnectionPruningPreference,
+    /// If set, TCP keepalive probes are sent on established connections after they have been idle for this interval,
+    /// so that half-open connections are detected without waiting for a read or write to fail. This only applies to
+    /// direct TCP connections i.e. the TCP transport. Connections made through Tor or a SOCKS5 proxy are not probed,
+    /// as the probes would only reach the local proxy.
+    /// Default: None (disabled)
+    pub keepalive_probe_interval: Option<Duration>,
+    /// The maximum number of dials that are in flight at once. Further dials are queued until a dial completes, so
+    /// that dialing a large peer list (e.g. the seed peers during bootstrap) does not exhaust file descriptors.
+    /// Default: 10
+    pub max_concurrent_dials: usize,
 }
 
 impl Default for ConnectivityConfig {
@@ -64,8 +80,50 @@ impl Default for ConnectivityConfig {
             is_connection_reaping_enabled: true,
             max_failures_mark_offline: 1,
             connection_tie_break_linger: Duration::from_secs(2),
+            tie_break_strategy: ConnectionTieBreakStrategy::default(),
             expire_peer_last_seen_duration: Duration::from_secs(24 * 60 * 60),
             maintain_n_closest_connections_only: None,
+            pruning_preference: ConnectionPruningPreference::default(),
+            keepalive_probe_interval: None,
+            max_concurrent_dials: 10,
         }
     }
 }
+
+/// Determines which connection is kept when a new connection is established to a peer that is already connected.
+///
+/// Only `PreferLowerNodeId` guarantees that both sides of the connection select the same survivor. The other
+/// strategies should only be used when the remote peer is known to apply the opposite (or no) preference, otherwise
+/// both connections may be closed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionTieBreakStrategy {
+    /// Keep the inbound connection. If both connections have the same direction, the newer connection is kept.
+    PreferInbound,
+    /// Keep the outbound connection. If both connections have the same direction, the newer connection is kept.
+    PreferOutbound,
+    /// Always keep the existing connection and discard the new one.
+    PreferOlder,
+    /// Keep the connection initiated by the node with the lower node ID. If both connections have the same
+    /// direction, the newer connection is kept.
+    #[default]
+    PreferLowerNodeId,
+}
+
+/// Determines which connections survive when pruning to the `maintain_n_closest_connections_only` closest peers.
+///
+/// The XOR distances of distinct node IDs never tie exactly, so for the purposes of this preference connections are
+/// equidistant when their distances fall in the same bucket. Remaining ties are broken by the exact distance, so
+/// pruning is deterministic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionPruningPreference {
+    /// Keep the connections with the smallest exact distance, without grouping them into buckets.
+    #[default]
+    Closest,
+    /// Among equidistant connections, keep the ones that have been established the longest.
+    PreferOlder,
+    /// Among equidistant connections, keep the most recently established ones.
+    PreferNewer,
+    /// Among equidistant connections, keep the ones with the lowest average latency to the connected address.
+    /// Connections without a latency measurement are pruned first.
+    PreferLowerLatency,
+}
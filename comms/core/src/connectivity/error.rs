@@ -41,6 +41,8 @@ pub enum ConnectivityError {
     ConnectivityEventStreamClosed,
     #[error("Timeout while waiting for node to come online ({0} peer(s) connected)")]
     OnlineWaitTimeout(usize),
+    #[error("Timeout while waiting for {min} outbound node connection(s) ({connected} connected)")]
+    MinConnectivityWaitTimeout { min: usize, connected: usize },
     #[error("Pending dial was cancelled")]
     DialCancelled,
     #[error("Client cancelled: '{0}'")]
@@ -20,6 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 use std::{
+    cmp::max,
     collections::HashMap,
     fmt,
     sync::Arc,
@@ -30,7 +31,8 @@ use log::*;
 use nom::lib::std::collections::hash_map::Entry;
 use tari_shutdown::ShutdownSignal;
 use tokio::{
-    sync::{mpsc, oneshot},
+    sync::{mpsc, oneshot, Semaphore},
+    task,
     task::JoinHandle,
     time,
     time::MissedTickBehavior,
@@ -38,12 +40,12 @@ use tokio::{
 use tracing::{span, Instrument, Level};
 
 use super::{
-    config::ConnectivityConfig,
+    config::{ConnectionPruningPreference, ConnectionTieBreakStrategy, ConnectivityConfig},
     connection_pool::{ConnectionPool, ConnectionStatus},
     connection_stats::PeerConnectionStats,
     error::ConnectivityError,
     requester::{ConnectivityEvent, ConnectivityRequest},
-    selection::ConnectivitySelection,
+    selection::{sort_for_pruning, ConnectivitySelection},
     ConnectivityEventTx,
 };
 use crate::{
@@ -52,6 +54,7 @@ use crate::{
         ConnectionManagerError,
         ConnectionManagerEvent,
         ConnectionManagerRequester,
+        DisconnectReason,
     },
     peer_manager::NodeId,
     utils::datetime::format_duration,
@@ -83,6 +86,7 @@ pub struct ConnectivityManager {
 impl ConnectivityManager {
     pub fn spawn(self) -> JoinHandle<()> {
         ConnectivityManagerActor {
+            dial_permits: Arc::new(Semaphore::new(max(self.config.max_concurrent_dials, 1))),
             config: self.config,
             status: ConnectivityStatus::Initializing,
             request_rx: self.request_rx,
@@ -153,6 +157,7 @@ struct ConnectivityManagerActor {
     #[cfg(feature = "metrics")]
     uptime: Option<Instant>,
     allow_list: Vec<NodeId>,
+    dial_permits: Arc<Semaphore>,
 }
 
 impl ConnectivityManagerActor {
@@ -346,16 +351,40 @@ impl ConnectivityManagerActor {
                     },
                 }
 
-                if let Err(err) = self.connection_manager.send_dial_peer(node_id, reply_tx).await {
-                    error!(
-                        target: LOG_TARGET,
-                        "Failed to send dial request to connection manager: {:?}", err
-                    );
-                }
+                self.spawn_dial(node_id, reply_tx);
             },
         }
     }
 
+    /// Dials the peer once fewer than `max_concurrent_dials` dials are in flight. The permit is held until the
+    /// connection manager replies, so excess dials are queued rather than attempted at once.
+    fn spawn_dial(
+        &self,
+        node_id: NodeId,
+        reply_tx: Option<oneshot::Sender<Result<PeerConnection, ConnectionManagerError>>>,
+    ) {
+        let dial_permits = self.dial_permits.clone();
+        let mut connection_manager = self.connection_manager.clone();
+        task::spawn(async move {
+            let Ok(_permit) = dial_permits.acquire_owned().await else {
+                return;
+            };
+            let (dial_reply_tx, dial_reply_rx) = oneshot::channel();
+            if let Err(err) = connection_manager.send_dial_peer(node_id, Some(dial_reply_tx)).await {
+                error!(
+                    target: LOG_TARGET,
+                    "Failed to send dial request to connection manager: {:?}", err
+                );
+                return;
+            }
+            if let Ok(result) = dial_reply_rx.await {
+                if let Some(reply_tx) = reply_tx {
+                    let _result = reply_tx.send(result);
+                }
+            }
+        });
+    }
+
     async fn disconnect_all(&mut self) {
         let mut node_ids = Vec::with_capacity(self.pool.count_connected());
         for mut state in self.pool.filter_drain(|_| true) {
@@ -380,7 +409,11 @@ impl ConnectivityManagerActor {
         }
 
         for node_id in node_ids {
-            self.publish_event(ConnectivityEvent::PeerDisconnected(node_id, Minimized::No));
+            self.publish_event(ConnectivityEvent::PeerDisconnected(
+                node_id,
+                Minimized::No,
+                Some(DisconnectReason::Shutdown),
+            ));
         }
     }
 
@@ -440,6 +473,17 @@ impl ConnectivityManagerActor {
             num_connections,
         );
 
+        let latencies = match self.config.pruning_preference {
+            ConnectionPruningPreference::PreferLowerLatency => self.get_connection_latencies(&connections).await,
+            _ => HashMap::new(),
+        };
+        sort_for_pruning(
+            &mut connections,
+            self.node_identity.node_id(),
+            self.config.pruning_preference,
+            &latencies,
+        );
+
         // Disconnect all remaining peers above the threshold
         for conn in connections.iter_mut().skip(threshold) {
             debug!(
@@ -461,6 +505,35 @@ impl ConnectivityManagerActor {
         }
     }
 
+    /// Returns the average latency of the connected address of each connection that has a latency measurement
+    async fn get_connection_latencies(&self, connections: &[PeerConnection]) -> HashMap<NodeId, Duration> {
+        let mut latencies = HashMap::with_capacity(connections.len());
+        for conn in connections {
+            let peer = match self.peer_manager.find_by_node_id(conn.peer_node_id()).await {
+                Ok(Some(peer)) => peer,
+                Ok(None) => continue,
+                Err(e) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "minimize_connections: Failed to get peer '{}' ({:?})",
+                        conn.peer_node_id().short_str(),
+                        e
+                    );
+                    continue;
+                },
+            };
+            let latency = peer
+                .addresses
+                .iter()
+                .find(|addr| addr.address() == conn.address())
+                .and_then(|addr| addr.avg_latency());
+            if let Some(latency) = latency {
+                latencies.insert(conn.peer_node_id().clone(), latency);
+            }
+        }
+        latencies
+    }
+
     async fn reap_inactive_connections(&mut self) {
         let excess_connections = self
             .pool
@@ -474,6 +547,7 @@ impl ConnectivityManagerActor {
             .pool
             .get_inactive_outbound_connections_mut(self.config.reaper_min_inactive_age);
         connections.truncate(excess_connections);
+        let mut reaped = Vec::with_capacity(connections.len());
         for conn in connections {
             if !conn.is_connected() {
                 continue;
@@ -485,16 +559,25 @@ impl ConnectivityManagerActor {
                 conn.peer_node_id().short_str(),
                 conn.handle_count()
             );
-            if let Err(err) = conn.disconnect(Minimized::Yes).await {
-                // Already disconnected
-                debug!(
-                    target: LOG_TARGET,
-                    "Peer '{}' already disconnected. Error: {:?}",
-                    conn.peer_node_id().short_str(),
-                    err
-                );
+            match conn.disconnect(Minimized::Yes).await {
+                Ok(()) => reaped.push(ConnectivityEvent::ConnectionReaped {
+                    node_id: conn.peer_node_id().clone(),
+                    connection_age: conn.age(),
+                }),
+                Err(err) => {
+                    // Already disconnected
+                    debug!(
+                        target: LOG_TARGET,
+                        "Peer '{}' already disconnected. Error: {:?}",
+                        conn.peer_node_id().short_str(),
+                        err
+                    );
+                },
             }
         }
+        for event in reaped {
+            self.publish_event(event);
+        }
     }
 
     fn clean_connection_pool(&mut self) {
@@ -631,7 +714,7 @@ impl ConnectivityManagerActor {
                     TieBreak::UseNew | TieBreak::None => {},
                 }
             },
-            PeerDisconnected(id, node_id, _minimized) => {
+            PeerDisconnected(id, node_id, _minimized, _reason) => {
                 if let Some(conn) = self.pool.get_connection(node_id) {
                     if conn.id() != *id {
                         debug!(
@@ -657,7 +740,7 @@ impl ConnectivityManagerActor {
         }
 
         let (node_id, mut new_status, connection) = match event {
-            PeerDisconnected(_, node_id, minimized) => (node_id, ConnectionStatus::Disconnected(*minimized), None),
+            PeerDisconnected(_, node_id, minimized, _) => (node_id, ConnectionStatus::Disconnected(*minimized), None),
             PeerConnected(conn) => (conn.peer_node_id(), ConnectionStatus::Connected, Some(conn.clone())),
             PeerConnectFailed(node_id, ConnectionManagerError::AllPeerAddressesAreExcluded(msg)) => {
                 debug!(
@@ -735,10 +818,15 @@ impl ConnectivityManagerActor {
                 ),
             },
             (Connected, Disconnected(..)) => {
-                self.publish_event(ConnectivityEvent::PeerDisconnected(node_id, match new_status {
-                    ConnectionStatus::Disconnected(reason) => reason,
+                let minimized = match new_status {
+                    ConnectionStatus::Disconnected(minimized) => minimized,
                     _ => Minimized::No,
-                }));
+                };
+                let reason = match event {
+                    PeerDisconnected(_, _, _, reason) => *reason,
+                    _ => None,
+                };
+                self.publish_event(ConnectivityEvent::PeerDisconnected(node_id, minimized, reason));
             },
             // Was not connected so don't broadcast event
             (_, Disconnected(..)) => {},
@@ -825,9 +913,9 @@ impl ConnectivityManagerActor {
         }
     }
 
-    /// Two connections to the same peer have been created. This function deterministically determines which peer
-    /// connection to close. It does this by comparing our NodeId to that of the peer. This rule enables both sides to
-    /// agree which connection to disconnect
+    /// Two connections to the same peer have been created. This function determines which peer connection to close
+    /// according to the configured `ConnectionTieBreakStrategy`. The default strategy compares our NodeId to that of
+    /// the peer, which enables both sides to agree which connection to disconnect.
     ///
     /// Returns true if the existing connection should close, otherwise false if the new connection should be closed.
     fn tie_break_existing_connection(&self, existing_conn: &PeerConnection, new_conn: &PeerConnection) -> bool {
@@ -837,20 +925,36 @@ impl ConnectivityManagerActor {
 
         debug!(
             target: LOG_TARGET,
-            "Tie-break: (Existing = {}, New = {})",
+            "Tie-break: (Existing = {}, New = {}, Strategy = {:?})",
             existing_conn.direction(),
-            new_conn.direction()
+            new_conn.direction(),
+            self.config.tie_break_strategy
         );
         use ConnectionDirection::{Inbound, Outbound};
-        match (existing_conn.direction(), new_conn.direction()) {
-            // They connected to us twice for some reason. Drop the older connection
-            (Inbound, Inbound) => true,
-            // They connected to us at the same time we connected to them
-            (Inbound, Outbound) => peer_node_id > our_node_id,
-            // We connected to them at the same time as they connected to us
-            (Outbound, Inbound) => our_node_id > peer_node_id,
-            // We connected to them twice for some reason. Drop the older connection.
-            (Outbound, Outbound) => true,
+        match self.config.tie_break_strategy {
+            ConnectionTieBreakStrategy::PreferOlder => false,
+            ConnectionTieBreakStrategy::PreferInbound => match (existing_conn.direction(), new_conn.direction()) {
+                (Outbound, Inbound) => true,
+                (Inbound, Outbound) => false,
+                // Same direction, drop the older connection
+                (Inbound, Inbound) | (Outbound, Outbound) => true,
+            },
+            ConnectionTieBreakStrategy::PreferOutbound => match (existing_conn.direction(), new_conn.direction()) {
+                (Inbound, Outbound) => true,
+                (Outbound, Inbound) => false,
+                // Same direction, drop the older connection
+                (Inbound, Inbound) | (Outbound, Outbound) => true,
+            },
+            ConnectionTieBreakStrategy::PreferLowerNodeId => match (existing_conn.direction(), new_conn.direction()) {
+                // They connected to us twice for some reason. Drop the older connection
+                (Inbound, Inbound) => true,
+                // They connected to us at the same time we connected to them
+                (Inbound, Outbound) => peer_node_id > our_node_id,
+                // We connected to them at the same time as they connected to us
+                (Outbound, Inbound) => our_node_id > peer_node_id,
+                // We connected to them twice for some reason. Drop the older connection.
+                (Outbound, Outbound) => true,
+            },
         }
     }
 
@@ -30,7 +30,7 @@
 mod connection_stats;
 
 mod config;
-pub use config::ConnectivityConfig;
+pub use config::{ConnectionPruningPreference, ConnectionTieBreakStrategy, ConnectivityConfig};
 
 mod connection_pool;
 
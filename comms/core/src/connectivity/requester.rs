@@ -39,7 +39,7 @@ use super::{
     ConnectivitySelection,
 };
 use crate::{
-    connection_manager::ConnectionManagerError,
+    connection_manager::{ConnectionDirection, ConnectionManagerError, DisconnectReason},
     peer_manager::{NodeId, Peer},
     Minimized,
     NodeIdentity,
@@ -56,10 +56,17 @@ pub type ConnectivityEventTx = broadcast::Sender<ConnectivityEvent>;
 /// Node connectivity events emitted by the ConnectivityManager.
 #[derive(Debug, Clone)]
 pub enum ConnectivityEvent {
-    PeerDisconnected(NodeId, Minimized),
+    /// A connected peer was disconnected. The reason is `None` if the peer closed the connection.
+    PeerDisconnected(NodeId, Minimized, Option<DisconnectReason>),
     PeerConnected(Box<PeerConnection>),
     PeerConnectFailed(NodeId),
     PeerBanned(NodeId),
+    /// The connection reaper disconnected an inactive connection. `connection_age` is the time since the connection
+    /// was established, which is at least `reaper_min_inactive_age`.
+    ConnectionReaped {
+        node_id: NodeId,
+        connection_age: Duration,
+    },
     ConnectivityStateInitialized,
     ConnectivityStateOnline(usize),
     ConnectivityStateDegraded(usize),
@@ -71,10 +78,15 @@ impl fmt::Display for ConnectivityEvent {
         #[allow(clippy::enum_glob_use)]
         use ConnectivityEvent::*;
         match self {
-            PeerDisconnected(node_id, minimized) => write!(f, "PeerDisconnected({}, {:?})", node_id, minimized),
+            PeerDisconnected(node_id, minimized, reason) => {
+                write!(f, "PeerDisconnected({}, {:?}, {:?})", node_id, minimized, reason)
+            },
             PeerConnected(node_id) => write!(f, "PeerConnected({})", node_id),
             PeerConnectFailed(node_id) => write!(f, "PeerConnectFailed({})", node_id),
             PeerBanned(node_id) => write!(f, "PeerBanned({})", node_id),
+            ConnectionReaped { node_id, connection_age } => {
+                write!(f, "ConnectionReaped({}, {:.2?})", node_id, connection_age)
+            },
             ConnectivityStateInitialized => write!(f, "ConnectivityStateInitialized"),
             ConnectivityStateOnline(n) => write!(f, "ConnectivityStateOnline({})", n),
             ConnectivityStateDegraded(n) => write!(f, "ConnectivityStateDegraded({})", n),
@@ -326,6 +338,62 @@ impl ConnectivityRequester {
         reply_rx.await.map_err(|_| ConnectivityError::ActorResponseCancelled)
     }
 
+    /// Waits until there are at least `min` outbound connections to nodes (i.e. not to clients) and returns the number
+    /// of such connections, e.g. to wait for the network to warm up before starting a sync.
+    pub async fn wait_for_min_connectivity(
+        &mut self,
+        min: usize,
+        timeout: Duration,
+    ) -> Result<usize, ConnectivityError> {
+        let mut connectivity_events = self.get_event_subscription();
+        let start = Instant::now();
+        loop {
+            let num_connected = self.num_outbound_node_connections().await?;
+            if num_connected >= min {
+                return Ok(num_connected);
+            }
+            debug!(
+                target: LOG_TARGET,
+                "Waiting for {} outbound node connection(s), {} connected", min, num_connected
+            );
+
+            // Wait until a peer connects before counting the connections again
+            loop {
+                let remaining =
+                    timeout
+                        .checked_sub(start.elapsed())
+                        .ok_or(ConnectivityError::MinConnectivityWaitTimeout {
+                            min,
+                            connected: num_connected,
+                        })?;
+                let recv_result = time::timeout(remaining, connectivity_events.recv())
+                    .await
+                    .map_err(|_| ConnectivityError::MinConnectivityWaitTimeout {
+                        min,
+                        connected: num_connected,
+                    })?;
+                match recv_result {
+                    Ok(ConnectivityEvent::PeerConnected(_)) => break,
+                    Ok(_) => {},
+                    Err(RecvError::Closed) => return Err(ConnectivityError::ConnectivityEventStreamClosed),
+                    Err(RecvError::Lagged(n)) => {
+                        warn!(target: LOG_TARGET, "Lagging behind on {} connectivity event(s)", n);
+                        // We lagged, so could have missed a connection. Check the connections explicitly.
+                        break;
+                    },
+                }
+            }
+        }
+    }
+
+    async fn num_outbound_node_connections(&mut self) -> Result<usize, ConnectivityError> {
+        let connections = self.get_active_connections().await?;
+        Ok(connections
+            .iter()
+            .filter(|conn| conn.peer_features().is_node() && conn.direction() == ConnectionDirection::Outbound)
+            .count())
+    }
+
     /// Waits for the node to get at least one connection.
     /// This is useful for testing and is not typically be needed in application code.
     pub async fn wait_for_connectivity(&mut self, timeout: Duration) -> Result<(), ConnectivityError> {
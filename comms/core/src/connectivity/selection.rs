@@ -20,11 +20,11 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{fmt, fmt::Display};
+use std::{collections::HashMap, fmt, fmt::Display, time::Duration};
 
 use rand::{rngs::OsRng, seq::SliceRandom};
 
-use super::connection_pool::ConnectionPool;
+use super::{config::ConnectionPruningPreference, connection_pool::ConnectionPool};
 use crate::{connectivity::connection_pool::ConnectionStatus, peer_manager::NodeId, PeerConnection};
 
 /// Selection query for PeerConnections.
@@ -119,6 +119,31 @@ fn select_closest<'a>(pool: &'a ConnectionPool, node_id: &NodeId, exclude: &[Nod
     nodes
 }
 
+/// Orders the connections so that the ones to keep when pruning to the closest connections to `node_id` come first,
+/// according to the pruning `preference`. `latencies` holds the average latency of the peers that have one.
+pub(super) fn sort_for_pruning(
+    connections: &mut [PeerConnection],
+    node_id: &NodeId,
+    preference: ConnectionPruningPreference,
+    latencies: &HashMap<NodeId, Duration>,
+) {
+    connections.sort_by_cached_key(|conn| {
+        let distance = conn.peer_node_id().distance(node_id);
+        let (bucket, tie_break) = match preference {
+            ConnectionPruningPreference::Closest => (0, Duration::ZERO),
+            ConnectionPruningPreference::PreferOlder => {
+                (distance.get_bucket_index(), Duration::MAX.saturating_sub(conn.age()))
+            },
+            ConnectionPruningPreference::PreferNewer => (distance.get_bucket_index(), conn.age()),
+            ConnectionPruningPreference::PreferLowerLatency => (
+                distance.get_bucket_index(),
+                latencies.get(conn.peer_node_id()).copied().unwrap_or(Duration::MAX),
+            ),
+        };
+        (bucket, tie_break, distance)
+    });
+}
+
 fn select_random_nodes<'a>(pool: &'a ConnectionPool, n: usize, exclude: &[NodeId]) -> Vec<&'a PeerConnection> {
     let nodes = select_connected_nodes(pool, exclude);
     nodes.choose_multiple(&mut OsRng, n).copied().collect()
@@ -206,4 +231,67 @@ mod test {
         let conns = select_closest(&pool, node_identity.node_id(), &[]);
         assert!(conns.is_empty());
     }
+
+    #[test]
+    fn it_breaks_pruning_ties_between_equidistant_connections_by_preference() {
+        let node_id_with_first_byte = |byte| {
+            let mut bytes = [0u8; NodeId::byte_size()];
+            bytes[0] = byte;
+            NodeId::try_from(bytes.as_slice()).unwrap()
+        };
+        let subject = node_id_with_first_byte(0);
+        // `far_older` and `far_newer` are in the same distance bucket, `near` is in a closer one
+        let far_older = node_id_with_first_byte(0xc0);
+        let near = node_id_with_first_byte(0x01);
+        let far_newer = node_id_with_first_byte(0x80);
+        let mut receivers = Vec::new();
+        let mut connections = Vec::new();
+        for node_id in [&far_older, &near, &far_newer] {
+            let (conn, rx) = create_dummy_peer_connection(node_id.clone());
+            connections.push(conn);
+            receivers.push(rx);
+            std::thread::sleep(Duration::from_millis(2));
+        }
+        let latencies = HashMap::from([
+            (far_older.clone(), Duration::from_millis(100)),
+            (far_newer.clone(), Duration::from_millis(10)),
+        ]);
+
+        let survivors = |preference| {
+            let mut connections = connections.clone();
+            sort_for_pruning(&mut connections, &subject, preference, &latencies);
+            connections
+                .iter()
+                .take(2)
+                .map(|c| c.peer_node_id().clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(survivors(ConnectionPruningPreference::Closest), vec![
+            near.clone(),
+            far_newer.clone()
+        ]);
+        assert_eq!(survivors(ConnectionPruningPreference::PreferOlder), vec![
+            near.clone(),
+            far_older.clone()
+        ]);
+        assert_eq!(survivors(ConnectionPruningPreference::PreferNewer), vec![
+            near.clone(),
+            far_newer.clone()
+        ]);
+        assert_eq!(survivors(ConnectionPruningPreference::PreferLowerLatency), vec![
+            near.clone(),
+            far_newer.clone()
+        ]);
+
+        // Without latency measurements the exact distance decides
+        let mut connections = connections.clone();
+        sort_for_pruning(
+            &mut connections,
+            &subject,
+            ConnectionPruningPreference::PreferLowerLatency,
+            &HashMap::new(),
+        );
+        assert_eq!(connections[1].peer_node_id(), &far_newer);
+    }
 }
@@ -20,22 +20,23 @@
 // CAUSED AND ON ANY THEORY OF LIABILITY,  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR
 // OTHERWISE) ARISING IN ANY WAY OUT OF THE  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH
 // DAMAGE.
-use std::{sync::Arc, time::Duration};
+use std::{cmp::min, sync::Arc, time::Duration};
 
 use futures::{future, StreamExt};
 use tari_shutdown::Shutdown;
-use tari_test_utils::{collect_try_recv, streams, unpack_enum};
+use tari_test_utils::{async_assert_eventually, collect_try_recv, streams, unpack_enum};
 use tokio::sync::{broadcast, mpsc};
 
 use super::{
-    config::ConnectivityConfig,
+    config::{ConnectionTieBreakStrategy, ConnectivityConfig},
     connection_pool::ConnectionStatus,
+    error::ConnectivityError,
     manager::ConnectivityManager,
     requester::{ConnectivityEvent, ConnectivityRequester},
     selection::ConnectivitySelection,
 };
 use crate::{
-    connection_manager::{ConnectionManagerError, ConnectionManagerEvent},
+    connection_manager::{ConnectionDirection, ConnectionManagerError, ConnectionManagerEvent, DisconnectReason},
     connectivity::ConnectivityEventRx,
     peer_manager::{Peer, PeerFeatures},
     test_utils::{
@@ -205,6 +206,7 @@ async fn online_then_offline_then_online() {
             conn.id(),
             conn.peer_node_id().clone(),
             Minimized::No,
+            None,
         ));
     }
 
@@ -227,6 +229,7 @@ async fn online_then_offline_then_online() {
             conn.id(),
             conn.peer_node_id().clone(),
             Minimized::No,
+            None,
         ));
     }
 
@@ -428,6 +431,7 @@ async fn pool_management() {
                 conn.id(),
                 conn.peer_node_id().clone(),
                 Minimized::No,
+                Some(DisconnectReason::Requested),
             ));
         }
     }
@@ -436,7 +440,8 @@ async fn pool_management() {
 
     let events = collect_try_recv!(event_stream, take = 9, timeout = Duration::from_secs(10));
     for event in events {
-        unpack_enum!(ConnectivityEvent::PeerDisconnected(..) = event);
+        unpack_enum!(ConnectivityEvent::PeerDisconnected(_node_id, _minimized, reason) = event);
+        assert_eq!(reason, Some(DisconnectReason::Requested));
     }
 
     assert_eq!(important_connection.handle_count(), 2);
@@ -449,11 +454,185 @@ async fn pool_management() {
         important_connection.id(),
         important_connection.peer_node_id().clone(),
         Minimized::No,
+        Some(DisconnectReason::Unresponsive),
     ));
     drop(important_connection);
 
     let mut events = collect_try_recv!(event_stream, take = 1, timeout = Duration::from_secs(10));
-    unpack_enum!(ConnectivityEvent::PeerDisconnected(..) = events.remove(0));
+    unpack_enum!(ConnectivityEvent::PeerDisconnected(_node_id, _minimized, reason) = events.remove(0));
+    assert_eq!(reason, Some(DisconnectReason::Unresponsive));
     let conns = connectivity.get_active_connections().await.unwrap();
     assert!(conns.is_empty());
 }
+
+#[tokio::test]
+async fn it_publishes_an_event_when_a_connection_is_reaped() {
+    let config = ConnectivityConfig {
+        connection_pool_refresh_interval: Duration::from_millis(100),
+        reaper_min_inactive_age: Duration::ZERO,
+        reaper_min_connection_threshold: 0,
+        is_connection_reaping_enabled: true,
+        ..Default::default()
+    };
+    let (_connectivity, mut event_stream, node_identity, peer_manager, cm_mock_state, _shutdown) =
+        setup_connectivity_manager(config);
+    let peer = add_test_peers(&peer_manager, 1).await.pop().unwrap();
+    let (_, _, conn, mock_state) = create_peer_connection_mock_pair(peer.clone(), node_identity.to_peer()).await;
+    assert!(conn.direction().is_outbound());
+
+    // An idle connection has no handles other than the pool's, but has open substreams
+    let mut substreams = Vec::new();
+    for _ in 0..3 {
+        substreams.push(mock_state.open_substream().await.unwrap());
+    }
+    cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(conn.into()));
+
+    let connection_age = streams::assert_in_broadcast(
+        &mut event_stream,
+        |item| match item {
+            ConnectivityEvent::ConnectionReaped { node_id, connection_age } if node_id == peer.node_id => {
+                Some(connection_age)
+            },
+            _ => None,
+        },
+        Duration::from_secs(10),
+    )
+    .await;
+    assert!(connection_age > Duration::ZERO);
+}
+
+#[tokio::test]
+async fn tie_break_strategy_selects_the_surviving_connection() {
+    let cases = [
+        (ConnectionTieBreakStrategy::PreferInbound, ConnectionDirection::Inbound),
+        (
+            ConnectionTieBreakStrategy::PreferOutbound,
+            ConnectionDirection::Outbound,
+        ),
+        (ConnectionTieBreakStrategy::PreferOlder, ConnectionDirection::Outbound),
+    ];
+    for (strategy, expected_direction) in cases {
+        let config = ConnectivityConfig {
+            tie_break_strategy: strategy,
+            ..Default::default()
+        };
+        let (mut connectivity, mut event_stream, node_identity, peer_manager, cm_mock_state, _shutdown) =
+            setup_connectivity_manager(config);
+        let peer = add_test_peers(&peer_manager, 1).await.pop().unwrap();
+        let (_, _, outbound_conn, _) = create_peer_connection_mock_pair(peer.clone(), node_identity.to_peer()).await;
+        let (inbound_conn, _, _, _) = create_peer_connection_mock_pair(node_identity.to_peer(), peer.clone()).await;
+        assert_eq!(outbound_conn.direction(), ConnectionDirection::Outbound);
+        assert_eq!(inbound_conn.direction(), ConnectionDirection::Inbound);
+
+        let mut events = collect_try_recv!(event_stream, take = 1, timeout = Duration::from_secs(10));
+        unpack_enum!(ConnectivityEvent::ConnectivityStateInitialized = events.remove(0));
+
+        // Simultaneous dual connection: our outbound connection is established first, then the peer's inbound one
+        cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(outbound_conn.clone().into()));
+        cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(inbound_conn.clone().into()));
+
+        let (survivor, loser) = if expected_direction == ConnectionDirection::Inbound {
+            (inbound_conn, outbound_conn)
+        } else {
+            (outbound_conn, inbound_conn)
+        };
+        async_assert_eventually!(
+            loser.is_connected(),
+            expect = false,
+            max_attempts = 20,
+            interval = Duration::from_millis(100)
+        );
+        let conn = connectivity
+            .get_connection(peer.node_id.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(conn.id(), survivor.id(), "Unexpected survivor for {:?}", strategy);
+        assert!(survivor.is_connected());
+    }
+}
+
+#[tokio::test]
+async fn max_concurrent_dials() {
+    let (connectivity, _event_stream, _node_identity, peer_manager, cm_mock_state, _shutdown) =
+        setup_connectivity_manager(ConnectivityConfig {
+            max_concurrent_dials: 3,
+            ..Default::default()
+        });
+    let peers = add_test_peers(&peer_manager, 10).await;
+    cm_mock_state.set_hold_dials(true);
+
+    let dials = tokio::spawn({
+        let connectivity = connectivity.clone();
+        let node_ids = peers.iter().map(|p| p.node_id.clone()).collect::<Vec<_>>();
+        async move { connectivity.dial_many_peers(node_ids).collect::<Vec<_>>().await }
+    });
+
+    // Excess dials are queued until the in-flight dials complete
+    let mut num_dialed = 0;
+    while num_dialed < peers.len() {
+        async_assert_eventually!(
+            cm_mock_state.held_dial_count().await,
+            expect = min(3, peers.len() - num_dialed),
+            max_attempts = 20,
+            interval = Duration::from_millis(50)
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(cm_mock_state.held_dial_count().await <= 3);
+        num_dialed += cm_mock_state.release_held_dials().await;
+    }
+
+    let results = tokio::time::timeout(Duration::from_secs(10), dials)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(results.len(), 10);
+    assert!(results.iter().all(|r| matches!(
+        r,
+        Err(ConnectivityError::ConnectionFailed(
+            ConnectionManagerError::DialConnectFailedAllAddresses
+        ))
+    )));
+}
+
+#[tokio::test]
+async fn wait_for_min_connectivity() {
+    let (mut connectivity, mut event_stream, node_identity, peer_manager, cm_mock_state, _shutdown) =
+        setup_connectivity_manager(Default::default());
+    let peers = add_test_peers(&peer_manager, 4).await;
+    let mut events = collect_try_recv!(event_stream, take = 1, timeout = Duration::from_secs(10));
+    unpack_enum!(ConnectivityEvent::ConnectivityStateInitialized = events.remove(0));
+
+    let err = connectivity
+        .wait_for_min_connectivity(1, Duration::from_millis(100))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ConnectivityError::MinConnectivityWaitTimeout {
+        min: 1,
+        connected: 0
+    }));
+
+    let mut waiter = connectivity.clone();
+    let wait_task = tokio::spawn(async move { waiter.wait_for_min_connectivity(3, Duration::from_secs(10)).await });
+
+    // Inbound connections do not count towards the minimum
+    let (inbound, _, _, _) = create_peer_connection_mock_pair(node_identity.to_peer(), peers[3].clone()).await;
+    cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(inbound.into()));
+
+    for (i, peer) in peers.iter().take(3).enumerate() {
+        let (_, _, outbound, _) = create_peer_connection_mock_pair(peer.clone(), node_identity.to_peer()).await;
+        cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(outbound.into()));
+        if i < 2 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(!wait_task.is_finished());
+        }
+    }
+
+    let num_connected = tokio::time::timeout(Duration::from_secs(10), wait_task)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(num_connected, 3);
+    assert_eq!(connectivity.get_active_connections().await.unwrap().len(), 4);
+}
@@ -57,6 +57,7 @@ pub struct Body {
     kind: BodyKind,
     is_complete: bool,
     is_terminated: bool,
+    is_compressed: bool,
 }
 
 impl Body {
@@ -65,6 +66,7 @@ impl Body {
             kind: BodyKind::Single(Some(body.into())),
             is_complete: false,
             is_terminated: false,
+            is_compressed: false,
         }
     }
 
@@ -74,9 +76,20 @@ impl Body {
             kind: BodyKind::Streaming(stream.boxed()),
             is_complete: false,
             is_terminated: false,
+            is_compressed: false,
         }
     }
 
+    /// Compress each message of this body before it is sent. Clients decompress the messages transparently.
+    pub fn compressed(mut self) -> Self {
+        self.is_compressed = true;
+        self
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.is_compressed
+    }
+
     pub fn is_single(&self) -> bool {
         matches!(self.kind, BodyKind::Single(_))
     }
@@ -224,16 +237,27 @@ impl Buf for BodyBytes {
 #[derive(Debug)]
 pub struct Streaming<T> {
     inner: mpsc::Receiver<Result<T, RpcStatus>>,
+    is_compressed: bool,
 }
 
 impl<T> Streaming<T> {
     pub fn new(inner: mpsc::Receiver<Result<T, RpcStatus>>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            is_compressed: false,
+        }
     }
 
     pub fn empty() -> Self {
         let (_, rx) = mpsc::channel(1);
-        Self { inner: rx }
+        Self::new(rx)
+    }
+
+    /// Compress each streamed message, e.g. for large, compressible items. Clients decompress the messages
+    /// transparently.
+    pub fn compressed(mut self) -> Self {
+        self.is_compressed = true;
+        self
     }
 
     pub fn into_inner(self) -> mpsc::Receiver<Result<T, RpcStatus>> {
@@ -257,7 +281,13 @@ impl<T: prost::Message> Stream for Streaming<T> {
 
 impl<T: prost::Message + 'static> IntoBody for Streaming<T> {
     fn into_body(self) -> Body {
-        Body::streaming(self)
+        let is_compressed = self.is_compressed;
+        let body = Body::streaming(self);
+        if is_compressed {
+            body.compressed()
+        } else {
+            body
+        }
     }
 }
 
@@ -68,7 +68,7 @@ use crate::{
         rpc,
         rpc::{
             body::ClientStreaming,
-            message::{BaseRequest, RpcMessageFlags},
+            message::{decompress_payload, BaseRequest, RpcMessageFlags},
             Handshake,
             NamedProtocolService,
             Response,
@@ -261,6 +261,12 @@ impl<TClient> RpcClientBuilder<TClient> {
         self
     }
 
+    /// Only offer the given RPC protocol version in the handshake, instead of all versions supported by this node
+    pub fn with_rpc_version(mut self, version: u32) -> Self {
+        self.config.rpc_version = Some(version);
+        self
+    }
+
     /// Set the protocol ID associated with this client. This is used for logging purposes only.
     pub fn with_protocol_id(mut self, protocol_id: ProtocolId) -> Self {
         self.protocol_id = Some(protocol_id);
@@ -306,6 +312,8 @@ pub struct RpcClientConfig {
     pub deadline: Option<Duration>,
     pub deadline_grace_period: Duration,
     pub handshake_timeout: Duration,
+    /// The RPC protocol version to negotiate. If None, all versions supported by this node are offered.
+    pub rpc_version: Option<u32>,
 }
 
 impl RpcClientConfig {
@@ -326,6 +334,7 @@ impl Default for RpcClientConfig {
             deadline: Some(Duration::from_secs(120)),
             deadline_grace_period: Duration::from_secs(60),
             handshake_timeout: Duration::from_secs(90),
+            rpc_version: None,
         }
     }
 }
@@ -465,14 +474,18 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
         );
         let start = Instant::now();
         let mut handshake = Handshake::new(&mut self.framed).with_timeout(self.config.handshake_timeout());
+        if let Some(version) = self.config.rpc_version {
+            handshake = handshake.with_supported_versions(&[version]);
+        }
         match handshake.perform_client_handshake().await {
-            Ok(_) => {
+            Ok(version) => {
                 let latency = start.elapsed();
                 debug!(
                     target: LOG_TARGET,
-                    "(stream={}) RPC Session ({}) negotiation completed. Latency: {:.0?}",
+                    "(stream={}) RPC Session ({}) negotiated v{}. Latency: {:.0?}",
                     self.stream_id(),
                     self.protocol_name(),
+                    version,
                     latency
                 );
                 let _ = self.last_request_latency_tx.send(Some(latency));
@@ -920,10 +933,15 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             Ok(flags) => flags,
             Err(e) => return Ok(Err(RpcError::ServerError(RpcServerError::ProtocolError(e)).into())),
         };
-        let resp = Response {
-            flags,
-            payload: resp.payload.into(),
+        let payload = if flags.is_compressed() {
+            match decompress_payload(&resp.payload) {
+                Ok(payload) => payload,
+                Err(e) => return Ok(Err(RpcError::ServerError(RpcServerError::ProtocolError(e)).into())),
+            }
+        } else {
+            resp.payload.into()
         };
+        let resp = Response { flags, payload };
 
         Ok(Ok(resp))
     }
@@ -113,6 +113,14 @@ impl RpcError {
             RpcError::UnknownError(_) => false,
         }
     }
+
+    /// Returns the reason the server gave for rejecting the session handshake, if it was rejected
+    pub fn handshake_reject_reason(&self) -> Option<HandshakeRejectReason> {
+        match self {
+            RpcError::HandshakeError(RpcHandshakeError::Rejected(reason)) => Some(*reason),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Error, Clone, Copy)]
@@ -139,6 +147,18 @@ impl HandshakeRejectReason {
             .map(Into::into)
             .ok()
     }
+
+    /// Returns true if the session may be accepted when retried later, i.e. the rejection is due to a session limit.
+    /// A protocol or version the server does not support will be rejected again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HandshakeRejectReason::NoServerSessionsAvailable(_) |
+            HandshakeRejectReason::NoClientSessionsAvailable(_) => true,
+            HandshakeRejectReason::UnsupportedVersion |
+            HandshakeRejectReason::ProtocolNotSupported |
+            HandshakeRejectReason::Unknown(_) => false,
+        }
+    }
 }
 
 impl From<rpc_proto::rpc_session_reply::HandshakeRejectReason> for HandshakeRejectReason {
@@ -61,6 +61,8 @@ pub enum RpcHandshakeError {
 pub struct Handshake<'a, T> {
     framed: &'a mut CanonicalFraming<T>,
     timeout: Option<Duration>,
+    supported_versions: Vec<u32>,
+    client_supports_compression: bool,
 }
 
 impl<'a, T> Handshake<'a, T>
@@ -68,7 +70,12 @@ where T: AsyncRead + AsyncWrite + Unpin
 {
     /// Create a Handshake using the given framing and no timeout. To set a timeout, use `with_timeout`.
     pub fn new(framed: &'a mut CanonicalFraming<T>) -> Self {
-        Self { framed, timeout: None }
+        Self {
+            framed,
+            timeout: None,
+            supported_versions: SUPPORTED_RPC_VERSIONS.to_vec(),
+            client_supports_compression: false,
+        }
     }
 
     /// Set the length of time that a client/server should wait for the other side to respond before timing out.
@@ -77,11 +84,24 @@ where T: AsyncRead + AsyncWrite + Unpin
         self
     }
 
+    /// Set the RPC protocol versions that the client offers to the server. Defaults to all supported versions.
+    pub fn with_supported_versions(mut self, versions: &[u32]) -> Self {
+        self.supported_versions = versions.to_vec();
+        self
+    }
+
+    /// Whether the client can decompress compressed response payloads. Only known once the server-side handshake has
+    /// been performed, and false for clients that predate compression.
+    pub fn client_supports_compression(&self) -> bool {
+        self.client_supports_compression
+    }
+
     /// Server-side handshake protocol
     pub async fn perform_server_handshake(&mut self) -> Result<u32, RpcHandshakeError> {
         match self.recv_next_frame().await {
             Ok(Some(Ok(msg))) => {
                 let msg = proto::rpc::RpcSession::decode(&mut msg.freeze())?;
+                self.client_supports_compression = msg.supports_compression;
                 let version = SUPPORTED_RPC_VERSIONS
                     .iter()
                     .find(|v| msg.supported_versions.contains(v));
@@ -131,10 +151,11 @@ where T: AsyncRead + AsyncWrite + Unpin
         Ok(())
     }
 
-    /// Client-side handshake protocol
-    pub async fn perform_client_handshake(&mut self) -> Result<(), RpcHandshakeError> {
+    /// Client-side handshake protocol. Returns the version accepted by the server.
+    pub async fn perform_client_handshake(&mut self) -> Result<u32, RpcHandshakeError> {
         let msg = proto::rpc::RpcSession {
-            supported_versions: SUPPORTED_RPC_VERSIONS.to_vec(),
+            supported_versions: self.supported_versions.clone(),
+            supports_compression: cfg!(feature = "compression"),
         };
         let payload = msg.to_encoded_bytes();
         debug!(target: LOG_TARGET, "Sending client handshake ({} bytes)", payload.len());
@@ -153,7 +174,7 @@ where T: AsyncRead + AsyncWrite + Unpin
                 let msg = proto::rpc::RpcSessionReply::decode(&mut msg.freeze())?;
                 let version = msg.result()?;
                 debug!(target: LOG_TARGET, "Remote server accepted version {}", version);
-                Ok(())
+                Ok(version)
             },
             Ok(Some(Err(err))) => {
                 error!(target: LOG_TARGET, "Error during handshake: {}", err);
@@ -208,6 +208,8 @@ bitflags! {
         const FIN = 0x01;
         /// Typically sent with empty contents and used to confirm a substream is alive.
         const ACK = 0x02;
+        /// The payload is compressed and must be decompressed before it is decoded.
+        const COMPRESSED = 0x04;
     }
 }
 impl RpcMessageFlags {
@@ -218,6 +220,10 @@ impl RpcMessageFlags {
     pub fn is_ack(self) -> bool {
         self.contains(Self::ACK)
     }
+
+    pub fn is_compressed(self) -> bool {
+        self.contains(Self::COMPRESSED)
+    }
 }
 
 impl Default for RpcMessageFlags {
@@ -276,6 +282,20 @@ impl RpcResponse {
         }
     }
 
+    /// Compresses a non-empty, successful payload and sets the `COMPRESSED` flag. Without the `compression` feature the
+    /// response is returned as is.
+    pub fn compress(self) -> RpcResponse {
+        #[cfg(feature = "compression")]
+        if self.status.is_ok() && !self.payload.is_empty() {
+            return RpcResponse {
+                flags: self.flags | RpcMessageFlags::COMPRESSED,
+                payload: lz4_flex::block::compress_prepend_size(&self.payload).into(),
+                ..self
+            };
+        }
+        self
+    }
+
     pub fn exceeded_message_size(self) -> RpcResponse {
         let msg = format!(
             "The response size exceeded the maximum allowed payload size. Max = {} bytes, Got = {} bytes",
@@ -319,6 +339,30 @@ impl proto::rpc::RpcResponse {
     }
 }
 
+/// Decompresses the payload of a response with the `COMPRESSED` flag. The decompressed size may not exceed the
+/// maximum response payload size.
+pub(super) fn decompress_payload(payload: &[u8]) -> Result<Bytes, String> {
+    #[cfg(feature = "compression")]
+    {
+        let (size, compressed) =
+            lz4_flex::block::uncompressed_size(payload).map_err(|e| format!("invalid compressed payload: {}", e))?;
+        if size > rpc::max_response_payload_size() {
+            return Err(format!(
+                "decompressed payload size ({} bytes) exceeds the maximum allowed payload size",
+                size
+            ));
+        }
+        lz4_flex::block::decompress(compressed, size)
+            .map(Bytes::from)
+            .map_err(|e| format!("invalid compressed payload: {}", e))
+    }
+    #[cfg(not(feature = "compression"))]
+    {
+        let _unused = payload;
+        Err("received a compressed payload but compression is not supported".to_string())
+    }
+}
+
 impl fmt::Display for proto::rpc::RpcResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -54,7 +54,16 @@ pub use body::{Body, ClientStreaming, IntoBody, Streaming};
 mod context;
 
 mod server;
-pub use server::{mock, NamedProtocolService, RpcServer, RpcServerBuilder, RpcServerError, RpcServerHandle};
+pub use server::{
+    mock,
+    ByteCounts,
+    NamedProtocolService,
+    RpcServer,
+    RpcServerBuilder,
+    RpcServerError,
+    RpcServerHandle,
+    SessionInfo,
+};
 
 mod client;
 pub use client::{
@@ -71,7 +80,7 @@ mod message;
 pub use message::{Request, Response};
 
 mod error;
-pub use error::RpcError;
+pub use error::{HandshakeRejectReason, RpcError};
 
 mod handshake;
 pub use handshake::{Handshake, RpcHandshakeError};
@@ -64,6 +64,8 @@ pub enum RpcServerError {
     EarlyClose(#[from] EarlyCloseError<BytesMut>),
     #[error("Protocol error: {0}")]
     ProtocolError(String),
+    #[error("Session exceeded its byte quota of {quota} bytes")]
+    SessionByteQuotaExceeded { quota: u64 },
 }
 
 impl RpcServerError {
@@ -20,16 +20,47 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::HashMap;
+
 use tokio::sync::{mpsc, oneshot};
 
 use super::RpcServerError;
-use crate::peer_manager::NodeId;
+use crate::{peer_manager::NodeId, protocol::ProtocolId};
 
 #[derive(Debug)]
 pub enum RpcServerRequest {
     GetNumActiveSessions(oneshot::Sender<usize>),
     GetNumActiveSessionsForPeer(NodeId, oneshot::Sender<usize>),
     CloseAllSessionsForPeer(NodeId, oneshot::Sender<usize>),
+    ListActiveSessions(oneshot::Sender<Vec<SessionInfo>>),
+    GetNumActiveSessionsForVersion(u32, oneshot::Sender<usize>),
+    GetBytesForPeer(NodeId, oneshot::Sender<ByteCounts>),
+    ListServices(oneshot::Sender<Vec<ProtocolId>>),
+    GetActiveSessionsByPeer(oneshot::Sender<HashMap<NodeId, usize>>),
+}
+
+/// Information about an active RPC session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionInfo {
+    pub node_id: NodeId,
+    pub protocol: ProtocolId,
+    /// The RPC protocol version negotiated in the session handshake
+    pub version: u32,
+    /// The bytes sent and received in this session, excluding the session handshake
+    pub bytes: ByteCounts,
+}
+
+/// The number of RPC message bytes sent to and received from a peer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByteCounts {
+    pub sent: u64,
+    pub received: u64,
+}
+
+impl ByteCounts {
+    pub fn total(&self) -> u64 {
+        self.sent.saturating_add(self.received)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +91,57 @@ impl RpcServerHandle {
         resp.await.map_err(Into::into)
     }
 
+    /// Returns information about all active sessions
+    pub async fn list_active_sessions(&mut self) -> Result<Vec<SessionInfo>, RpcServerError> {
+        let (req, resp) = oneshot::channel();
+        self.sender
+            .send(RpcServerRequest::ListActiveSessions(req))
+            .await
+            .map_err(|_| RpcServerError::RequestCanceled)?;
+        resp.await.map_err(Into::into)
+    }
+
+    /// Returns the number of active sessions that negotiated the given RPC protocol version
+    pub async fn get_num_active_sessions_for_version(&mut self, version: u32) -> Result<usize, RpcServerError> {
+        let (req, resp) = oneshot::channel();
+        self.sender
+            .send(RpcServerRequest::GetNumActiveSessionsForVersion(version, req))
+            .await
+            .map_err(|_| RpcServerError::RequestCanceled)?;
+        resp.await.map_err(Into::into)
+    }
+
+    /// Returns the bytes sent and received in all sessions (active and closed) with the peer since the server started.
+    /// The counts of peers without active sessions are only kept for the most recently active peers.
+    pub async fn get_bytes_for(&mut self, peer: NodeId) -> Result<ByteCounts, RpcServerError> {
+        let (req, resp) = oneshot::channel();
+        self.sender
+            .send(RpcServerRequest::GetBytesForPeer(peer, req))
+            .await
+            .map_err(|_| RpcServerError::RequestCanceled)?;
+        resp.await.map_err(Into::into)
+    }
+
+    /// Returns the protocol ids of all services registered on this server
+    pub async fn list_services(&mut self) -> Result<Vec<ProtocolId>, RpcServerError> {
+        let (req, resp) = oneshot::channel();
+        self.sender
+            .send(RpcServerRequest::ListServices(req))
+            .await
+            .map_err(|_| RpcServerError::RequestCanceled)?;
+        resp.await.map_err(Into::into)
+    }
+
+    /// Returns the number of active sessions of every peer that has at least one active session
+    pub async fn get_active_sessions_by_peer(&mut self) -> Result<HashMap<NodeId, usize>, RpcServerError> {
+        let (req, resp) = oneshot::channel();
+        self.sender
+            .send(RpcServerRequest::GetActiveSessionsByPeer(req))
+            .await
+            .map_err(|_| RpcServerError::RequestCanceled)?;
+        resp.await.map_err(Into::into)
+    }
+
     pub async fn close_all_sessions_for(&mut self, peer: NodeId) -> Result<usize, RpcServerError> {
         let (req, resp) = oneshot::channel();
         self.sender
@@ -289,6 +289,15 @@ impl MockRpcImpl {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Respond to calls to the method with the given identifier with `response`
+    pub async fn set_response<T: prost::Message>(&self, method_id: u32, response: T) {
+        self.state
+            .lock()
+            .await
+            .accepted_calls
+            .insert(method_id, Response::new(Bytes::from(response.encode_to_vec())));
+    }
 }
 
 impl Service<Request<Bytes>> for MockRpcImpl {
@@ -26,8 +26,8 @@ mod error;
 pub use error::RpcServerError;
 
 mod handle;
-pub use handle::RpcServerHandle;
 use handle::RpcServerRequest;
+pub use handle::{ByteCounts, RpcServerHandle, SessionInfo};
 
 #[cfg(feature = "metrics")]
 mod metrics;
@@ -40,13 +40,16 @@ mod router;
 use std::{
     borrow::Cow,
     cmp,
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     convert::TryFrom,
     future::Future,
     io,
     io::ErrorKind,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     task::Poll,
     time::{Duration, Instant},
 };
@@ -95,6 +98,9 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "comms::rpc::server";
+/// The maximum number of peers without active sessions whose byte counts are kept. Beyond this, the counts of the
+/// peers whose last session ended longest ago are discarded.
+const MAX_IDLE_PEER_BYTE_COUNTS: usize = 1000;
 
 pub trait NamedProtocolService {
     const PROTOCOL_NAME: &'static [u8];
@@ -143,6 +149,7 @@ impl RpcServer {
     pub(super) async fn serve<S, TCommsProvider>(
         self,
         service: S,
+        protocols: Vec<ProtocolId>,
         notifications: ProtocolNotificationRx<Substream>,
         comms_provider: TCommsProvider,
     ) -> Result<(), RpcServerError>
@@ -162,6 +169,7 @@ impl RpcServer {
         TCommsProvider: RpcCommsProvider + Clone + Send + 'static,
     {
         PeerRpcServer::new(self.builder, service, notifications, comms_provider, self.request_rx)
+            .with_protocols(protocols)
             .serve()
             .await
     }
@@ -180,6 +188,8 @@ pub struct RpcServerBuilder {
     minimum_client_deadline: Duration,
     handshake_timeout: Duration,
     cull_oldest_peer_rpc_connection_on_full: bool,
+    method_rate_limits: HashMap<u32, u32>,
+    session_byte_quota: Option<u64>,
 }
 
 impl RpcServerBuilder {
@@ -217,6 +227,20 @@ impl RpcServerBuilder {
         self
     }
 
+    /// Limit the number of calls to the given method to `max_per_sec` per second for each session. Requests that
+    /// exceed the limit are rejected with a `RateLimited` status.
+    pub fn with_method_rate_limit(mut self, method_id: u32, max_per_sec: u32) -> Self {
+        self.method_rate_limits.insert(method_id, max_per_sec);
+        self
+    }
+
+    /// Close sessions once the bytes sent and received in the session would exceed `quota`. The request that exceeds
+    /// the quota is rejected with a `QuotaExceeded` status.
+    pub fn with_session_byte_quota(mut self, quota: u64) -> Self {
+        self.session_byte_quota = Some(quota);
+        self
+    }
+
     pub fn finish(self) -> RpcServer {
         let (request_tx, request_rx) = mpsc::channel(10);
         RpcServer {
@@ -235,6 +259,8 @@ impl Default for RpcServerBuilder {
             minimum_client_deadline: Duration::from_secs(1),
             handshake_timeout: Duration::from_secs(15),
             cull_oldest_peer_rpc_connection_on_full: false,
+            method_rate_limits: HashMap::new(),
+            session_byte_quota: None,
         }
     }
 }
@@ -246,13 +272,58 @@ pub(super) struct PeerRpcServer<TSvc, TCommsProvider> {
     protocol_notifications: Option<ProtocolNotificationRx<Substream>>,
     comms_provider: TCommsProvider,
     request_rx: mpsc::Receiver<RpcServerRequest>,
-    sessions: HashMap<NodeId, Vec<SessionInfo>>,
+    protocols: Vec<ProtocolId>,
+    sessions: HashMap<NodeId, Vec<PeerSession>>,
+    peer_bytes: HashMap<NodeId, Arc<ByteCounter>>,
+    /// Peers in `peer_bytes` whose sessions have all ended, least recently ended first
+    idle_peers: VecDeque<NodeId>,
     tasks: FuturesUnordered<JoinHandle<(NodeId, Id)>>,
 }
 
-struct SessionInfo {
+struct PeerSession {
     pub(crate) peer_watch: tokio::sync::watch::Sender<()>,
     pub(crate) stream_id: Id,
+    pub(crate) protocol: ProtocolId,
+    pub(crate) version: u32,
+    pub(crate) bytes: Arc<ByteCounter>,
+}
+
+#[derive(Debug, Default)]
+struct ByteCounter {
+    sent: AtomicU64,
+    received: AtomicU64,
+}
+
+impl ByteCounter {
+    fn counts(&self) -> ByteCounts {
+        ByteCounts {
+            sent: self.sent.load(Ordering::Relaxed),
+            received: self.received.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Counts the bytes of a session towards both the session and the peer totals
+#[derive(Debug, Clone, Default)]
+struct SessionBytes {
+    session: Arc<ByteCounter>,
+    peer: Arc<ByteCounter>,
+}
+
+impl SessionBytes {
+    fn add_sent(&self, num_bytes: usize) {
+        self.session.sent.fetch_add(num_bytes as u64, Ordering::Relaxed);
+        self.peer.sent.fetch_add(num_bytes as u64, Ordering::Relaxed);
+    }
+
+    fn add_received(&self, num_bytes: usize) {
+        self.session.received.fetch_add(num_bytes as u64, Ordering::Relaxed);
+        self.peer.received.fetch_add(num_bytes as u64, Ordering::Relaxed);
+    }
+
+    fn session_total(&self) -> u64 {
+        self.session.counts().total()
+    }
 }
 
 impl<TSvc, TCommsProvider> PeerRpcServer<TSvc, TCommsProvider>
@@ -288,11 +359,20 @@ where
             protocol_notifications: Some(protocol_notifications),
             comms_provider,
             request_rx,
+            protocols: Vec::new(),
             sessions: HashMap::new(),
+            peer_bytes: HashMap::new(),
+            idle_peers: VecDeque::new(),
             tasks: FuturesUnordered::new(),
         }
     }
 
+    /// Sets the protocol ids of the registered services, as reported by `RpcServerHandle::list_services`
+    fn with_protocols(mut self, protocols: Vec<ProtocolId>) -> Self {
+        self.protocols = protocols;
+        self
+    }
+
     pub async fn serve(mut self) -> Result<(), RpcServerError> {
         let mut protocol_notifs = self
             .protocol_notifications
@@ -347,6 +427,50 @@ where
                 let num_closed = self.close_all_sessions(&node_id);
                 let _ = reply.send(num_closed);
             },
+            ListActiveSessions(reply) => {
+                let sessions = self
+                    .sessions
+                    .iter()
+                    .flat_map(|(node_id, sessions)| {
+                        sessions.iter().map(move |session| SessionInfo {
+                            node_id: node_id.clone(),
+                            protocol: session.protocol.clone(),
+                            version: session.version,
+                            bytes: session.bytes.counts(),
+                        })
+                    })
+                    .collect();
+                let _ = reply.send(sessions);
+            },
+            GetNumActiveSessionsForVersion(version, reply) => {
+                let num_active = self
+                    .sessions
+                    .values()
+                    .flatten()
+                    .filter(|session| session.version == version)
+                    .count();
+                let _ = reply.send(num_active);
+            },
+            GetBytesForPeer(node_id, reply) => {
+                let bytes = self
+                    .peer_bytes
+                    .get(&node_id)
+                    .map(|counter| counter.counts())
+                    .unwrap_or_default();
+                let _ = reply.send(bytes);
+            },
+            ListServices(reply) => {
+                let _ = reply.send(self.protocols.clone());
+            },
+            GetActiveSessionsByPeer(reply) => {
+                let sessions_by_peer = self
+                    .sessions
+                    .iter()
+                    .filter(|(_, sessions)| !sessions.is_empty())
+                    .map(|(node_id, sessions)| (node_id.clone(), sessions.len()))
+                    .collect();
+                let _ = reply.send(sessions_by_peer);
+            },
         }
     }
 
@@ -442,6 +566,33 @@ where
                 self.sessions.remove(node_id);
             }
         }
+        self.on_peer_bytes_released(node_id);
+    }
+
+    /// Marks the byte counts of the peer as idle once no session holds them anymore, and discards the counts of the
+    /// least recently idle peers beyond `MAX_IDLE_PEER_BYTE_COUNTS`
+    fn on_peer_bytes_released(&mut self, node_id: &NodeId) {
+        let is_idle = self
+            .peer_bytes
+            .get(node_id)
+            .is_some_and(|counter| Arc::strong_count(counter) == 1);
+        if !is_idle {
+            return;
+        }
+        self.idle_peers.retain(|peer| peer != node_id);
+        self.idle_peers.push_back(node_id.clone());
+        while self.idle_peers.len() > MAX_IDLE_PEER_BYTE_COUNTS {
+            if let Some(peer) = self.idle_peers.pop_front() {
+                // The peer may have started a new session since it became idle
+                if self
+                    .peer_bytes
+                    .get(&peer)
+                    .is_some_and(|counter| Arc::strong_count(counter) == 1)
+                {
+                    self.peer_bytes.remove(&peer);
+                }
+            }
+        }
     }
 
     #[allow(clippy::too_many_lines)]
@@ -504,43 +655,60 @@ where
         }
 
         let version = handshake.perform_server_handshake().await?;
+        let compress_responses = handshake.client_supports_compression();
         debug!(
             target: LOG_TARGET,
-            "Server negotiated RPC v{} with client node `{}`", version, node_id
+            "Server negotiated RPC v{} with client node `{}` (compression: {})", version, node_id, compress_responses
         );
         let stream_id = framed.stream_id();
         let (stop_tx, stop_rx) = tokio::sync::watch::channel(());
+        let bytes = SessionBytes {
+            session: Arc::new(ByteCounter::default()),
+            peer: self.peer_bytes.entry(node_id.clone()).or_default().clone(),
+        };
         let service = ActivePeerRpcService::new(
             self.config.clone(),
-            protocol,
+            protocol.clone(),
             node_id.clone(),
             service,
             framed,
             self.comms_provider.clone(),
             stop_rx,
+            bytes.clone(),
+            compress_responses,
         );
 
         let node_id_clone = node_id.clone();
-        let handle = self
-            .executor
-            .try_spawn(async move {
-                #[cfg(feature = "metrics")]
-                let num_sessions = metrics::num_sessions(&node_id_clone, &service.protocol);
-                #[cfg(feature = "metrics")]
-                num_sessions.inc();
-                service.start().await;
-                info!(target: LOG_TARGET, "END OF SESSION for {} ", node_id_clone,);
-                #[cfg(feature = "metrics")]
-                num_sessions.dec();
-
-                (node_id_clone, stream_id)
-            })
-            .map_err(|e| RpcServerError::MaximumSessionsReached(format!("{:?}", e)))?;
+        let spawn_result = self.executor.try_spawn(async move {
+            #[cfg(feature = "metrics")]
+            let num_sessions = metrics::num_sessions(&node_id_clone, &service.protocol);
+            #[cfg(feature = "metrics")]
+            num_sessions.inc();
+            service.start().await;
+            info!(target: LOG_TARGET, "END OF SESSION for {} ", node_id_clone,);
+            #[cfg(feature = "metrics")]
+            num_sessions.dec();
+
+            (node_id_clone, stream_id)
+        });
+        // From here on only the session task holds the peer's byte counts
+        let SessionBytes { session, peer } = bytes;
+        drop(peer);
+        let handle = match spawn_result {
+            Ok(handle) => handle,
+            Err(e) => {
+                self.on_peer_bytes_released(node_id);
+                return Err(RpcServerError::MaximumSessionsReached(format!("{:?}", e)));
+            },
+        };
 
         self.tasks.push(handle);
-        let mut peer_stop = vec![SessionInfo {
+        let mut peer_stop = vec![PeerSession {
             peer_watch: stop_tx,
             stream_id,
+            protocol,
+            version,
+            bytes: session,
         }];
         self.sessions
             .entry(node_id.clone())
@@ -573,6 +741,10 @@ struct ActivePeerRpcService<TSvc, TCommsProvider> {
     comms_provider: TCommsProvider,
     logging_context_string: Arc<String>,
     stop_rx: tokio::sync::watch::Receiver<()>,
+    method_call_windows: HashMap<u32, (Instant, u32)>,
+    bytes: SessionBytes,
+    /// Streams that ask for compression are only compressed if the client said it can decompress them
+    compress_responses: bool,
 }
 
 impl<TSvc, TCommsProvider> ActivePeerRpcService<TSvc, TCommsProvider>
@@ -588,6 +760,8 @@ where
         framed: CanonicalFraming<Substream>,
         comms_provider: TCommsProvider,
         stop_rx: tokio::sync::watch::Receiver<()>,
+        bytes: SessionBytes,
+        compress_responses: bool,
     ) -> Self {
         Self {
             logging_context_string: Arc::new(format!(
@@ -605,6 +779,9 @@ where
             framed: EarlyClose::new(framed),
             comms_provider,
             stop_rx,
+            method_call_windows: HashMap::new(),
+            bytes,
+            compress_responses,
         }
     }
 
@@ -619,6 +796,7 @@ where
             let level = match &err {
                 RpcServerError::Io(e) => err_to_log_level(e),
                 RpcServerError::EarlyClose(e) => e.io().map(err_to_log_level).unwrap_or(log::Level::Error),
+                RpcServerError::SessionByteQuotaExceeded { .. } => log::Level::Debug,
                 _ => log::Level::Error,
             };
             log!(
@@ -643,6 +821,7 @@ where
                         Some(Ok(frame)) => {
                             #[cfg(feature = "metrics")]
                             metrics::inbound_requests_bytes(&self.node_id, &self.protocol).observe(frame.len() as f64);
+                            self.bytes.add_received(frame.len());
 
                             let start = Instant::now();
 
@@ -658,7 +837,10 @@ where
                                         err,
                                     );
                                 }
-                                let level = err.early_close_io().map(err_to_log_level).unwrap_or(log::Level::Error);
+                                let level = match &err {
+                                    RpcServerError::SessionByteQuotaExceeded { .. } => log::Level::Debug,
+                                    err => err.early_close_io().map(err_to_log_level).unwrap_or(log::Level::Error),
+                                };
                                 log!(
                                     target: LOG_TARGET,
                                     level,
@@ -705,6 +887,10 @@ where
         let method = RpcMethod::from(decoded_msg.method);
         let deadline = Duration::from_secs(decoded_msg.deadline);
 
+        if self.exceeds_byte_quota(0) {
+            return self.close_for_byte_quota(request_id).await;
+        }
+
         // The client side deadline MUST be greater or equal to the minimum_client_deadline
         if deadline < self.config.minimum_client_deadline {
             debug!(
@@ -724,7 +910,7 @@ where
             };
             #[cfg(feature = "metrics")]
             metrics::status_error_counter(&self.node_id, &self.protocol, status.as_status_code()).inc();
-            self.framed.send(bad_request.to_encoded_bytes().into()).await?;
+            self.send(bad_request.to_encoded_bytes().into()).await?;
             return Ok(());
         }
 
@@ -751,7 +937,7 @@ where
                 flags: RpcMessageFlags::ACK.bits().into(),
                 ..Default::default()
             };
-            self.framed.send(ack.to_encoded_bytes().into()).await?;
+            self.send(ack.to_encoded_bytes().into()).await?;
             return Ok(());
         }
 
@@ -763,6 +949,27 @@ where
             method.id()
         );
 
+        if self.is_rate_limited(method) {
+            debug!(
+                target: LOG_TARGET,
+                "({}) Method {} exceeded its rate limit, rejecting request {}",
+                self.logging_context_string,
+                method.id(),
+                request_id
+            );
+            let status = RpcStatus::rate_limited(&format!("Rate limit exceeded for method {}", method.id()));
+            let resp = proto::rpc::RpcResponse {
+                request_id,
+                status: status.as_code(),
+                flags: RpcMessageFlags::FIN.bits().into(),
+                payload: status.to_details_bytes(),
+            };
+            #[cfg(feature = "metrics")]
+            metrics::status_error_counter(&self.node_id, &self.protocol, status.as_status_code()).inc();
+            self.send(resp.to_encoded_bytes().into()).await?;
+            return Ok(());
+        }
+
         let req = Request::with_context(
             self.create_request_context(request_id),
             method,
@@ -815,13 +1022,69 @@ where
 
                 #[cfg(feature = "metrics")]
                 metrics::status_error_counter(&self.node_id, &self.protocol, err.as_status_code()).inc();
-                self.framed.send(resp.to_encoded_bytes().into()).await?;
+                self.send(resp.to_encoded_bytes().into()).await?;
             },
         }
 
         Ok(())
     }
 
+    /// Records a call to `method` and returns true if it exceeds the configured per-second limit for this session.
+    fn is_rate_limited(&mut self, method: RpcMethod) -> bool {
+        let Some(max_per_sec) = self.config.method_rate_limits.get(&method.id()).copied() else {
+            return false;
+        };
+        let now = Instant::now();
+        let (window_start, count) = self.method_call_windows.entry(method.id()).or_insert((now, 0));
+        if now.duration_since(*window_start) >= Duration::from_secs(1) {
+            *window_start = now;
+            *count = 0;
+        }
+        if *count >= max_per_sec {
+            return true;
+        }
+        *count += 1;
+        false
+    }
+
+    /// Sends a message to the client, counting it towards the session bytes
+    async fn send(&mut self, msg: Bytes) -> Result<(), RpcServerError> {
+        let len = msg.len();
+        self.framed.send(msg).await?;
+        self.bytes.add_sent(len);
+        Ok(())
+    }
+
+    /// Returns true if sending `num_bytes` more would take the session over its byte quota
+    fn exceeds_byte_quota(&self, num_bytes: usize) -> bool {
+        self.config
+            .session_byte_quota
+            .is_some_and(|quota| self.bytes.session_total().saturating_add(num_bytes as u64) > quota)
+    }
+
+    /// Rejects the request with a `QuotaExceeded` status and returns an error to close the session
+    async fn close_for_byte_quota(&mut self, request_id: u32) -> Result<(), RpcServerError> {
+        let quota = self.config.session_byte_quota.unwrap_or_default();
+        debug!(
+            target: LOG_TARGET,
+            "({}) Session exceeded its byte quota of {} bytes ({:?}), closing session",
+            self.logging_context_string,
+            quota,
+            self.bytes.session.counts()
+        );
+        let status = RpcStatus::quota_exceeded(&format!("Session exceeded its byte quota of {} bytes", quota));
+        let resp = proto::rpc::RpcResponse {
+            request_id,
+            status: status.as_code(),
+            flags: RpcMessageFlags::FIN.bits().into(),
+            payload: status.to_details_bytes(),
+        };
+        #[cfg(feature = "metrics")]
+        metrics::status_error_counter(&self.node_id, &self.protocol, status.as_status_code()).inc();
+        self.framed.send(resp.to_encoded_bytes().into()).await?;
+        Err(RpcServerError::SessionByteQuotaExceeded { quota })
+    }
+
     fn protocol_name(&self) -> Cow<'_, str> {
         String::from_utf8_lossy(&self.protocol)
     }
@@ -838,12 +1101,15 @@ where
         let node_id = self.node_id.clone();
         #[cfg(feature = "metrics")]
         let protocol = self.protocol.clone();
+        let is_compressed = self.compress_responses && body.payload.is_compressed();
         let mut stream = body
             .into_message()
             .map(|result| into_response(request_id, result))
             .map(move |mut message| {
                 if message.payload.len() > rpc::max_response_payload_size() {
                     message = message.exceeded_message_size();
+                } else if is_compressed {
+                    message = message.compress();
                 }
                 #[cfg(feature = "metrics")]
                 if !message.status.is_ok() {
@@ -888,7 +1154,10 @@ where
                                 msg.len()
                             );
 
-                            self.framed.send(msg).await?;
+                            if self.exceeds_byte_quota(msg.len()) {
+                                return self.close_for_byte_quota(request_id).await;
+                            }
+                            self.send(msg).await?;
                         },
                         None => {
                             trace!(target: LOG_TARGET, "{} Request complete", self.logging_context_string,);
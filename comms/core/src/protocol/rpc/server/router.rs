@@ -141,7 +141,7 @@ where
         TCommsProvider: RpcCommsProvider + Clone + Send + 'static,
     {
         self.server
-            .serve(self.routes, protocol_notifications, comms_provider)
+            .serve(self.routes, self.protocol_names, protocol_notifications, comms_provider)
             .await
             .map_err(Into::into)
     }
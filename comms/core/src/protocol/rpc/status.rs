@@ -106,6 +106,20 @@ impl RpcStatus {
         }
     }
 
+    pub fn rate_limited<T: ToString + ?Sized>(details: &T) -> Self {
+        Self {
+            code: RpcStatusCode::RateLimited,
+            details: details.to_string(),
+        }
+    }
+
+    pub fn quota_exceeded<T: ToString + ?Sized>(details: &T) -> Self {
+        Self {
+            code: RpcStatusCode::QuotaExceeded,
+            details: details.to_string(),
+        }
+    }
+
     /// Returns a closure that logs the given error and returns a generic general error that does not leak any
     /// potentially sensitive error information. Use this function with map_err to catch "miscellaneous" errors.
     pub fn log_internal_error<'a, E: std::error::Error + 'a>(target: &'a str) -> impl Fn(E) -> Self + 'a {
@@ -145,6 +159,14 @@ impl RpcStatus {
     pub fn is_not_found(&self) -> bool {
         self.code.is_not_found()
     }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.code.is_rate_limited()
+    }
+
+    pub fn is_quota_exceeded(&self) -> bool {
+        self.code.is_quota_exceeded()
+    }
 }
 
 impl Display for RpcStatus {
@@ -239,6 +261,10 @@ pub enum RpcStatusCode {
     Forbidden = 9,
     /// RPC conflict error
     Conflict = 10,
+    /// The request was rejected because the method rate limit for this session was exceeded
+    RateLimited = 11,
+    /// The session was closed because it exceeded its byte quota
+    QuotaExceeded = 12,
     // The following status represents anything that is not recognised (i.e not one of the above codes).
     /// Unrecognised RPC status code
     InvalidRpcStatusCode,
@@ -257,6 +283,14 @@ impl RpcStatusCode {
         self == Self::Timeout
     }
 
+    pub fn is_rate_limited(self) -> bool {
+        self == Self::RateLimited
+    }
+
+    pub fn is_quota_exceeded(self) -> bool {
+        self == Self::QuotaExceeded
+    }
+
     pub fn as_u32(&self) -> u32 {
         *self as u32
     }
@@ -282,6 +316,8 @@ impl From<u32> for RpcStatusCode {
             8 => ProtocolError,
             9 => Forbidden,
             10 => Conflict,
+            11 => RateLimited,
+            12 => QuotaExceeded,
             _ => InvalidRpcStatusCode,
         }
     }
@@ -307,6 +343,8 @@ mod test {
         assert_eq!(RpcStatusCode::from(ProtocolError as u32), ProtocolError);
         assert_eq!(RpcStatusCode::from(Forbidden as u32), Forbidden);
         assert_eq!(RpcStatusCode::from(Conflict as u32), Conflict);
+        assert_eq!(RpcStatusCode::from(RateLimited as u32), RateLimited);
+        assert_eq!(RpcStatusCode::from(QuotaExceeded as u32), QuotaExceeded);
         assert_eq!(RpcStatusCode::from(123), InvalidRpcStatusCode);
     }
 
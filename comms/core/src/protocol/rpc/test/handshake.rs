@@ -20,12 +20,15 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use futures::SinkExt;
 use tari_test_utils::unpack_enum;
 use tokio::task;
 
 use crate::{
     framing,
     memsocket::MemorySocket,
+    message::MessageExt,
+    proto,
     protocol::rpc::{
         error::HandshakeRejectReason,
         handshake::{RpcHandshakeError, SUPPORTED_RPC_VERSIONS},
@@ -51,6 +54,47 @@ async fn it_performs_the_handshake() {
     assert!(SUPPORTED_RPC_VERSIONS.contains(&v));
 }
 
+#[tokio::test]
+async fn it_tells_the_server_whether_the_client_supports_compression() {
+    let (client, server) = MemorySocket::new_pair();
+
+    let handshake_result = task::spawn(async move {
+        let mut server_framed = framing::canonical(server, 1024);
+        let mut handshake_server = Handshake::new(&mut server_framed);
+        handshake_server.perform_server_handshake().await.unwrap();
+        handshake_server.client_supports_compression()
+    });
+
+    let mut client_framed = framing::canonical(client, 1024);
+    let mut handshake_client = Handshake::new(&mut client_framed);
+
+    handshake_client.perform_client_handshake().await.unwrap();
+    let supports_compression = handshake_result.await.unwrap();
+    assert_eq!(supports_compression, cfg!(feature = "compression"));
+}
+
+#[tokio::test]
+async fn it_does_not_compress_for_clients_that_do_not_announce_support() {
+    let (client, server) = MemorySocket::new_pair();
+
+    let handshake_result = task::spawn(async move {
+        let mut server_framed = framing::canonical(server, 1024);
+        let mut handshake_server = Handshake::new(&mut server_framed);
+        handshake_server.perform_server_handshake().await.unwrap();
+        handshake_server.client_supports_compression()
+    });
+
+    // A client from before compression existed does not send the field at all
+    let mut client_framed = framing::canonical(client, 1024);
+    let msg = proto::rpc::RpcSession {
+        supported_versions: SUPPORTED_RPC_VERSIONS.to_vec(),
+        ..Default::default()
+    };
+    client_framed.send(msg.to_encoded_bytes().into()).await.unwrap();
+
+    assert!(!handshake_result.await.unwrap());
+}
+
 #[tokio::test]
 async fn it_rejects_the_handshake() {
     let (client, server) = MemorySocket::new_pair();
@@ -23,12 +23,12 @@
 use std::{
     collections::HashMap,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
 };
 
-use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
 
 use crate::{
     connection_manager::{
@@ -56,8 +56,12 @@ pub struct ConnectionManagerMockState {
     calls: Arc<Mutex<Vec<String>>>,
     active_conns: Arc<Mutex<HashMap<NodeId, PeerConnection>>>,
     event_tx: broadcast::Sender<Arc<ConnectionManagerEvent>>,
+    hold_dials: Arc<AtomicBool>,
+    held_dials: Arc<Mutex<Vec<HeldDial>>>,
 }
 
+type HeldDial = (NodeId, oneshot::Sender<Result<PeerConnection, ConnectionManagerError>>);
+
 impl ConnectionManagerMockState {
     pub fn new(event_tx: broadcast::Sender<Arc<ConnectionManagerEvent>>) -> Self {
         Self {
@@ -65,6 +69,8 @@ impl ConnectionManagerMockState {
             calls: Arc::new(Mutex::new(Vec::new())),
             event_tx,
             active_conns: Arc::new(Mutex::new(HashMap::new())),
+            hold_dials: Arc::new(AtomicBool::new(false)),
+            held_dials: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -94,6 +100,38 @@ impl ConnectionManagerMockState {
     pub fn publish_event(&self, event: ConnectionManagerEvent) {
         self.event_tx.send(Arc::new(event)).unwrap();
     }
+
+    /// Hold back the reply to dial requests until they are released, i.e. keep the dials in flight
+    #[allow(dead_code)]
+    pub fn set_hold_dials(&self, hold: bool) {
+        self.hold_dials.store(hold, Ordering::SeqCst);
+    }
+
+    /// The number of dials that are currently held in flight
+    #[allow(dead_code)]
+    pub async fn held_dial_count(&self) -> usize {
+        self.held_dials.lock().await.len()
+    }
+
+    /// Completes all held dials, returning how many were released
+    #[allow(dead_code)]
+    pub async fn release_held_dials(&self) -> usize {
+        let held = self.held_dials.lock().await.drain(..).collect::<Vec<_>>();
+        let num_released = held.len();
+        for (node_id, reply_tx) in held {
+            let _result = reply_tx.send(self.dial_result(&node_id).await);
+        }
+        num_released
+    }
+
+    async fn dial_result(&self, node_id: &NodeId) -> Result<PeerConnection, ConnectionManagerError> {
+        self.active_conns
+            .lock()
+            .await
+            .get(node_id)
+            .cloned()
+            .ok_or(ConnectionManagerError::DialConnectFailedAllAddresses)
+    }
 }
 
 pub struct ConnectionManagerMock {
@@ -132,15 +170,14 @@ impl ConnectionManagerMock {
         self.state.add_call(format!("{:?}", req)).await;
         match req {
             DialPeer { node_id, mut reply_tx } => {
+                if self.state.hold_dials.load(Ordering::SeqCst) {
+                    if let Some(reply_tx) = reply_tx {
+                        self.state.held_dials.lock().await.push((node_id, reply_tx));
+                    }
+                    return;
+                }
                 // Send Ok(&mut conn) if we have an active connection, otherwise Err(DialConnectFailedAllAddresses)
-                let result = self
-                    .state
-                    .active_conns
-                    .lock()
-                    .await
-                    .get(&node_id)
-                    .cloned()
-                    .ok_or(ConnectionManagerError::DialConnectFailedAllAddresses);
+                let result = self.state.dial_result(&node_id).await;
                 let _result = reply_tx.take().map(|tx| tx.send(result));
             },
             CancelDial(_) => {},
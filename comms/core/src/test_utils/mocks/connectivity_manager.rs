@@ -64,6 +64,8 @@ struct State {
     selected_connections: Vec<PeerConnection>,
     banned_peers: Vec<(NodeId, Duration, String)>,
     connectivity_status: ConnectivityStatus,
+    hang_active_connections: bool,
+    hung_active_connections_replies: Vec<oneshot::Sender<Vec<PeerConnection>>>,
 }
 
 impl ConnectivityManagerMockState {
@@ -186,6 +188,14 @@ impl ConnectivityManagerMockState {
         .await
     }
 
+    /// When set, `GetActiveConnections` requests are never answered, simulating a hung connectivity manager
+    pub async fn set_hang_active_connections(&self, hang: bool) {
+        self.with_state(|state| {
+            state.hang_active_connections = hang;
+        })
+        .await
+    }
+
     pub fn publish_event(&self, event: ConnectivityEvent) {
         self.event_tx.send(event).unwrap();
     }
@@ -293,7 +303,13 @@ impl ConnectivityManagerMock {
             RemovePeerFromAllowList(_) => {},
             GetActiveConnections(reply) => {
                 self.state
-                    .with_state(|state| reply.send(state.active_conns.values().cloned().collect()).unwrap())
+                    .with_state(|state| {
+                        if state.hang_active_connections {
+                            state.hung_active_connections_replies.push(reply);
+                        } else {
+                            reply.send(state.active_conns.values().cloned().collect()).unwrap();
+                        }
+                    })
                     .await;
             },
             WaitStarted(reply) => reply.send(()).unwrap(),
@@ -53,6 +53,13 @@ use crate::{
 static ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
 pub fn create_dummy_peer_connection(node_id: NodeId) -> (PeerConnection, mpsc::Receiver<PeerConnectionRequest>) {
+    create_dummy_peer_connection_with_direction(node_id, ConnectionDirection::Inbound)
+}
+
+pub fn create_dummy_peer_connection_with_direction(
+    node_id: NodeId,
+    direction: ConnectionDirection,
+) -> (PeerConnection, mpsc::Receiver<PeerConnectionRequest>) {
     let (tx, rx) = mpsc::channel(1);
     let addr = Multiaddr::from_str("/ip4/23.23.23.23/tcp/80").unwrap();
     (
@@ -62,8 +69,9 @@ pub fn create_dummy_peer_connection(node_id: NodeId) -> (PeerConnection, mpsc::R
             node_id,
             PeerFeatures::COMMUNICATION_NODE,
             addr,
-            ConnectionDirection::Inbound,
+            direction,
             AtomicRefCounter::new(),
+            Vec::new(),
         ),
         rx,
     )
@@ -101,6 +109,7 @@ pub async fn create_peer_connection_mock_pair(
             listen_addr.clone(),
             ConnectionDirection::Inbound,
             mock_state_in.substream_counter(),
+            peer2.supported_protocols,
         ),
         mock_state_in,
         PeerConnection::new(
@@ -111,6 +120,7 @@ pub async fn create_peer_connection_mock_pair(
             listen_addr,
             ConnectionDirection::Outbound,
             mock_state_out.substream_counter(),
+            peer1.supported_protocols,
         ),
         mock_state_out,
     )
@@ -220,7 +230,7 @@ impl PeerConnectionMock {
                     reply_tx.send(Err(err)).unwrap();
                 },
             },
-            Disconnect(_, reply_tx, _minimized) => {
+            Disconnect(_, reply_tx, _minimized, _reason) => {
                 self.receiver.close();
                 reply_tx.send(self.state.disconnect().await).unwrap();
             },
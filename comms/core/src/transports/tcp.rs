@@ -26,10 +26,12 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::{ready, FutureExt};
 use multiaddr::Multiaddr;
+use socket2::{SockRef, TcpKeepalive};
 use tokio::net::{TcpListener, TcpStream};
 use tokio_stream::Stream;
 
@@ -45,8 +47,7 @@ pub struct TcpTransport {
     // recv_buffer_size: Option<usize>,
     // send_buffer_size: Option<usize>,
     ttl: Option<u32>,
-    // #[allow(clippy::option_option)]
-    // keepalive: Option<Option<Duration>>,
+    keepalive: Option<Duration>,
     nodelay: Option<bool>,
     dns_resolver: DnsResolverRef,
 }
@@ -62,7 +63,7 @@ impl TcpTransport {
     setter_mut!(set_ttl, ttl, Option<u32>);
 
     // #[doc("Sets `SO_KEEPALIVE` i.e. the interval to send keepalive probes, or None to disable.")]
-    // setter_mut!(set_keepalive, keepalive, Option<Option<Duration>>);
+    setter_mut!(set_keepalive, keepalive, Option<Duration>);
 
     // #[doc("Sets `TCP_NODELAY` i.e disable Nagle's algorithm if set to true.")]
     setter_mut!(set_nodelay, nodelay, Option<bool>);
@@ -81,10 +82,18 @@ impl TcpTransport {
 
     /// Apply socket options to `TcpStream`.
     fn configure(&self, socket: &TcpStream) -> io::Result<()> {
-        // https://github.com/rust-lang/rust/issues/69774
-        // if let Some(keepalive) = self.keepalive {
-        //     socket.set_keepalive(keepalive)?;
-        // }
+        if let Some(interval) = self.keepalive {
+            // The std/tokio TcpStream does not expose SO_KEEPALIVE (https://github.com/rust-lang/rust/issues/69774)
+            let keepalive = TcpKeepalive::new().with_time(interval);
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "windows"
+            ))]
+            let keepalive = keepalive.with_interval(interval);
+            SockRef::from(socket).set_tcp_keepalive(&keepalive)?;
+        }
 
         if let Some(ttl) = self.ttl {
             socket.set_ttl(ttl)?;
@@ -102,6 +111,7 @@ impl Default for TcpTransport {
     fn default() -> Self {
         Self {
             ttl: None,
+            keepalive: None,
             nodelay: None,
             dns_resolver: Arc::new(SystemDnsResolver),
         }
@@ -197,4 +207,22 @@ mod test {
         assert_eq!(tcp.nodelay, Some(true));
         assert_eq!(tcp.ttl, Some(789));
     }
+
+    #[tokio::test]
+    async fn it_applies_the_keepalive_option_to_the_socket() {
+        let mut tcp = TcpTransport::new();
+        tcp.set_keepalive(Duration::from_secs(30));
+        let (_listener, addr) = tcp.listen(&"/ip4/127.0.0.1/tcp/0".parse().unwrap()).await.unwrap();
+        let socket = tcp.dial(&addr).await.unwrap();
+
+        let sock_ref = SockRef::from(&socket);
+        assert!(sock_ref.keepalive().unwrap());
+        #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+        assert_eq!(sock_ref.keepalive_time().unwrap(), Duration::from_secs(30));
+
+        // Keepalive is left at the OS default when not set
+        let tcp = TcpTransport::new();
+        let socket = tcp.dial(&addr).await.unwrap();
+        assert!(!SockRef::from(&socket).keepalive().unwrap());
+    }
 }
@@ -106,6 +106,7 @@ impl GreetingRpc for GreetingService {
             item_size,
             num_items,
             delay_ms: delay_secs,
+            compress,
         } = request.into_message();
         let (tx, rx) = mpsc::channel(10);
         let t = std::time::Instant::now();
@@ -141,7 +142,12 @@ impl GreetingRpc for GreetingService {
                 );
             }
         });
-        Ok(Streaming::new(rx))
+        let stream = Streaming::new(rx);
+        if compress {
+            Ok(stream.compressed())
+        } else {
+            Ok(stream)
+        }
     }
 
     async fn slow_response(&self, request: Request<u64>) -> Result<Response<()>, RpcStatus> {
@@ -174,4 +180,6 @@ pub struct StreamLargeItemsRequest {
     pub item_size: u64,
     #[prost(uint64, tag = "4")]
     pub delay_ms: u64,
+    #[prost(bool, tag = "5")]
+    pub compress: bool,
 }
@@ -20,16 +20,37 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::sync::Arc;
+use std::{
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
+use futures::{stream::BoxStream, StreamExt};
 use rand::rngs::OsRng;
-use tari_comms::{peer_manager::PeerFeatures, types::CommsDatabase, CommsBuilder, NodeIdentity, UnspawnedCommsNode};
+use tari_comms::{
+    multiaddr::Multiaddr,
+    peer_manager::PeerFeatures,
+    transports::{TcpTransport, Transport},
+    types::CommsDatabase,
+    CommsBuilder,
+    NodeIdentity,
+    UnspawnedCommsNode,
+};
 use tari_shutdown::ShutdownSignal;
 use tari_storage::{
     lmdb_store::{LMDBBuilder, LMDBConfig},
     LMDBWrapper,
 };
 use tari_test_utils::{paths::create_temporary_data_path, random};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+};
 
 pub fn create_peer_storage() -> CommsDatabase {
     let database_name = random::string(8);
@@ -61,3 +82,75 @@ pub fn create_comms(signal: ShutdownSignal) -> UnspawnedCommsNode {
         .build()
         .unwrap()
 }
+
+/// A TCP transport that counts the total number of bytes read from all of its sockets
+#[derive(Clone, Default)]
+pub struct CountingTransport {
+    inner: TcpTransport,
+    bytes_read: Arc<AtomicUsize>,
+}
+
+impl CountingTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read.load(Ordering::SeqCst)
+    }
+}
+
+#[tari_comms::async_trait]
+impl Transport for CountingTransport {
+    type Error = io::Error;
+    type Listener = BoxStream<'static, io::Result<(CountingStream, Multiaddr)>>;
+    type Output = CountingStream;
+
+    async fn listen(&self, addr: &Multiaddr) -> Result<(Self::Listener, Multiaddr), Self::Error> {
+        let (listener, addr) = self.inner.listen(addr).await?;
+        let bytes_read = self.bytes_read.clone();
+        let listener = listener
+            .map(move |result| result.map(|(socket, addr)| (CountingStream::new(socket, bytes_read.clone()), addr)))
+            .boxed();
+        Ok((listener, addr))
+    }
+
+    async fn dial(&self, addr: &Multiaddr) -> Result<Self::Output, Self::Error> {
+        let socket = self.inner.dial(addr).await?;
+        Ok(CountingStream::new(socket, self.bytes_read.clone()))
+    }
+}
+
+pub struct CountingStream {
+    inner: TcpStream,
+    bytes_read: Arc<AtomicUsize>,
+}
+
+impl CountingStream {
+    fn new(inner: TcpStream, bytes_read: Arc<AtomicUsize>) -> Self {
+        Self { inner, bytes_read }
+    }
+}
+
+impl AsyncRead for CountingStream {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        self.bytes_read.fetch_add(buf.filled().len() - filled, Ordering::SeqCst);
+        result
+    }
+}
+
+impl AsyncWrite for CountingStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
@@ -24,7 +24,18 @@ use std::time::Duration;
 
 use futures::StreamExt;
 use tari_comms::{
-    protocol::rpc::{RpcServer, RpcServerHandle},
+    protocol::{
+        rpc::{
+            ByteCounts,
+            HandshakeRejectReason,
+            NamedProtocolService,
+            RpcError,
+            RpcServer,
+            RpcServerHandle,
+            SessionInfo,
+        },
+        ProtocolId,
+    },
     transports::TcpTransport,
     CommsNode,
     Minimized,
@@ -35,7 +46,7 @@ use tokio::time;
 
 use crate::tests::{
     greeting_service::{GreetingClient, GreetingServer, GreetingService, SayHelloRequest, StreamLargeItemsRequest},
-    helpers::create_comms,
+    helpers::{create_comms, CountingTransport},
 };
 
 async fn spawn_node(signal: ShutdownSignal) -> (CommsNode, RpcServerHandle) {
@@ -89,6 +100,60 @@ async fn spawn_culling_node(signal: ShutdownSignal, sessions: usize, culling: bo
     (comms, rpc_server_hnd)
 }
 
+async fn spawn_rate_limited_node(
+    signal: ShutdownSignal,
+    method_id: u32,
+    max_per_sec: u32,
+) -> (CommsNode, RpcServerHandle) {
+    let rpc_server = RpcServer::builder()
+        .with_method_rate_limit(method_id, max_per_sec)
+        .finish()
+        .add_service(GreetingServer::new(GreetingService::default()));
+
+    let rpc_server_hnd = rpc_server.get_handle();
+    let mut comms = create_comms(signal)
+        .add_rpc_server(rpc_server)
+        .spawn_with_transport(TcpTransport::new())
+        .await
+        .unwrap();
+
+    let address = comms
+        .connection_manager_requester()
+        .wait_until_listening()
+        .await
+        .unwrap();
+    comms
+        .node_identity()
+        .set_public_addresses(vec![address.bind_address().clone()]);
+
+    (comms, rpc_server_hnd)
+}
+
+async fn spawn_byte_quota_node(signal: ShutdownSignal, quota: u64) -> (CommsNode, RpcServerHandle) {
+    let rpc_server = RpcServer::builder()
+        .with_session_byte_quota(quota)
+        .finish()
+        .add_service(GreetingServer::new(GreetingService::default()));
+
+    let rpc_server_hnd = rpc_server.get_handle();
+    let mut comms = create_comms(signal)
+        .add_rpc_server(rpc_server)
+        .spawn_with_transport(TcpTransport::new())
+        .await
+        .unwrap();
+
+    let address = comms
+        .connection_manager_requester()
+        .wait_until_listening()
+        .await
+        .unwrap();
+    comms
+        .node_identity()
+        .set_public_addresses(vec![address.bind_address().clone()]);
+
+    (comms, rpc_server_hnd)
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn rpc_server_can_request_drop_sessions() {
     // env_logger::init(); // Set `$env:RUST_LOG = "trace"`
@@ -265,7 +330,13 @@ async fn rpc_server_can_prioritize_old_connections() {
                     "Handshake error: RPC handshake was explicitly rejected: no more RPC server sessions available: \
                      session limit reached"
                         .to_string()
-                )
+                );
+                let reason = e.handshake_reject_reason().unwrap();
+                assert!(matches!(
+                    reason,
+                    HandshakeRejectReason::NoServerSessionsAvailable("session limit reached")
+                ));
+                assert!(reason.is_retryable());
             },
         }
     }
@@ -516,6 +587,7 @@ async fn client_prematurely_ends_session() {
                 num_items: 100,
                 item_size: 2300 * 1024,
                 delay_ms: 50,
+                compress: false,
             })
             .await
             .unwrap();
@@ -548,3 +620,302 @@ async fn client_prematurely_ends_session() {
         interval = Duration::from_millis(1000)
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rpc_server_rate_limits_method_calls() {
+    // env_logger::init(); // Set `$env:RUST_LOG = "trace"`
+    let shutdown = Shutdown::new();
+    // `say_hello` is method 1
+    let (say_hello_method, max_per_sec, number_of_calls) = (1, 3, 10);
+    let (node1, _rpc_server1) = spawn_node(shutdown.to_signal()).await;
+    let (node2, _rpc_server2) = spawn_rate_limited_node(shutdown.to_signal(), say_hello_method, max_per_sec).await;
+
+    node1
+        .peer_manager()
+        .add_peer(node2.node_identity().to_peer())
+        .await
+        .unwrap();
+
+    let mut conn1_2 = node1
+        .connectivity()
+        .dial_peer(node2.node_identity().node_id().clone())
+        .await
+        .unwrap();
+    let mut client = conn1_2.connect_rpc::<GreetingClient>().await.unwrap();
+
+    let mut num_ok = 0;
+    let mut num_rate_limited = 0;
+    for _ in 0..number_of_calls {
+        match client
+            .say_hello(SayHelloRequest {
+                name: "Bob".to_string(),
+                language: 0,
+            })
+            .await
+        {
+            Ok(_) => num_ok += 1,
+            Err(RpcError::RequestFailed(status)) if status.is_rate_limited() => num_rate_limited += 1,
+            Err(err) => panic!("Unexpected error: {}", err),
+        }
+    }
+    assert_eq!(num_ok, max_per_sec);
+    assert_eq!(num_rate_limited, number_of_calls - max_per_sec);
+
+    // The session remains usable once the rate limit window has passed
+    time::sleep(Duration::from_millis(1100)).await;
+    assert!(client
+        .say_hello(SayHelloRequest {
+            name: "Bob".to_string(),
+            language: 0
+        })
+        .await
+        .is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn peer_connection_exposes_the_advertised_rpc_protocols() {
+    let shutdown = Shutdown::new();
+    let (node1, _rpc_server1) = spawn_node(shutdown.to_signal()).await;
+    let (node2, _rpc_server2) = spawn_node(shutdown.to_signal()).await;
+
+    node1
+        .peer_manager()
+        .add_peer(node2.node_identity().to_peer())
+        .await
+        .unwrap();
+
+    let conn1_2 = node1
+        .connectivity()
+        .dial_peer(node2.node_identity().node_id().clone())
+        .await
+        .unwrap();
+
+    let greeting_protocol = ProtocolId::from_static(GreetingClient::PROTOCOL_NAME);
+    assert!(conn1_2.supported_rpc_protocols().contains(&greeting_protocol));
+    assert!(conn1_2.supports_rpc::<GreetingClient>());
+    assert!(!conn1_2.supports_protocol(&ProtocolId::from_static(b"t/not-registered/1")));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rpc_server_reports_the_negotiated_protocol_version() {
+    let shutdown = Shutdown::new();
+    let (node1, _rpc_server1) = spawn_node(shutdown.to_signal()).await;
+    let (node2, mut rpc_server2) = spawn_node(shutdown.to_signal()).await;
+
+    node1
+        .peer_manager()
+        .add_peer(node2.node_identity().to_peer())
+        .await
+        .unwrap();
+
+    let mut conn1_2 = node1
+        .connectivity()
+        .dial_peer(node2.node_identity().node_id().clone())
+        .await
+        .unwrap();
+    let _client = conn1_2
+        .connect_rpc_using_builder(GreetingClient::builder().with_rpc_version(0))
+        .await
+        .unwrap();
+
+    let sessions = rpc_server2.list_active_sessions().await.unwrap();
+    assert_eq!(sessions, vec![SessionInfo {
+        node_id: node1.node_identity().node_id().clone(),
+        protocol: ProtocolId::from_static(GreetingClient::PROTOCOL_NAME),
+        version: 0,
+        bytes: ByteCounts::default(),
+    }]);
+    assert_eq!(rpc_server2.get_num_active_sessions_for_version(0).await.unwrap(), 1);
+    assert_eq!(rpc_server2.get_num_active_sessions_for_version(1).await.unwrap(), 0);
+
+    // A client that only offers an unsupported version is rejected and no session is recorded
+    assert!(conn1_2
+        .connect_rpc_using_builder(GreetingClient::builder().with_rpc_version(1))
+        .await
+        .is_err());
+    assert_eq!(rpc_server2.list_active_sessions().await.unwrap().len(), 1);
+}
+
+async fn stream_zeros_and_count_bytes_read(
+    client: &mut GreetingClient,
+    transport: &CountingTransport,
+    num_items: usize,
+    item_size: usize,
+    compress: bool,
+) -> usize {
+    let start = transport.bytes_read();
+    let items = client
+        .stream_large_items(StreamLargeItemsRequest {
+            id: 1,
+            num_items: num_items as u64,
+            item_size: item_size as u64,
+            delay_ms: 0,
+            compress,
+        })
+        .await
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(items.len(), num_items);
+    for item in items {
+        assert_eq!(item.unwrap(), vec![0u8; item_size]);
+    }
+    transport.bytes_read() - start
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rpc_server_compresses_streamed_responses_when_requested() {
+    const NUM_ITEMS: usize = 10;
+    const ITEM_SIZE: usize = 64 * 1024;
+
+    let shutdown = Shutdown::new();
+    let transport = CountingTransport::new();
+    let mut node1 = create_comms(shutdown.to_signal())
+        .spawn_with_transport(transport.clone())
+        .await
+        .unwrap();
+    node1
+        .connection_manager_requester()
+        .wait_until_listening()
+        .await
+        .unwrap();
+    let (node2, _rpc_server2) = spawn_node(shutdown.to_signal()).await;
+
+    node1
+        .peer_manager()
+        .add_peer(node2.node_identity().to_peer())
+        .await
+        .unwrap();
+
+    let mut conn1_2 = node1
+        .connectivity()
+        .dial_peer(node2.node_identity().node_id().clone())
+        .await
+        .unwrap();
+    let mut client = conn1_2.connect_rpc::<GreetingClient>().await.unwrap();
+
+    let uncompressed = stream_zeros_and_count_bytes_read(&mut client, &transport, NUM_ITEMS, ITEM_SIZE, false).await;
+    let compressed = stream_zeros_and_count_bytes_read(&mut client, &transport, NUM_ITEMS, ITEM_SIZE, true).await;
+    assert!(uncompressed >= NUM_ITEMS * ITEM_SIZE);
+    assert!(
+        compressed * 10 < uncompressed,
+        "compressed stream read {} bytes, uncompressed stream read {} bytes",
+        compressed,
+        uncompressed
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rpc_server_accounts_session_bytes_and_enforces_the_quota() {
+    const ITEM_SIZE: u64 = 16 * 1024;
+    const QUOTA: u64 = 512 * 1024;
+
+    let shutdown = Shutdown::new();
+    let (node1, _rpc_server1) = spawn_node(shutdown.to_signal()).await;
+    let (node2, mut rpc_server2) = spawn_byte_quota_node(shutdown.to_signal(), QUOTA).await;
+    let node1_id = node1.node_identity().node_id().clone();
+
+    node1
+        .peer_manager()
+        .add_peer(node2.node_identity().to_peer())
+        .await
+        .unwrap();
+
+    let mut conn1_2 = node1
+        .connectivity()
+        .dial_peer(node2.node_identity().node_id().clone())
+        .await
+        .unwrap();
+    let mut client = conn1_2.connect_rpc::<GreetingClient>().await.unwrap();
+
+    // A stream within the quota is counted
+    let items = client
+        .stream_large_items(StreamLargeItemsRequest {
+            id: 1,
+            num_items: 10,
+            item_size: ITEM_SIZE,
+            delay_ms: 0,
+            compress: false,
+        })
+        .await
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    assert!(items.iter().all(|item| item.is_ok()));
+
+    let sessions = rpc_server2.list_active_sessions().await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    let bytes = sessions[0].bytes;
+    assert!(bytes.sent >= 10 * ITEM_SIZE);
+    assert!(bytes.received > 0);
+    assert_eq!(rpc_server2.get_bytes_for(node1_id.clone()).await.unwrap(), bytes);
+
+    // A stream that would exceed the quota is cut off and the session is closed
+    let items = client
+        .stream_large_items(StreamLargeItemsRequest {
+            id: 2,
+            num_items: 40,
+            item_size: ITEM_SIZE,
+            delay_ms: 0,
+            compress: false,
+        })
+        .await
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    assert!(items.len() < 40);
+    let status = items.last().unwrap().as_ref().unwrap_err();
+    assert!(status.is_quota_exceeded());
+
+    async_assert_eventually!(
+        rpc_server2.get_num_active_sessions_for(node1_id.clone()).await.unwrap(),
+        expect = 0,
+        max_attempts = 20,
+        interval = Duration::from_millis(100)
+    );
+    let bytes = rpc_server2.get_bytes_for(node1_id).await.unwrap();
+    assert!(bytes.total() <= QUOTA);
+    assert!(bytes.sent > 10 * ITEM_SIZE);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rpc_server_lists_its_registered_services() {
+    let shutdown = Shutdown::new();
+    let (_node, mut rpc_server) = spawn_node(shutdown.to_signal()).await;
+
+    let services = rpc_server.list_services().await.unwrap();
+    assert_eq!(services, vec![ProtocolId::from_static(GreetingClient::PROTOCOL_NAME)]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rpc_server_reports_the_active_sessions_of_all_peers() {
+    let shutdown = Shutdown::new();
+    let (node1, _rpc_server1) = spawn_node(shutdown.to_signal()).await;
+    let (node2, mut rpc_server2) = spawn_node(shutdown.to_signal()).await;
+    let (node3, _rpc_server3) = spawn_node(shutdown.to_signal()).await;
+
+    assert_eq!(rpc_server2.get_num_active_sessions().await.unwrap(), 0);
+    assert!(rpc_server2.get_active_sessions_by_peer().await.unwrap().is_empty());
+
+    let mut clients = Vec::new();
+    for (node, num_sessions) in [(&node1, 2), (&node3, 1)] {
+        node.peer_manager()
+            .add_peer(node2.node_identity().to_peer())
+            .await
+            .unwrap();
+        let mut conn = node
+            .connectivity()
+            .dial_peer(node2.node_identity().node_id().clone())
+            .await
+            .unwrap();
+        for _ in 0..num_sessions {
+            clients.push(conn.connect_rpc::<GreetingClient>().await.unwrap());
+        }
+    }
+
+    assert_eq!(rpc_server2.get_num_active_sessions().await.unwrap(), 3);
+    let sessions_by_peer = rpc_server2.get_active_sessions_by_peer().await.unwrap();
+    assert_eq!(sessions_by_peer.len(), 2);
+    assert_eq!(sessions_by_peer[node1.node_identity().node_id()], 2);
+    assert_eq!(sessions_by_peer[node3.node_identity().node_id()], 1);
+}
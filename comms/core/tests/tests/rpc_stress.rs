@@ -139,6 +139,7 @@ async fn run_stress_test(test_params: Params) {
                     num_items: num_items as u64,
                     item_size: payload_size as u64,
                     delay_ms: 0,
+                    compress: false,
                 })
                 .await
                 .unwrap();
@@ -632,7 +632,7 @@ fn connection_manager_logger(
                     println!("'{}' connected to '{}'", node_name, get_name(conn.peer_node_id()),);
                 },
             },
-            PeerDisconnected(_, node_id, minimized) => {
+            PeerDisconnected(_, node_id, minimized, _) => {
                 println!(
                     "'{}' disconnected from '{}', {:?}",
                     get_name(node_id),
@@ -194,6 +194,7 @@ async fn replace_peer_when_peer_goes_offline() {
     connectivity.publish_event(ConnectivityEvent::PeerDisconnected(
         node_identities[4].node_id().clone(),
         Minimized::No,
+        None,
     ));
 
     async_assert!(